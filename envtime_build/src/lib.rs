@@ -0,0 +1,56 @@
+//! # envtime_build
+//!
+//! Build script helpers for crates using `envtime`.
+//!
+//! Values `envtime` bakes in at compile time don't make Cargo rebuild anything when they change,
+//! so a stale value silently persists until something else triggers a build. A procedural macro
+//! can't tell Cargo what it read (the compiler's own `proc_macro::tracked_env` is still unstable),
+//! so instead the crate's build script tells Cargo which variables to watch.
+//!
+//! ## Examples
+//!
+//! In the `main` of `build.rs`, next to the `Cargo.toml` of the crate using the macros:
+//! ```
+//! // Rebuild when either variable is set, changed or unset
+//! envtime_build::track("PORT");
+//! envtime_build::track("LOG_LEVEL");
+//! // And when the `.env` files the macros also read are edited
+//! envtime_build::track_dotenv();
+//! ```
+//!
+//! With `envtime_build` listed under `[build-dependencies]`, running `PORT=8080 cargo build` and then
+//! `PORT=9090 cargo build` recompiles the crate the second time, baking in the new value.
+//! Note that once a build script prints any of these directives, Cargo no longer reruns it for
+//! every change in the package, only for the tracked variables and files.
+
+use std::path::Path;
+
+/// Rebuilds the crate whenever the environment variable is set, changed or unset
+///
+/// Prints a `cargo:rerun-if-env-changed` directive, so it has to be called from a build script.
+pub fn track(name: &str) {
+    println!("cargo:rerun-if-env-changed={}", name);
+}
+
+/// Rebuilds the crate whenever one of its `.env` files is edited or removed, or `ENVTIME_PROFILE` changes
+///
+/// The files are the ones `envtime` reads next to the `Cargo.toml` of the crate being built: `.env`, `.env.local`,
+/// and for the `ENVTIME_PROFILE` of the build, `.env.{profile}` and `.env.{profile}.local`.
+/// A file is only tracked if it exists, since Cargo reruns the build script on every build for a missing file,
+/// so a newly created file is picked up by the next build that happens for another reason.
+pub fn track_dotenv() {
+    track("ENVTIME_PROFILE");
+    let dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+    let mut names = vec![String::from(".env"), String::from(".env.local")];
+    let profile = std::env::var("ENVTIME_PROFILE").ok().map(|profile| profile.trim().to_string());
+    if let Some(profile) = profile.filter(|profile| !profile.is_empty()) {
+        names.push(format!(".env.{}", profile));
+        names.push(format!(".env.{}.local", profile));
+    }
+    for name in names {
+        let path = Path::new(&dir).join(name);
+        if path.is_file() {
+            println!("cargo:rerun-if-changed={}", path.display());
+        }
+    }
+}
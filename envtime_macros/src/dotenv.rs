@@ -0,0 +1,104 @@
+//! Compile time lookups in the `.env` files at the root of the crate being compiled
+//!
+//! The files are layered, each overriding the ones before it: `.env`, then `.env.local`, and with
+//! `ENVTIME_PROFILE` set during the build, `.env.{profile}` and then `.env.{profile}.local`.
+
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+/// Merged `.env` files by crate root, so every macro invocation in a crate reads the files only once
+static CACHE: OnceLock<Mutex<HashMap<PathBuf, HashMap<String, String>>>> = OnceLock::new();
+
+/// Gets a variable from the `.env` files next to the `Cargo.toml` of the crate being compiled
+pub(crate) fn get(name: &str) -> Option<String> {
+    with_vars(|vars| vars.get(name).cloned())?
+}
+
+/// Gets every variable from the `.env` files whose name starts with a prefix
+pub(crate) fn with_prefix(prefix: &str) -> Vec<(String, String)> {
+    with_vars(|vars| vars.iter()
+        .filter(|(key, _)| key.starts_with(prefix))
+        .map(|(key, val)| (key.clone(), val.clone()))
+        .collect())
+        .unwrap_or_default()
+}
+
+/// Runs a closure on the variables of the crate being compiled, loading its `.env` files the first time
+///
+/// Without `CARGO_MANIFEST_DIR` there's no crate root to find the files in, so there are no variables.
+fn with_vars<T>(f: impl FnOnce(&HashMap<String, String>) -> T) -> Option<T> {
+    let manifest_dir = match env::var_os("CARGO_MANIFEST_DIR") {
+        Some(manifest_dir) => PathBuf::from(manifest_dir),
+        None => {
+            crate::warn_once("CARGO_MANIFEST_DIR isn't set during the build, `.env` files are skipped");
+            return None
+        }
+    };
+    let mut cache = CACHE.get_or_init(Default::default).lock().ok()?;
+    let vars = cache.entry(manifest_dir)
+        .or_insert_with_key(|dir| files(dir).iter().flat_map(|path| load(path)).collect());
+    Some(f(vars))
+}
+
+/// The `.env` files of a crate root, lowest precedence first
+fn files(dir: &Path) -> Vec<PathBuf> {
+    let mut names = vec![String::from(".env"), String::from(".env.local")];
+    let profile = env::var("ENVTIME_PROFILE").ok().map(|profile| profile.trim().to_string());
+    if let Some(profile) = profile.filter(|profile| !profile.is_empty()) {
+        names.push(format!(".env.{}", profile));
+        names.push(format!(".env.{}.local", profile));
+    }
+    names.iter().map(|name| dir.join(name)).collect()
+}
+
+/// Reads a `.env` file, a missing or unreadable file has no variables
+///
+/// A file that isn't valid UTF-8 is read with the invalid bytes replaced by `U+FFFD`, with a warning.
+fn load(path: &Path) -> HashMap<String, String> {
+    let bytes = match fs::read(path) {
+        Ok(bytes) => bytes,
+        Err(_) => return HashMap::new()
+    };
+    match String::from_utf8(bytes) {
+        Ok(s) => parse(&s),
+        Err(err) => {
+            crate::warn_once(&format!("{} isn't valid UTF-8, invalid bytes are replaced: {}", path.display(), err.utf8_error()));
+            parse(&String::from_utf8_lossy(err.as_bytes()))
+        }
+    }
+}
+
+/// Parses `KEY=VALUE` lines, as written in shell scripts
+///
+/// Blank lines, `#` comments and lines without a `=` are skipped, a leading `export` is ignored,
+/// and whitespace around keys and values is trimmed.
+fn parse(s: &str) -> HashMap<String, String> {
+    s.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| match line.strip_prefix("export") {
+            Some(rest) if rest.starts_with(char::is_whitespace) => rest.trim_start(),
+            _ => line
+        })
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, val)| (key.trim().to_string(), parse_value(val.trim())))
+        .filter(|(key, _)| !key.is_empty())
+        .collect()
+}
+
+/// A value in matching `"` or `'` quotes is taken verbatim up to the closing quote,
+/// otherwise a `#` after whitespace starts a comment
+fn parse_value(val: &str) -> String {
+    for quote in ['"', '\''] {
+        if let Some((quoted, _)) = val.strip_prefix(quote).and_then(|rest| rest.split_once(quote)) {
+            return quoted.to_string()
+        }
+    }
+    let end = val.char_indices()
+        .find(|&(i, c)| c == '#' && val[..i].ends_with(char::is_whitespace))
+        .map_or(val.len(), |(i, _)| i);
+    val[..end].trim_end().to_string()
+}
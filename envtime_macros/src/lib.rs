@@ -0,0 +1,5215 @@
+//! Procedural macros for `envtime`, which re-exports them along with its runtime helpers.
+//!
+//! Depend on `envtime` rather than on this crate directly, see its documentation for the macros.
+
+extern crate proc_macro;
+
+mod dotenv;
+#[cfg(feature = "manifest")]
+mod manifest;
+
+use proc_macro::TokenStream;
+use std::env;
+use syn::{parse_macro_input, Lit, LitStr, Token, LitBool, LitByte, LitInt, Type};
+use quote::{quote};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+
+/// Gets a environment variable as a String either at compile or runtime
+/// # Example
+/// ```
+/// use std::env;
+/// use envtime::*;
+///
+/// // Assuming the variable isn't set
+/// let var = envtime!("DOMAIN");
+/// assert_eq!(var, None);
+/// let var = var.unwrap_or_else(|| String::from("example.com"));
+/// assert_eq!(var, String::from("example.com"));
+/// ```
+///
+/// Several names can be given, like a new and a legacy name for the same setting.
+/// The first one that is set wins, checked in order at compile time and then at runtime.
+/// ```
+/// use std::env;
+/// use envtime::*;
+///
+/// // Assuming neither variable is set at compile time
+/// env::set_var("LEGACY_DOMAIN", "example.net");
+/// assert_eq!(envtime!("DOMAIN_V2", "LEGACY_DOMAIN"), Some(String::from("example.net")));
+/// ```
+///
+/// With the `any_case` flag, a miss on the exact name also tries the uppercased and then the
+/// lowercased name, both at compile time and at runtime. No other casings are tried.
+/// ```
+/// use std::env;
+/// use envtime::*;
+///
+/// // Assuming the variable isn't set at compile time
+/// env::set_var("DOMAIN_NAME", "example.org");
+/// assert_eq!(envtime!("domain_name", any_case), Some(String::from("example.org")));
+/// ```
+///
+/// A name can also be a path to a `&str` const. Its value isn't known while the macro expands,
+/// so such a name is only ever looked up at runtime, as are any names after it.
+/// ```
+/// use std::env;
+/// use envtime::*;
+///
+/// const DOMAIN_VAR: &str = "SERVICE_DOMAIN";
+///
+/// env::set_var("SERVICE_DOMAIN", "example.io");
+/// assert_eq!(envtime!(DOMAIN_VAR), Some(String::from("example.io")));
+/// ```
+///
+/// A name that isn't set falls back to the trimmed contents of the file its `_FILE` sibling points at,
+/// the convention for secrets mounted as files, read like `envtime_file!` does. A different suffix can be
+/// given with `file_suffix = "_PATH"`, and an empty one turns the fallback off. A file that can't be read
+/// during the build is left for runtime with a warning, instead of failing the build like `envtime_file!`.
+/// ```
+/// use std::env;
+/// use envtime::*;
+///
+/// // Assuming neither variable is set at compile time
+/// env::set_var("API_TOKEN_PATH", concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml"));
+/// assert!(envtime!("API_TOKEN", file_suffix = "_PATH").is_some());
+/// assert_eq!(envtime!("API_TOKEN"), None);
+/// ```
+///
+/// With `unquote = true`, a single matching pair of `"` or `'` quotes around the value is stripped,
+/// for values that were copied along with their quotes. Quotes inside the value are kept.
+/// ```
+/// use std::env;
+/// use envtime::*;
+///
+/// // Assuming the variable isn't set at compile time
+/// env::set_var("USER_NAME", "\"alice\"");
+/// assert_eq!(envtime!("USER_NAME", unquote = true), Some(String::from("alice")));
+/// assert_eq!(envtime!("USER_NAME"), Some(String::from("\"alice\"")));
+/// ```
+///
+/// With `interpolate = true`, `${NAME}` references in the value are replaced by the value of `NAME`, expanded in turn.
+/// They're looked up during the build, `.env` file included, for a baked value and at runtime otherwise. A baked value referencing a variable that isn't set during
+/// the build, or referring back to itself, fails the build. At runtime such a reference is left as written,
+/// see `envtime::runtime::interpolate`. Referenced names are used as written, without `ENVTIME_PREFIX`.
+/// ```
+/// use std::env;
+/// use envtime::*;
+///
+/// // Assuming the variables aren't set at compile time
+/// env::set_var("APP_HOST", "example.com");
+/// env::set_var("APP_URL", "https://${APP_HOST}/${APP_PATH}");
+/// assert_eq!(envtime!("APP_URL", interpolate = true), Some(String::from("https://example.com/${APP_PATH}")));
+/// ```
+#[proc_macro]
+pub fn envtime(input: TokenStream) -> TokenStream {
+    envtime_impl(parse_macro_input!(input as EnvtimeArgs), false).into()
+}
+
+/// Gets a environment variable like `envtime!`, as an `Option<Cow<'static, str>>`
+///
+/// A compile time value is borrowed from a literal without allocating, a runtime value is owned.
+/// Names, flags and the `_FILE` fallback work as with `envtime!`, use `envtime_str!` for a default.
+/// # Example
+/// ```
+/// use std::borrow::Cow;
+/// use std::env;
+/// use envtime::*;
+///
+/// // Assuming the variables aren't set at compile time
+/// assert_eq!(envtime_cow!("REGION_V2", "REGION"), None);
+/// env::set_var("REGION", "eu-west-1");
+/// assert!(matches!(envtime_cow!("REGION_V2", "REGION"), Some(Cow::Owned(region)) if region == "eu-west-1"));
+/// ```
+#[proc_macro]
+pub fn envtime_cow(input: TokenStream) -> TokenStream {
+    envtime_impl(parse_macro_input!(input as EnvtimeArgs), true).into()
+}
+
+/// Expands `envtime!`, or `envtime_cow!` with `cow` set
+fn envtime_impl(args: EnvtimeArgs, cow: bool) -> proc_macro2::TokenStream {
+    let EnvtimeArgs { names: env_names, any_case, file_suffix, unquote: strip_quotes, interpolate: expand } = args;
+    let names = env_names.into_iter()
+        .map(EnvName::prefixed)
+        .flat_map(|name| match name.value() {
+            Some(value) if any_case => case_variants(&value).into_iter()
+                .map(|variant| EnvName::Lit(LitStr::new(&variant, name.span())))
+                .collect(),
+            _ => vec![name]
+        })
+        .collect::<Vec<_>>();
+    #[cfg(feature = "manifest")]
+    names.iter().filter_map(EnvName::value).for_each(|name| manifest::record(&name));
+
+    // Only names ahead of the first const can be resolved at compile time, without skipping over it
+    for name in names.iter().map_while(EnvName::value) {
+        let (comp_env_val, track) = match comp_env(&name) {
+            Some(val) => (val, None),
+            None if file_suffix.is_empty() => continue,
+            None => {
+                let file_var = format!("{}{}", name, file_suffix);
+                let path = match comp_env(&file_var) {
+                    Some(path) => path,
+                    None => continue
+                };
+                // Unlike `envtime_file!` the file is only a fallback, one that can't be read is left for runtime
+                match read_comp_file(&file_var, &path, None) {
+                    Ok(CompFile::Read { contents: Some(contents), track }) => (contents, Some(track)),
+                    Ok(CompFile::Read { contents: None, .. } | CompFile::Unlocated) => continue,
+                    Err(message) => {
+                        warn_once(&format!("{}, it's left for runtime", message));
+                        continue
+                    }
+                }
+            }
+        };
+        let expanded = match expand.then(|| interpolate(&name, &comp_env_val)) {
+            Some(Ok(expanded)) => expanded,
+            Some(Err(message)) => return syn::Error::new(names[0].span(), message).to_compile_error(),
+            None => comp_env_val.clone()
+        };
+        let val = if strip_quotes { unquote(&expanded) } else { expanded.as_str() };
+        let literal = LitStr::new(val, names[0].span());
+        let value = if cow {
+            quote! { Some(std::borrow::Cow::<'static, str>::Borrowed(#literal)) }
+        } else {
+            quote! { Some(String::from(#literal)) }
+        };
+        let value = match track {
+            Some(track) => quote! { { #track #value } },
+            None => value
+        };
+        return check_divergence(&name, &comp_env_val, value)
+    }
+    let lookups = names.iter()
+        .map(|name| {
+            let lookup = runtime_env(name);
+            let file_var = match name {
+                _ if file_suffix.is_empty() => return lookup,
+                EnvName::Lit(lit) => {
+                    let file_var = LitStr::new(&format!("{}{}", lit.value(), file_suffix), lit.span());
+                    quote! { #file_var }
+                },
+                name => quote! { format!("{}{}", #name, #file_suffix) }
+            };
+            let file_lookup = runtime_file(file_var, None);
+            quote! { #lookup.or_else(|| #file_lookup) }
+        })
+        .collect::<Vec<_>>();
+    let (first, rest) = (&lookups[0], &lookups[1..]);
+    let interpolate = Some(quote! { |s: String| ::envtime::runtime::interpolate(&s) }).filter(|_| expand).into_iter();
+    let unquote = Some(unquoter()).filter(|_| strip_quotes).into_iter();
+    let owned = Some(quote! { std::borrow::Cow::<'static, str>::Owned }).filter(|_| cow).into_iter();
+    quote! {
+        #first #(.or_else(|| #rest))* #(.map(#interpolate))* #(.map(#unquote))* #(.map(#owned))*
+    }
+}
+
+struct EnvtimeArgs {
+    names: Vec<EnvName>,
+    any_case: bool,
+    file_suffix: String,
+    unquote: bool,
+    interpolate: bool,
+}
+
+impl Parse for EnvtimeArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut names = vec![input.parse::<EnvName>()?];
+        let mut any_case = false;
+        let mut file_suffix = None;
+        let (mut unquote, mut interpolate) = (false, false);
+        // Flags come after every name
+        let mut flagged = false;
+        while input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            let flag = input.fork().parse::<syn::Ident>().ok().map(|flag| flag.to_string());
+            let is_key = input.peek2(Token![=]);
+            match flag.as_deref() {
+                Some("any_case") => {
+                    input.parse::<syn::Ident>()?;
+                    if let Some(name) = names.iter().find(|name| name.value().is_none()) {
+                        return Err(syn::Error::new(name.span(), "`any_case` needs every name to be a string literal"))
+                    }
+                    any_case = true;
+                    flagged = true;
+                },
+                Some("file_suffix") if is_key => {
+                    input.parse::<syn::Ident>()?;
+                    input.parse::<Token![=]>()?;
+                    file_suffix = Some(input.parse::<LitStr>()?.value());
+                    flagged = true;
+                },
+                Some("unquote") if is_key => {
+                    input.parse::<syn::Ident>()?;
+                    input.parse::<Token![=]>()?;
+                    unquote = input.parse::<LitBool>()?.value;
+                    flagged = true;
+                },
+                Some("interpolate") if is_key => {
+                    input.parse::<syn::Ident>()?;
+                    input.parse::<Token![=]>()?;
+                    interpolate = input.parse::<LitBool>()?.value;
+                    flagged = true;
+                },
+                _ if flagged => {
+                    return Err(input.error("Unexpected argument after `any_case`, `file_suffix`, `unquote` or `interpolate`"))
+                },
+                _ => names.push(input.parse()?)
+            }
+        }
+        let file_suffix = file_suffix.unwrap_or_else(|| String::from("_FILE"));
+        Ok(EnvtimeArgs { names, any_case, file_suffix, unquote, interpolate })
+    }
+}
+
+/// A variable name, either a string literal or a path to a `&str` const that is only known at runtime
+enum EnvName {
+    Lit(LitStr),
+    Path(syn::Path),
+    /// A const name with the `ENVTIME_PREFIX` of the build, prepended at runtime
+    Prefixed(String, syn::Path),
+}
+
+impl EnvName {
+    /// The name, if it is known while the macro expands
+    fn value(&self) -> Option<String> {
+        match self {
+            EnvName::Lit(lit) => Some(lit.value()),
+            EnvName::Path(_) | EnvName::Prefixed(..) => None
+        }
+    }
+
+    fn span(&self) -> proc_macro2::Span {
+        match self {
+            EnvName::Lit(lit) => lit.span(),
+            EnvName::Path(path) | EnvName::Prefixed(_, path) => path.span()
+        }
+    }
+
+    /// The name with the `ENVTIME_PREFIX` set during the build prepended, if there is one
+    fn prefixed(self) -> EnvName {
+        match (name_prefix(), self) {
+            (Some(prefix), EnvName::Lit(lit)) => EnvName::Lit(LitStr::new(&format!("{}{}", prefix, lit.value()), lit.span())),
+            (Some(prefix), EnvName::Path(path)) => EnvName::Prefixed(prefix, path),
+            (_, name) => name
+        }
+    }
+}
+
+/// The `ENVTIME_PREFIX` set during the build, prepended to the names `envtime!`, the `envtime_def!` family,
+/// `envtime_expect!` and `envtime_matches!` look up
+fn name_prefix() -> Option<String> {
+    build_env("ENVTIME_PREFIX").map(|prefix| prefix.trim().to_string())
+}
+
+impl Parse for EnvName {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(LitStr) {
+            Ok(EnvName::Lit(input.parse()?))
+        } else if input.peek(syn::Ident) || input.peek(Token![::]) {
+            Ok(EnvName::Path(input.parse()?))
+        } else {
+            Err(input.error("Expected a string literal or a const naming the env variable"))
+        }
+    }
+}
+
+impl quote::ToTokens for EnvName {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        match self {
+            EnvName::Lit(lit) => lit.to_tokens(tokens),
+            EnvName::Path(path) => path.to_tokens(tokens),
+            EnvName::Prefixed(prefix, path) => quote! { &format!("{}{}", #prefix, #path) }.to_tokens(tokens)
+        }
+    }
+}
+
+/// Gets a required environment variable as a `&'static str`, failing the build if it isn't set at compile time
+///
+/// Like the standard `env!` macro, this never falls back to the runtime environment.
+/// # Example
+/// ```
+/// use envtime::*;
+///
+/// // Assuming we set the value of "PORT" to 5678 at compile time
+/// let port: &'static str = envtime_req!("PORT");
+/// assert_eq!(port, "5678");
+/// ```
+#[proc_macro]
+pub fn envtime_req(input: TokenStream) -> TokenStream {
+    let lit_str = parse_macro_input!(input as LitStr);
+    match build_env(&lit_str.value()) {
+        Some(comp_env_val) => {
+            let literal = LitStr::new(comp_env_val.as_str(), lit_str.span());
+            quote! { #literal }.into()
+        },
+        None => syn::Error::new(
+            lit_str.span(),
+            format!("required environment variable {} was not set at compile time", lit_str.value())
+        ).to_compile_error().into()
+    }
+}
+
+/// Gets a environment variable like `envtime_def!`, but fails a release build if it isn't set at compile time
+///
+/// Takes the same arguments as `envtime_def!`. In a debug build an unset variable falls back to the runtime
+/// environment and then the default, for a sane development setup. In a release build, which is any build of the
+/// calling crate without `debug_assertions`, an unset variable is a compile error, so a critical variable
+/// can't be forgotten when shipping. The name has to be a string literal for it to be checked during the build.
+/// # Example
+/// ```
+/// use std::env;
+/// use envtime::*;
+///
+/// // Assuming the variable isn't set at compile time, this only builds in debug
+/// # #[cfg(debug_assertions)] {
+/// env::set_var("SENTRY_DSN", "https://key@sentry.example/1");
+/// assert_eq!(envtime_req_release!("SENTRY_DSN", ""), "https://key@sentry.example/1");
+/// # }
+/// ```
+#[proc_macro]
+pub fn envtime_req_release(input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(input as EnvtimeDefArgs);
+    envtime_req_release_impl(&args).unwrap_or_else(|e| e.to_compile_error()).into()
+}
+
+fn envtime_req_release_impl(args: &EnvtimeDefArgs) -> syn::Result<proc_macro2::TokenStream> {
+    let expr = envtime_def_impl(args)?;
+    let (env_var, _) = args.name_and_default()?;
+    let name = env_var.value().ok_or_else(|| syn::Error::new(
+        env_var.span(),
+        "envtime_req_release! needs a string literal name, a const can't be checked during the build"
+    ))?;
+    // `runtime-only` doesn't matter here, the variable only has to be there during the build
+    let mut names = std::iter::once(name.clone()).chain(args.fallback.as_ref().and_then(EnvName::value));
+    if names.any(|name| build_env(&name).is_some()) {
+        return Ok(expr)
+    }
+    let message = format!("required environment variable {} was not set at compile time of a release build", name);
+    let error = quote::quote_spanned! { env_var.span() =>
+        #[cfg(not(debug_assertions))]
+        compile_error!(#message);
+    };
+    Ok(quote! {
+        {
+            #error
+            #expr
+        }
+    })
+}
+
+/// Gets a environment variable as a String like `envtime!`, panicking with a message if it isn't set at runtime
+///
+/// A compile time value is baked in and never panics. Otherwise the variable is read at runtime
+/// and an unset or empty variable panics with the given message, like `Option::expect`.
+/// # Example
+/// ```
+/// use std::env;
+/// use envtime::*;
+///
+/// // Assuming the variable isn't set at compile time
+/// env::set_var("DATABASE_URL", "postgres://localhost/app");
+/// let url = envtime_expect!("DATABASE_URL", "set DATABASE_URL to your DB connection string");
+/// assert_eq!(url, "postgres://localhost/app");
+/// ```
+/// ```should_panic
+/// use envtime::*;
+///
+/// // Assuming the variable isn't set at compile time or runtime, this panics with the message
+/// let url = envtime_expect!("DATABASE_URL", "set DATABASE_URL to your DB connection string");
+/// ```
+#[proc_macro]
+pub fn envtime_expect(input: TokenStream) -> TokenStream {
+    let ExpectArgs { name, message } = parse_macro_input!(input as ExpectArgs);
+    let name = name.prefixed();
+
+    if let Some((name, comp_env_val)) = name.value().and_then(|name| comp_env(&name).map(|val| (name, val))) {
+        let literal = LitStr::new(&comp_env_val, message.span());
+        return check_divergence(&name, &comp_env_val, quote! { String::from(#literal) }).into()
+    }
+    let lookup = runtime_env(&name);
+    quote! {
+        #lookup.expect(#message)
+    }.into()
+}
+
+/// Checks whether a environment variable is set to a value, either at compile or runtime
+///
+/// A compile time value makes this a literal `true` or `false`, so an `if` on it is removed entirely
+/// by the optimizer, otherwise the variable is compared at runtime. An unset variable matches nothing.
+/// Surrounding whitespace is ignored, and with `ignore_case = true` so is ASCII case.
+/// # Example
+/// ```
+/// use std::env;
+/// use envtime::*;
+///
+/// // Assuming the variable isn't set at compile time
+/// assert!(!envtime_matches!("MODE", "debug"));
+/// env::set_var("MODE", "Debug");
+/// assert!(!envtime_matches!("MODE", "debug"));
+/// assert!(envtime_matches!("MODE", "debug", ignore_case = true));
+/// ```
+#[proc_macro]
+pub fn envtime_matches(input: TokenStream) -> TokenStream {
+    let MatchesArgs { name, expected, ignore_case } = parse_macro_input!(input as MatchesArgs);
+    let name = name.prefixed();
+
+    if let Some((name, comp_env_val)) = name.value().and_then(|name| comp_env(&name).map(|val| (name, val))) {
+        let val = comp_env_val.trim();
+        let matches = if ignore_case { val.eq_ignore_ascii_case(&expected.value()) } else { val == expected.value() };
+        let lit = LitBool::new(matches, expected.span());
+        return check_divergence(&name, &comp_env_val, quote! { #lit }).into()
+    }
+    let lookup = runtime_env(&name);
+    let compare = if ignore_case {
+        quote! { s.trim().eq_ignore_ascii_case(#expected) }
+    } else {
+        quote! { s.trim() == #expected }
+    };
+    quote! {
+        #lookup.is_some_and(|s| #compare)
+    }.into()
+}
+
+/// Gets a stable short hash of a environment variable, for fingerprinting assets and cache-busting
+///
+/// The hash is the 64-bit FNV-1a hash of the value as it's set, as 16 lowercase hex digits.
+/// A compile time value is hashed during the build and baked in as a `&'static str`, so the binary only carries the hash.
+/// Otherwise the runtime value, or the default while it isn't set, is hashed into a `String` with
+/// `envtime::runtime::fnv_hash`, so the type depends on the build and `&*` gives a `&str` either way.
+/// Without a default an unset variable hashes like an empty value.
+/// # Example
+/// ```
+/// use std::env;
+/// use envtime::*;
+///
+/// // Assuming the variable isn't set at compile time
+/// assert_eq!(&*envtime_hash!("ASSET_MANIFEST", "foobar"), "85944171f73967e8");
+/// env::set_var("ASSET_MANIFEST", "a");
+/// assert_eq!(&*envtime_hash!("ASSET_MANIFEST"), "af63dc4c8601ec8c");
+/// ```
+#[proc_macro]
+pub fn envtime_hash(input: TokenStream) -> TokenStream {
+    let HashArgs { name, default } = parse_macro_input!(input as HashArgs);
+
+    if let Some(comp_env_val) = comp_env(&name.value()) {
+        let lit = LitStr::new(&fnv_hash(&comp_env_val), name.span());
+        return check_divergence(&name.value(), &comp_env_val, quote! { #lit }).into()
+    }
+    let lookup = runtime_env(&name);
+    let default = default.map_or_else(String::new, |default| default.value());
+    quote! {
+        ::envtime::runtime::fnv_hash(&#lookup.unwrap_or_else(|| String::from(#default)))
+    }.into()
+}
+
+/// The 64-bit FNV-1a hash of a value as hex, matching `envtime::runtime::fnv_hash`
+fn fnv_hash(val: &str) -> String {
+    let hash = val.bytes().fold(0xcbf2_9ce4_8422_2325u64, |hash, byte| (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3));
+    format!("{:016x}", hash)
+}
+
+/// Gets a environment variable as a literal usable in a `const`, always resolved at compile time
+///
+/// The runtime environment is never looked at, even with the `runtime-only` feature: a variable that isn't set
+/// during the build resolves to the default itself. The default has to be a string, bool, byte, char, integer or
+/// float literal and sets the type, a string yields a `&'static str`. A compile time value that doesn't parse
+/// as that type fails the build.
+/// # Example
+/// ```
+/// use envtime::*;
+///
+/// // Assuming the variable isn't set at compile time
+/// const BUFFER_SIZE: usize = envtime_const!("BUFFER_SIZE", 1024usize);
+/// let buffer = [0u8; BUFFER_SIZE];
+/// assert_eq!(buffer.len(), 1024);
+/// ```
+#[proc_macro]
+pub fn envtime_const(input: TokenStream) -> TokenStream {
+    let ConstArgs { name, default } = parse_macro_input!(input as ConstArgs);
+    envtime_const_impl(&name, &default).unwrap_or_else(|e| e.to_compile_error()).into()
+}
+
+fn envtime_const_impl(name: &LitStr, default: &Lit) -> syn::Result<proc_macro2::TokenStream> {
+    // Only literals get through, anything else couldn't be evaluated in a const
+    match default {
+        Lit::Str(_) | Lit::Bool(_) | Lit::Byte(_) | Lit::Char(_) => {},
+        Lit::Int(lit_int) if int_bounds(lit_int.suffix()).is_none() => return Err(unsupported_int_type(lit_int, lit_int.suffix())),
+        Lit::Float(lit_float) if !matches!(lit_float.suffix(), "" | "f32" | "f64") => return Err(unsupported_float_type(lit_float)),
+        Lit::Int(_) | Lit::Float(_) => {},
+        _ => return Err(unsupported_default(default))
+    }
+    match build_env(&name.value()) {
+        Some(comp_env_val) if matches!(default, Lit::Str(_)) => {
+            let lit = LitStr::new(&comp_env_val, name.span());
+            Ok(quote! { #lit })
+        },
+        Some(comp_env_val) => baked_literal(name.span(), &name.value(), default, comp_env_val.trim()),
+        None => Ok(quote! { #default })
+    }
+}
+
+/// Includes a block of code only if a environment variable is truthy at compile time, like a `#[cfg]` without a Cargo feature
+///
+/// The tokens inside the braces are emitted as they are when the variable is set to `1`, `true`, `yes`, `y` or `on`
+/// during the build, in any case, and left out of the compiled output otherwise, so excluded code isn't even type checked.
+/// It can wrap items or statements, not an expression. Being resolved while the macro expands, it can't respond to
+/// runtime values, even with the `runtime-only` feature. With the `strict-bool` feature a value that isn't
+/// recognized as a bool fails the build instead of leaving the block out.
+/// # Example
+/// ```
+/// use envtime::*;
+///
+/// // Assuming the variable isn't set at compile time
+/// envtime_cfg!("ENABLE_METRICS", {
+///     fn metrics() {
+///         compile_error!("only compiled with ENABLE_METRICS set");
+///     }
+/// });
+/// ```
+#[proc_macro]
+pub fn envtime_cfg(input: TokenStream) -> TokenStream {
+    let CfgArgs { name, body } = parse_macro_input!(input as CfgArgs);
+    let comp_env_val = match build_env(&name.value()) {
+        Some(comp_env_val) => comp_env_val,
+        None => return TokenStream::new()
+    };
+    match recognize_bool(comp_env_val.trim()) {
+        Some(true) => body.into(),
+        None if cfg!(feature = "strict-bool") => syn::Error::new(
+            name.span(),
+            format!("Cannot parse compilation env var {} as bool: {:?}", name.value(), comp_env_val)
+        ).to_compile_error().into(),
+        Some(false) | None => TokenStream::new()
+    }
+}
+
+/// Gets a environment variable as the type specified by the default value, either at compile or runtime
+/// # Example
+/// ```
+/// use std::env;
+/// use envtime::*;
+///
+/// // Assuming we set the value of "PORT" to 5678 at compile or runtime
+/// let var = envtime_def!("PORT", 1234u16);
+/// assert_eq!(var, 5678u16);
+///
+/// // The default can also be given first
+/// let var = envtime_def!(1234u16, "PORT");
+/// assert_eq!(var, 5678u16);
+/// ```
+///
+/// The name can also be a path to a `&str` const, which is only looked up at runtime,
+/// since its value isn't known while the macro expands.
+/// ```
+/// use std::env;
+/// use envtime::*;
+///
+/// const WORKERS_VAR: &str = "WORKER_COUNT";
+///
+/// env::set_var("WORKER_COUNT", "8");
+/// assert_eq!(envtime_def!(WORKERS_VAR, 1u8), 8u8);
+/// ```
+///
+/// Surrounding whitespace is trimmed from values for bool, byte, integer and float defaults.
+/// String values are kept as-is, unless `trim = true` is passed. Like `envtime!`, `unquote = true`
+/// strips a pair of quotes around a string value, after trimming it.
+/// ```
+/// use std::env;
+/// use envtime::*;
+///
+/// // Assuming the variables aren't set at compile time
+/// env::set_var("WORKERS", " 4\n");
+/// assert_eq!(envtime_def!("WORKERS", 1u8), 4u8);
+/// env::set_var("GREETING", " hi ");
+/// assert_eq!(envtime_def!("GREETING", "hello"), " hi ");
+/// assert_eq!(envtime_def!("GREETING", "hello", trim = true), "hi");
+/// ```
+///
+/// With `transform = "lowercase"` or `"uppercase"`, a string value is converted after `trim` and `unquote`,
+/// whether it's baked in or read at runtime. The default is returned as written.
+/// ```
+/// use std::env;
+/// use envtime::*;
+///
+/// // Assuming the variable isn't set at compile time
+/// env::set_var("REGION", "EU-West");
+/// assert_eq!(envtime_def!("REGION", "us", transform = "lowercase"), "eu-west");
+/// ```
+///
+/// With `borrowed = true` a string default makes this a `Cow<'static, str>` instead of a `String`,
+/// so a value baked in, or the default, is borrowed from a literal without allocating.
+/// A value read at runtime is still owned.
+/// ```
+/// use std::borrow::Cow;
+/// use std::env;
+/// use envtime::*;
+///
+/// // Assuming the variable isn't set at compile time
+/// assert!(matches!(envtime_def!("LOG_FORMAT", "text", borrowed = true), Cow::Borrowed("text")));
+/// env::set_var("LOG_FORMAT", "json");
+/// assert_eq!(envtime_def!("LOG_FORMAT", "text", borrowed = true), "json");
+/// ```
+///
+/// The default can also be one of the keywords `pkg_name`, `pkg_version`, `pkg_authors`, `pkg_description`,
+/// `pkg_homepage` or `pkg_repository`, standing for that field of the calling crate's `Cargo.toml` as a string default.
+/// Those names are always keywords here, so a const with one of them can't be used as the default.
+/// ```
+/// use std::env;
+/// use envtime::*;
+///
+/// // Assuming the variable isn't set at compile time
+/// assert_eq!(envtime_def!("APP_VERSION", pkg_version), env!("CARGO_PKG_VERSION"));
+/// env::set_var("APP_VERSION", "1.0.0-custom");
+/// assert_eq!(envtime_def!("APP_VERSION", pkg_version), "1.0.0-custom");
+/// ```
+///
+/// A byte default takes a decimal, `0x`, `0o` or `0b` number, or a single ASCII character standing for its code,
+/// so `A`, `65` and `0x41` are all `b'A'`. A single digit is a number. A non-ASCII character fails the build
+/// when it's baked in, and falls back to the default at runtime.
+/// ```
+/// use std::env;
+/// use envtime::*;
+///
+/// // Assuming the variable isn't set at compile time
+/// env::set_var("DELIMITER", ";");
+/// assert_eq!(envtime_def!("DELIMITER", b','), b';');
+/// env::set_var("DELIMITER", "0x09");
+/// assert_eq!(envtime_def!("DELIMITER", b','), b'\t');
+/// ```
+///
+/// Float values are parsed like `f64::from_str`, so scientific notation like `1e-9`, `inf` and `nan` are accepted too.
+/// ```
+/// use std::env;
+/// use envtime::*;
+///
+/// // Assuming the variable isn't set at compile time
+/// env::set_var("EPSILON", "1e-9");
+/// assert_eq!(envtime_def!("EPSILON", 0.001), 0.000000001);
+/// env::set_var("EPSILON", "NaN");
+/// assert!(envtime_def!("EPSILON", 0.001f32).is_nan());
+/// ```
+///
+/// A bool default is true for `1`, `true`, `yes`, `y` or `on` and false for `0`, `false`, `no`, `n` or `off`,
+/// in any case. Any other value falls back to the default, like an integer value that fails to parse.
+/// With the `strict` feature a bool or integer value that is set but invalid panics at runtime instead,
+/// and a compile time bool value that isn't recognized fails the build. The `strict-bool` feature only does the latter,
+/// so a typo like `TRUEE` baked in can't silently disable something, while runtime values keep falling back.
+/// ```
+/// use std::env;
+/// use envtime::*;
+///
+/// // Assuming the variable isn't set at compile time
+/// env::set_var("DEBUG", "On");
+/// assert_eq!(envtime_def!("DEBUG", false), true);
+/// env::set_var("DEBUG", "off");
+/// assert_eq!(envtime_def!("DEBUG", true), false);
+/// ```
+///
+/// A char default takes a single character or a code point like `envtime_char!`, anything else falls back to the default
+/// ```
+/// use std::env;
+/// use envtime::*;
+///
+/// // Assuming the variable isn't set at compile time
+/// env::set_var("DELIM", ";");
+/// assert_eq!(envtime_def!("DELIM", ','), ';');
+/// env::set_var("DELIM", "\\u{2603}");
+/// assert_eq!(envtime_def!("DELIM", ','), '\u{2603}');
+/// env::set_var("DELIM", ";;");
+/// assert_eq!(envtime_def!("DELIM", ','), ',');
+/// ```
+///
+/// An integer or float type can be given after the default, instead of a suffix on the default itself.
+/// With an integer type the default can also be a byte literal, which stands for its value, so `b'a'` is `97`
+/// and has to fit the type, `b'\xFF'` being too large for an `i8`. The value is parsed as an integer of the type.
+/// ```
+/// use std::env;
+/// use envtime::*;
+///
+/// // Assuming the variables aren't set at compile time
+/// env::set_var("RETRIES", "3000000000");
+/// assert_eq!(envtime_def!("RETRIES", 0, u32), 3_000_000_000u32);
+/// assert_eq!(envtime_def!("OFFSET", b'a', i8), 97i8);
+/// env::set_var("OFFSET", "-3");
+/// assert_eq!(envtime_def!("OFFSET", b'a', i8), -3i8);
+/// ```
+///
+/// Integer defaults accept optional `min` and `max` bounds. A compile time value out of range fails the build,
+/// a runtime value out of range falls back to the default.
+/// ```
+/// use std::env;
+/// use envtime::*;
+///
+/// // Assuming the variable isn't set at compile time
+/// env::set_var("SERVICE_PORT", "80");
+/// assert_eq!(envtime_def!("SERVICE_PORT", 8080u16, min = 1024, max = 49151), 8080u16);
+/// env::set_var("SERVICE_PORT", "9090");
+/// assert_eq!(envtime_def!("SERVICE_PORT", 8080u16, min = 1024, max = 49151), 9090u16);
+/// ```
+///
+/// String defaults accept optional `min_len` and `max_len` bounds on the length in chars, after `trim` and `unquote`.
+/// A compile time value out of bounds fails the build, a runtime value out of bounds falls back to the default.
+/// A default that is out of bounds itself, like an empty one, makes the variable required: using it panics.
+/// ```
+/// use std::env;
+/// use envtime::*;
+///
+/// // Assuming the variable isn't set at compile time
+/// env::set_var("API_KEY", "too-short");
+/// assert_eq!(envtime_def!("API_KEY", "0123456789abcdef", min_len = 16, max_len = 64), "0123456789abcdef");
+/// env::set_var("API_KEY", "fedcba9876543210");
+/// assert_eq!(envtime_def!("API_KEY", "", min_len = 16, max_len = 64), "fedcba9876543210");
+/// ```
+///
+/// With the `regex` feature, string defaults also accept a `pattern` the value has to match, checked the same way.
+/// `regex` has to be a dependency of the calling crate, the pattern is compiled once when first used at runtime.
+/// ```
+/// # #[cfg(feature = "regex")] {
+/// use std::env;
+/// use envtime::*;
+///
+/// // Assuming the variable isn't set at compile time
+/// env::set_var("SLUG", "Not A Slug");
+/// assert_eq!(envtime_def!("SLUG", "default", pattern = "^[a-z0-9-]+$"), "default");
+/// env::set_var("SLUG", "my-app-2");
+/// assert_eq!(envtime_def!("SLUG", "default", pattern = "^[a-z0-9-]+$"), "my-app-2");
+/// # }
+/// ```
+///
+/// String defaults also accept `one_of = [...]`, the values allowed without defining an enum, compared exactly
+/// after `trim`, `unquote` and `transform`. It's checked the same way as the length bounds.
+/// ```
+/// use std::env;
+/// use envtime::*;
+///
+/// // Assuming the variable isn't set at compile time
+/// env::set_var("DEPLOY_MODE", "qa");
+/// assert_eq!(envtime_def!("DEPLOY_MODE", "prod", one_of = ["dev", "staging", "prod"]), "prod");
+/// env::set_var("DEPLOY_MODE", "staging");
+/// assert_eq!(envtime_def!("DEPLOY_MODE", "prod", one_of = ["dev", "staging", "prod"]), "staging");
+/// ```
+///
+/// Integer values may be written in hex, octal or binary with a `0x`, `0o` or `0b` prefix, and numbers
+/// may use `_` separators between digits like Rust literals. A leading or trailing `_` is still malformed.
+/// ```
+/// use std::env;
+/// use envtime::*;
+///
+/// // Assuming the variable isn't set at compile time
+/// env::set_var("MASK", "0xFF");
+/// assert_eq!(envtime_def!("MASK", 0u32), 255u32);
+/// env::set_var("MASK", "0b1010");
+/// assert_eq!(envtime_def!("MASK", 0u32), 10u32);
+/// env::set_var("MAX_BYTES", "1_000_000");
+/// assert_eq!(envtime_def!("MAX_BYTES", 0u64), 1_000_000u64);
+/// ```
+///
+/// The default may also be a const or any other expression, as long as the name comes first.
+/// Without a type the value is a `String` and the default has to convert into one with `String::from`,
+/// with a type after the default the value is parsed as that `FromStr` type like `envtime_parse!`.
+/// An expression default is only evaluated when it's used and can't have `min` or `max` bounds.
+/// ```
+/// use std::env;
+/// use std::net::Ipv4Addr;
+/// use envtime::*;
+///
+/// const DEFAULT_HOST: &str = "localhost";
+///
+/// // Assuming the variables aren't set at compile time
+/// assert_eq!(envtime_def!("DB_HOST", DEFAULT_HOST), "localhost");
+/// env::set_var("DB_ADDR", "10.0.0.2");
+/// assert_eq!(envtime_def!("DB_ADDR", Ipv4Addr::new(127, 0, 0, 1), Ipv4Addr), Ipv4Addr::new(10, 0, 0, 2));
+/// ```
+///
+/// String values accept `interpolate = true` to expand `${NAME}` references like `envtime!` does, before `trim`
+/// and the other options are applied. A string literal default may have references too, to compose it from other
+/// variables. It's expanded at runtime when it's used, once the variable and its `env` fallback turned out unset,
+/// and isn't trimmed or transformed, while the length, `pattern` and `one_of` checks apply to the default as written.
+/// A default that refers back to its own variable or fallback, directly or through variables set during the build,
+/// is a cycle and fails the build.
+/// ```
+/// use std::env;
+/// use envtime::*;
+///
+/// // Assuming the variables aren't set at compile time
+/// env::set_var("CACHE_HOST", "cache.local");
+/// env::set_var("CACHE_URL", "redis://${CACHE_HOST}:6379");
+/// assert_eq!(envtime_def!("CACHE_URL", "redis://localhost", interpolate = true), "redis://cache.local:6379");
+/// assert_eq!(envtime_def!("QUEUE_URL", "redis://${CACHE_HOST}:6380", interpolate = true), "redis://cache.local:6380");
+/// ```
+///
+/// A byte string default gives a `Cow<'static, [u8]>` of the value's bytes, borrowed from a byte string literal
+/// when it's baked in or the default is used, owned when read at runtime. The bytes are taken as they are, untrimmed.
+/// `envtime_cached!` gives a `&'static [u8]` instead.
+/// ```
+/// use std::env;
+/// use envtime::*;
+///
+/// // Assuming the variable isn't set at compile time
+/// assert_eq!(&*envtime_def!("FILE_MAGIC", b"MZ"), b"MZ");
+/// env::set_var("FILE_MAGIC", "PK");
+/// assert_eq!(&*envtime_def!("FILE_MAGIC", b"MZ"), b"PK");
+/// ```
+///
+/// A `None::<T>` or `Option::<T>::None` default gives an `Option<T>` like `envtime_opt!`, a baked value becomes
+/// `Some` of it and an unset variable stays `None`. A runtime value that fails to parse is `None` as well.
+/// ```
+/// use std::env;
+/// use envtime::*;
+///
+/// // Assuming the variable isn't set at compile time
+/// assert_eq!(envtime_def!("METRICS_PORT", None::<u16>), None);
+/// env::set_var("METRICS_PORT", "9100");
+/// assert_eq!(envtime_def!("METRICS_PORT", Option::<u16>::None), Some(9100));
+/// ```
+///
+/// With `env = "..."` another variable is used when the first one isn't set, at compile time as well as at runtime.
+/// A value that is set but invalid still falls back to the default, not to the other variable.
+/// With a default the value has the type of the default, without one it's an `Option<String>` that is `None`
+/// when neither variable is set.
+/// ```
+/// use std::env;
+/// use envtime::*;
+///
+/// // Assuming the variables aren't set at compile time
+/// env::set_var("GENERIC_PORT", "8000");
+/// assert_eq!(envtime_def!("SPECIFIC_PORT", 8080u16, env = "GENERIC_PORT"), 8000u16);
+/// assert_eq!(envtime_def!("SPECIFIC_PORT", env = "GENERIC_PORT"), Some(String::from("8000")));
+/// env::set_var("SPECIFIC_PORT", "9000");
+/// assert_eq!(envtime_def!("SPECIFIC_PORT", 8080u16, env = "GENERIC_PORT"), 9000u16);
+/// ```
+#[proc_macro]
+pub fn envtime_def(input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(input as EnvtimeDefArgs);
+    envtime_def_impl(&args).unwrap_or_else(|e| e.to_compile_error()).into()
+}
+
+struct EnvtimeDefArgs {
+    lits: Punctuated<DefArg, Token![,]>,
+    trim: bool,
+    unquote: bool,
+    min: Option<LitInt>,
+    max: Option<LitInt>,
+    min_len: Option<LitInt>,
+    max_len: Option<LitInt>,
+    pattern: Option<LitStr>,
+    /// The `one_of = [...]` values a string has to be one of, with where it was passed
+    one_of: Option<(Vec<LitStr>, proc_macro2::Span)>,
+    transform: Option<(Transform, proc_macro2::Span)>,
+    /// Where `borrowed = true` was passed, to return a `Cow<'static, str>`
+    borrowed: Option<proc_macro2::Span>,
+    /// Where `interpolate = true` was passed, to expand `${NAME}` references in the value
+    interpolate: Option<proc_macro2::Span>,
+    ty: Option<syn::Path>,
+    /// The `env = "..."` variable used when the name isn't set
+    fallback: Option<EnvName>,
+}
+
+/// The keywords standing for a Cargo package metadata default, with the variable Cargo sets for it
+const PACKAGE_KEYWORDS: &[(&str, &str)] = &[
+    ("pkg_name", "CARGO_PKG_NAME"),
+    ("pkg_version", "CARGO_PKG_VERSION"),
+    ("pkg_authors", "CARGO_PKG_AUTHORS"),
+    ("pkg_description", "CARGO_PKG_DESCRIPTION"),
+    ("pkg_homepage", "CARGO_PKG_HOMEPAGE"),
+    ("pkg_repository", "CARGO_PKG_REPOSITORY"),
+];
+
+/// The package metadata a keyword default like `pkg_version` stands for, as a string literal
+///
+/// Cargo sets these for the crate being compiled, so they're read during the build like any baked value.
+fn package_default(path: &syn::Path) -> Option<Lit> {
+    let ident = path.get_ident()?.to_string();
+    let (_, var) = PACKAGE_KEYWORDS.iter().find(|(keyword, _)| *keyword == ident)?;
+    let val = env::var(var).unwrap_or_default();
+    Some(Lit::Str(LitStr::new(&val, path.span())))
+}
+
+/// A positional `envtime_def!` argument, a path is either the name or a default expression
+enum DefArg {
+    Lit(Lit),
+    Path(syn::Path),
+    Expr(syn::Expr),
+}
+
+impl Parse for DefArg {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        // A literal on its own, negative numbers included, keeps the type-driven resolution
+        let fork = input.fork();
+        if fork.parse::<Lit>().is_ok() && (fork.is_empty() || fork.peek(Token![,])) {
+            return Ok(DefArg::Lit(input.parse()?))
+        }
+        match input.parse()? {
+            syn::Expr::Path(expr) if expr.qself.is_none() && expr.attrs.is_empty() => Ok(DefArg::Path(expr.path)),
+            expr => Ok(DefArg::Expr(expr))
+        }
+    }
+}
+
+impl quote::ToTokens for DefArg {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        match self {
+            DefArg::Lit(lit) => lit.to_tokens(tokens),
+            DefArg::Path(path) => path.to_tokens(tokens),
+            DefArg::Expr(expr) => expr.to_tokens(tokens)
+        }
+    }
+}
+
+impl Parse for EnvtimeDefArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut lits = Punctuated::new();
+        let (mut trim, mut unquote) = (false, false);
+        let (mut min, mut max) = (None, None);
+        let (mut min_len, mut max_len) = (None, None);
+        let (mut pattern, mut transform, mut fallback) = (None, None, None);
+        let (mut borrowed, mut interpolate, mut one_of) = (None, None, None);
+        while !input.is_empty() {
+            if input.peek(syn::Ident) && input.peek2(Token![=]) {
+                let key = input.parse::<syn::Ident>()?;
+                input.parse::<Token![=]>()?;
+                match key.to_string().as_str() {
+                    "trim" => trim = input.parse::<LitBool>()?.value,
+                    "unquote" => unquote = input.parse::<LitBool>()?.value,
+                    "min" => min = Some(input.parse()?),
+                    "max" => max = Some(input.parse()?),
+                    "min_len" => min_len = Some(input.parse()?),
+                    "max_len" => max_len = Some(input.parse()?),
+                    "pattern" => pattern = Some(input.parse()?),
+                    "one_of" => {
+                        let content;
+                        syn::bracketed!(content in input);
+                        let values = Punctuated::<LitStr, Token![,]>::parse_terminated(&content)?;
+                        if values.is_empty() {
+                            return Err(syn::Error::new(key.span(), "`one_of` needs at least one value"))
+                        }
+                        one_of = Some((values.into_iter().collect(), key.span()));
+                    },
+                    "transform" => transform = Some((input.parse()?, key.span())),
+                    "borrowed" => borrowed = input.parse::<LitBool>()?.value.then(|| key.span()),
+                    "interpolate" => interpolate = input.parse::<LitBool>()?.value.then(|| key.span()),
+                    "env" => fallback = Some(input.parse::<EnvName>()?.prefixed()),
+                    _ => return Err(syn::Error::new(
+                        key.span(),
+                        "Unknown argument, expected `trim`, `unquote`, `min`, `max`, `min_len`, `max_len`, `pattern`, `one_of`, `transform`, `borrowed`, `interpolate` or `env`"
+                    ))
+                }
+            } else {
+                lits.push_value(input.parse()?);
+            }
+            if input.is_empty() {
+                break
+            }
+            let comma = input.parse::<Token![,]>()?;
+            if !lits.empty_or_trailing() {
+                lits.push_punct(comma);
+            }
+        }
+        // A third positional path is an explicit type for the default, like `envtime_def!("COUNT", 0, u32)`
+        let ty = match lits.last() {
+            Some(DefArg::Path(_)) if lits.len() == 3 => match lits.pop().map(|pair| pair.into_value()) {
+                Some(DefArg::Path(path)) => Some(path),
+                _ => unreachable!()
+            },
+            _ => None
+        };
+        Ok(EnvtimeDefArgs { lits, trim, unquote, min, max, min_len, max_len, pattern, one_of, transform, borrowed, interpolate, ty, fallback })
+    }
+}
+
+impl EnvtimeDefArgs {
+    /// Arguments without any options, as the attribute macros and derives pass them on
+    fn with_defaults(lits: Vec<DefArg>) -> Self {
+        EnvtimeDefArgs {
+            lits: lits.into_iter().collect(),
+            trim: false,
+            unquote: false,
+            min: None,
+            max: None,
+            min_len: None,
+            max_len: None,
+            pattern: None,
+            one_of: None,
+            transform: None,
+            borrowed: None,
+            interpolate: None,
+            ty: None,
+            fallback: None,
+        }
+    }
+
+    /// The name and value of the variable baked in, the name's or else the `env` fallback's
+    ///
+    /// The fallback is only looked at if the name is known during the build, so it never shadows a const name.
+    fn baked(&self, env_var: &EnvName) -> Option<(String, String)> {
+        std::iter::once(env_var.value()?)
+            .chain(self.fallback.as_ref().and_then(EnvName::value))
+            .find_map(|name| comp_env(&name).map(|val| (name, val)))
+    }
+
+    /// Records the name and the `env` fallback in the manifest, when they're known while the macro expands
+    #[cfg(feature = "manifest")]
+    fn record(&self, env_var: &EnvName) {
+        std::iter::once(env_var)
+            .chain(self.fallback.as_ref())
+            .filter_map(EnvName::value)
+            .for_each(|name| manifest::record(&name));
+    }
+
+    /// Tokens looking up the name at runtime as an `Option<String>`, and then the `env` fallback
+    fn lookup(&self, env_var: &EnvName) -> proc_macro2::TokenStream {
+        let lookup = runtime_env(env_var);
+        match &self.fallback {
+            Some(fallback) => {
+                let fallback = runtime_env(fallback);
+                quote! { #lookup.or_else(|| #fallback) }
+            },
+            None => lookup
+        }
+    }
+
+    /// A compile time string value with `trim`, `unquote` and `transform` applied
+    fn string_value<'a>(&self, val: &'a str) -> std::borrow::Cow<'a, str> {
+        let val = if self.trim { val.trim() } else { val };
+        let val = if self.unquote { unquote(val) } else { val };
+        match &self.transform {
+            Some((transform, _)) => std::borrow::Cow::Owned(transform.apply(val)),
+            None => std::borrow::Cow::Borrowed(val)
+        }
+    }
+
+    /// A compile time string value with its `${NAME}` references expanded if asked to, then `string_value` applied
+    fn baked_string(&self, env_var: &EnvName, name: &str, val: &str) -> syn::Result<String> {
+        if self.interpolate.is_none() {
+            return Ok(self.string_value(val).into_owned())
+        }
+        let val = interpolate(name, val).map_err(|message| syn::Error::new(env_var.span(), message))?;
+        Ok(self.string_value(&val).into_owned())
+    }
+
+    /// Fails for a string default whose `${NAME}` references loop back to the variable or its `env` fallback,
+    /// or around any other variables, following the references of the ones set during the build
+    fn check_default_refs(&self, env_var: &EnvName, def_str: &LitStr) -> syn::Result<()> {
+        let mut expanding = std::iter::once(env_var)
+            .chain(self.fallback.as_ref())
+            .filter_map(EnvName::value)
+            .collect::<Vec<_>>();
+        let label = env_var.value().unwrap_or_else(|| quote! { #env_var }.to_string());
+        check_cycles(&def_str.value(), &mut expanding)
+            .map_err(|reference| syn::Error::new(
+                def_str.span(),
+                format!("The default of env var {} has a cyclic reference to ${{{}}}", label, reference)
+            ))
+    }
+
+    /// Tokens mapping a runtime `Option<String>` to apply `interpolate`, `trim`, `unquote` and `transform`, like `baked_string`
+    fn string_map(&self) -> proc_macro2::TokenStream {
+        let interpolate = Some(quote! { .map(|s| ::envtime::runtime::interpolate(&s)) }).filter(|_| self.interpolate.is_some());
+        let trim = Some(quote! { .map(|s| s.trim().to_string()) }).filter(|_| self.trim);
+        let unquote = Some(unquoter()).filter(|_| self.unquote).map(|unquoter| quote! { .map(#unquoter) });
+        let transform = self.transform.as_ref().map(|(transform, _)| transform.tokens()).map(|transform| quote! { .map(#transform) });
+        quote! { #interpolate #trim #unquote #transform }
+    }
+
+    /// Fails for a `transform` on a value that isn't a string
+    fn check_transform(&self) -> syn::Result<()> {
+        match &self.transform {
+            Some((_, span)) => Err(syn::Error::new(*span, "`transform` needs a string default")),
+            None => Ok(())
+        }
+    }
+
+    /// Fails for `interpolate = true` on a value that isn't a string
+    fn check_interpolate(&self) -> syn::Result<()> {
+        match self.interpolate {
+            Some(span) => Err(syn::Error::new(span, "`interpolate` needs a string default")),
+            None => Ok(())
+        }
+    }
+
+    /// Fails for `borrowed = true` on anything but a string literal default
+    fn check_borrowed(&self) -> syn::Result<()> {
+        match self.borrowed {
+            Some(span) => Err(syn::Error::new(span, "`borrowed` needs a string literal default")),
+            None => Ok(())
+        }
+    }
+
+    /// The `min_len` and `max_len` bounds on the length in chars of a string default's value
+    fn len_range(&self, def_val: &Lit) -> syn::Result<(Option<usize>, Option<usize>)> {
+        match (def_val, self.min_len.as_ref().or(self.max_len.as_ref())) {
+            (_, None) => return Ok((None, None)),
+            (Lit::Str(_), Some(_)) => {},
+            (_, Some(bound)) => return Err(syn::Error::new(bound.span(), "`min_len` and `max_len` need a string literal default"))
+        }
+        let bound = |lit: &Option<LitInt>| lit.as_ref().map(LitInt::base10_parse::<usize>).transpose();
+        let (min, max) = (bound(&self.min_len)?, bound(&self.max_len)?);
+        if let (Some(min), Some(max)) = (min, max) {
+            if min > max {
+                return Err(syn::Error::new(self.min_len.span(), "`min_len` is greater than `max_len`"))
+            }
+        }
+        Ok((min, max))
+    }
+
+    /// The `pattern` a string default's value has to match
+    fn pattern(&self, def_val: &Lit) -> syn::Result<Option<&LitStr>> {
+        match (def_val, &self.pattern) {
+            (Lit::Str(_), pattern) | (_, pattern @ None) => Ok(pattern.as_ref()),
+            (_, Some(pattern)) => Err(syn::Error::new(pattern.span(), "`pattern` needs a string literal default"))
+        }
+    }
+
+    /// The `one_of` values a string default's value has to be one of
+    fn one_of(&self, def_val: &Lit) -> syn::Result<Option<Vec<String>>> {
+        match (def_val, &self.one_of) {
+            (_, None) => Ok(None),
+            (Lit::Str(_), Some((values, _))) => Ok(Some(values.iter().map(LitStr::value).collect())),
+            (_, Some((_, span))) => Err(syn::Error::new(*span, "`one_of` needs a string literal default"))
+        }
+    }
+
+    /// The `min` and `max` bounds, checked against the type of the integer default
+    fn int_range(&self, def_val: &Lit) -> syn::Result<(Option<i128>, Option<i128>)> {
+        let lit_int = match (def_val, self.min.as_ref().or(self.max.as_ref())) {
+            (_, None) => return Ok((None, None)),
+            (Lit::Int(lit_int), Some(_)) => lit_int,
+            (_, Some(bound)) => return Err(syn::Error::new(bound.span(), "`min` and `max` need an integer default"))
+        };
+        let (lo, hi) = int_bounds(lit_int.suffix()).unwrap_or((i128::MIN, i128::MAX));
+        let bound = |lit: &Option<LitInt>| lit.as_ref().map(|lit| match lit.base10_parse::<i128>() {
+            Ok(val) if lo <= val && val <= hi => Ok(val),
+            _ => Err(syn::Error::new(lit.span(), format!("Bound is out of range for the default's type {}", int_type_name(lit_int.suffix()))))
+        }).transpose();
+        let (min, max) = (bound(&self.min)?, bound(&self.max)?);
+        if let (Some(min), Some(max)) = (min, max) {
+            if min > max {
+                return Err(syn::Error::new(self.min.span(), "`min` is greater than `max`"))
+            }
+        }
+        if let Ok(default) = lit_int.base10_parse::<i128>() {
+            if !in_range(default, min, max) {
+                return Err(syn::Error::new(lit_int.span(), format!("The default is out of range {}", range_text(min, max))))
+            }
+        }
+        Ok((min, max))
+    }
+
+    /// The name given without a default, next to an `env` fallback
+    fn sole_name(&self) -> syn::Result<EnvName> {
+        match self.lits.first() {
+            Some(DefArg::Lit(Lit::Str(lit))) => Ok(EnvName::Lit(lit.clone()).prefixed()),
+            Some(DefArg::Path(path)) => Ok(EnvName::Path(path.clone()).prefixed()),
+            first => Err(syn::Error::new(
+                first.map_or_else(proc_macro2::Span::call_site, |first| first.span()),
+                "The parameter has to be a string literal or a const naming the env variable"
+            ))
+        }
+    }
+
+    /// The name and default value, in either order, with the explicit type applied to a literal default
+    fn name_and_default(&self) -> syn::Result<(EnvName, DefArg)> {
+        let (name, def_val) = match self.positional()? {
+            (name, DefArg::Lit(def_val)) => (name, def_val),
+            (name, DefArg::Path(path)) if self.ty.is_none() => {
+                return Ok((name, package_default(path).map_or_else(|| DefArg::Path(path.clone()), DefArg::Lit)))
+            },
+            (name, DefArg::Path(path)) => return Ok((name, DefArg::Path(path.clone()))),
+            (name, DefArg::Expr(expr)) => return Ok((name, DefArg::Expr(expr.clone())))
+        };
+        let ty = match &self.ty {
+            Some(ty) => ty,
+            None => return Ok((name, DefArg::Lit(def_val.clone())))
+        };
+        let ident = ty.get_ident().map(|ident| ident.to_string()).unwrap_or_default();
+        let mismatch = |suffix: &str| syn::Error::new(
+            def_val.span(),
+            format!("The default's suffix {} doesn't match the explicit type {}", suffix, ident)
+        );
+        let def_val = match def_val {
+            Lit::Int(lit) if int_bounds(&ident).is_some() => {
+                if !lit.suffix().is_empty() && lit.suffix() != ident {
+                    return Err(mismatch(lit.suffix()))
+                }
+                Lit::Int(LitInt::new(&format!("{}{}", lit.base10_digits(), ident), lit.span()))
+            },
+            Lit::Int(lit) if ident == "f32" || ident == "f64" => {
+                if !lit.suffix().is_empty() && lit.suffix() != ident {
+                    return Err(mismatch(lit.suffix()))
+                }
+                Lit::Float(syn::LitFloat::new(&format!("{}.0{}", lit.base10_digits(), ident), lit.span()))
+            },
+            Lit::Float(lit) if ident == "f32" || ident == "f64" => {
+                if !lit.suffix().is_empty() && lit.suffix() != ident {
+                    return Err(mismatch(lit.suffix()))
+                }
+                Lit::Float(syn::LitFloat::new(&format!("{}{}", lit.base10_digits(), ident), lit.span()))
+            },
+            // A byte literal is just another way of writing an integer, its value has to fit the type as is
+            Lit::Byte(lit) if int_bounds(&ident).is_some_and(|(_, max)| i128::from(lit.value()) <= max) => {
+                Lit::Int(LitInt::new(&format!("{}{}", lit.value(), ident), lit.span()))
+            },
+            Lit::Byte(lit) if int_bounds(&ident).is_some() => {
+                return Err(syn::Error::new(
+                    lit.span(),
+                    format!("The default's value {} doesn't fit the explicit type {}", lit.value(), ident)
+                ))
+            },
+            _ if int_bounds(&ident).is_some() => {
+                return Err(syn::Error::new(
+                    def_val.span(),
+                    format!("A {} default doesn't match the explicit type {}, which needs an integer or byte default", lit_kind(def_val), ident)
+                ))
+            },
+            _ if ident == "f32" || ident == "f64" => {
+                return Err(syn::Error::new(
+                    def_val.span(),
+                    format!("A {} default doesn't match the explicit type {}, which needs an integer or float default", lit_kind(def_val), ident)
+                ))
+            },
+            _ => return Err(syn::Error::new(
+                ty.span(),
+                format!("Unsupported explicit type, expected f32, f64 or one of: {}", SUPPORTED_INT_TYPES)
+            ))
+        };
+        Ok((name, DefArg::Lit(def_val)))
+    }
+
+    /// The name and default value as given, in either order, with the name prefixed
+    fn positional(&self) -> syn::Result<(EnvName, &DefArg)> {
+        let input = &self.lits;
+        // Only the values are looked at, so a trailing comma from a generated invocation doesn't matter
+        let mut args = input.iter();
+        match (args.next(), args.next(), args.next()) {
+            (None, _, _) => return Err(syn::Error::new(
+                proc_macro2::Span::call_site(),
+                "Expected a env variable name and a default value"
+            )),
+            (Some(first), None, _) => return Err(syn::Error::new(first.span(), "Expected a default value after the variable name")),
+            (_, _, Some(extra)) => return Err(syn::Error::new(
+                extra.span(),
+                "Unexpected argument, expected a env variable name, a default value and optionally its type"
+            )),
+            _ => {}
+        }
+        // The name comes first, but a swapped `envtime_def!(8080u16, "PORT")` is accepted as well,
+        // only a literal default can be swapped since a leading string or path is always the name
+        let (name, def_val) = match (input.first().unwrap(), input.last().unwrap()) {
+            (DefArg::Lit(Lit::Str(lit)), def_val) => (EnvName::Lit(lit.clone()), def_val),
+            (def_val @ DefArg::Lit(_), DefArg::Lit(Lit::Str(lit))) => (EnvName::Lit(lit.clone()), def_val),
+            (DefArg::Path(path), def_val) | (def_val @ DefArg::Lit(_), DefArg::Path(path)) => {
+                (EnvName::Path(path.clone()), def_val)
+            },
+            (first, _) => return Err(syn::Error::new(
+                first.span(),
+                "One of the parameters has to be a string literal or a const naming the env variable"
+            ))
+        };
+        Ok((name.prefixed(), def_val))
+    }
+}
+
+fn envtime_def_impl(args: &EnvtimeDefArgs) -> syn::Result<proc_macro2::TokenStream> {
+    let input = &args.lits;
+    if input.len() == 1 && args.fallback.is_some() {
+        return envtime_def_fallback_impl(args)
+    }
+    let (env_var, def_val) = args.name_and_default()?;
+    #[cfg(feature = "manifest")]
+    args.record(&env_var);
+    let def_val = match def_val {
+        DefArg::Lit(def_val) => def_val,
+        def_val => return envtime_def_expr_impl(args, &env_var, &def_val)
+    };
+    let def_val = &def_val;
+    if let Lit::Int(lit_int) = def_val {
+        if int_bounds(lit_int.suffix()).is_none() {
+            return Err(unsupported_int_type(lit_int, lit_int.suffix()))
+        }
+    }
+    if let Lit::Float(lit_float) = def_val {
+        if !matches!(lit_float.suffix(), "" | "f32" | "f64") {
+            return Err(unsupported_float_type(lit_float))
+        }
+    }
+
+    let (min, max) = args.int_range(def_val)?;
+    let (min_len, max_len) = args.len_range(def_val)?;
+    if !matches!(def_val, Lit::Str(_)) {
+        args.check_transform()?;
+        args.check_interpolate()?;
+        args.check_borrowed()?;
+    }
+    let len_in_range = |len: usize| in_range(len as i128, min_len.map(|min| min as i128), max_len.map(|max| max as i128));
+    let len_range_text = range_text(min_len.map(|min| min as i128), max_len.map(|max| max as i128));
+    let pattern = args.pattern(def_val)?;
+    let matcher = pattern.map(pattern_matcher).transpose()?;
+    let matches_pattern = |val: &str| matcher.as_ref().is_none_or(|matcher| matcher(val));
+    let one_of = args.one_of(def_val)?;
+    let is_allowed = |val: &str| one_of.as_ref().is_none_or(|values| values.iter().any(|allowed| allowed == val));
+    // A default with references is expanded at runtime when it's used, but a cycle is known to be one already
+    let composed = match def_val {
+        Lit::Str(def_str) if args.interpolate.is_some() && def_str.value().contains("${") => {
+            args.check_default_refs(&env_var, def_str)?;
+            true
+        },
+        _ => false
+    };
+
+    let baked = args.baked(&env_var);
+    if let Some((name, comp_env_val)) = baked {
+        // Values from scripts often carry stray whitespace, strings only lose it when asked to
+        let comp_env_val = match def_val {
+            Lit::Str(_) => args.baked_string(&env_var, &name, &comp_env_val)?,
+            Lit::Bool(_) | Lit::Byte(_) | Lit::Int(_) | Lit::Float(_) => comp_env_val.trim().to_string(),
+            _ => comp_env_val
+        };
+        let val = split_radix(&comp_env_val).and_then(|(digits, radix)| i128::from_str_radix(&digits, radix).ok());
+        if val.is_some_and(|val| !in_range(val, min, max)) {
+            return Err(syn::Error::new(
+                env_var.span(),
+                format!("Compilation env var {} is out of range {}: {:?}", name, range_text(min, max), comp_env_val)
+            ))
+        }
+        let baked = match def_val {
+            // The value is left out of the error, as length limits are mostly for tokens and keys
+            Lit::Str(_) if !len_in_range(comp_env_val.chars().count()) => Err(syn::Error::new(
+                env_var.span(),
+                format!("Compilation env var {} has a length of {}, outside {}", name, comp_env_val.chars().count(), len_range_text)
+            )),
+            Lit::Str(_) if !matches_pattern(&comp_env_val) => Err(syn::Error::new(
+                env_var.span(),
+                format!("Compilation env var {} does not match the pattern {:?}: {:?}", name, pattern.map(LitStr::value).unwrap_or_default(), comp_env_val)
+            )),
+            Lit::Str(_) if !is_allowed(&comp_env_val) => Err(syn::Error::new(
+                env_var.span(),
+                format!("Compilation env var {} is not one of {:?}: {:?}", name, one_of.unwrap_or_default(), comp_env_val)
+            )),
+            Lit::Str(_) if args.borrowed.is_some() => {
+                let lit = LitStr::new(comp_env_val.as_str(), input.span());
+                Ok(quote! { std::borrow::Cow::<'static, str>::Borrowed(#lit) })
+            },
+            Lit::Str(_) => {
+                let lit = LitStr::new(comp_env_val.as_str(), input.span());
+                Ok(quote! { String::from(#lit) })
+            },
+            Lit::ByteStr(_) => {
+                let lit = syn::LitByteStr::new(comp_env_val.as_bytes(), input.span());
+                Ok(quote! { std::borrow::Cow::<'static, [u8]>::Borrowed(#lit) })
+            },
+            _ => baked_literal(env_var.span(), &name, def_val, &comp_env_val)
+        };
+        return baked.map(|expr| check_divergence(&name, &comp_env_val, expr))
+    }
+
+    let lookup = args.lookup(&env_var);
+    match def_val {
+        Lit::Str(def_str) => {
+            let normalize = args.string_map();
+            let owned_default = if composed {
+                quote! { ::envtime::runtime::interpolate(#def_val) }
+            } else {
+                quote! { String::from(#def_val) }
+            };
+            if args.borrowed.is_none() && min_len.is_none() && max_len.is_none() && pattern.is_none() && one_of.is_none() {
+                let unwrap = if composed {
+                    quote! { .unwrap_or_else(|| #owned_default) }
+                } else {
+                    quote! { .unwrap_or(#owned_default) }
+                };
+                return Ok(quote! {
+                    #lookup #normalize #unwrap
+                })
+            }
+            let len_filter = Some((min_len.unwrap_or(0), max_len))
+                .filter(|_| min_len.is_some() || max_len.is_some())
+                .map(|(min_len, max_len)| {
+                    let max_len = max_len.map_or_else(|| quote! { usize::MAX }, |max| quote! { #max });
+                    quote! { .filter(|s| (#min_len..=#max_len).contains(&s.chars().count())) }
+                });
+            // The pattern is compiled once, the first time a runtime value is checked
+            let pattern_filter = pattern.map(|pattern| quote! {
+                .filter(|s| {
+                    static PATTERN: std::sync::LazyLock<::regex::Regex> =
+                        std::sync::LazyLock::new(|| ::regex::Regex::new(#pattern).unwrap());
+                    PATTERN.is_match(s)
+                })
+            });
+            let one_of_filter = one_of.as_ref().map(|values| quote! {
+                .filter(|s| [#(#values),*].contains(&s.as_str()))
+            });
+            // A default that fails the checks, like an empty one with `min_len`, means the variable has to be set
+            let default = if !len_in_range(def_str.value().chars().count()) {
+                let message = format!("env var {{}} has to be set to a value with a length in {}", len_range_text);
+                quote! { panic!(#message, #env_var) }
+            } else if !matches_pattern(&def_str.value()) {
+                let message = format!("env var {{}} has to be set to a value matching {:?}", pattern.map(LitStr::value).unwrap_or_default());
+                quote! { panic!(#message, #env_var) }
+            } else if !is_allowed(&def_str.value()) {
+                let message = format!("env var {{}} has to be set to one of {:?}", one_of.unwrap_or_default());
+                quote! { panic!(#message, #env_var) }
+            } else if args.borrowed.is_some() && composed {
+                quote! { std::borrow::Cow::<'static, str>::Owned(#owned_default) }
+            } else if args.borrowed.is_some() {
+                quote! { std::borrow::Cow::<'static, str>::Borrowed(#def_val) }
+            } else {
+                owned_default
+            };
+            let unwrap = match args.borrowed {
+                Some(_) => quote! { .map_or_else(|| #default, std::borrow::Cow::Owned) },
+                None => quote! { .unwrap_or_else(|| #default) }
+            };
+            Ok(quote! {
+                #lookup #normalize
+                    #len_filter
+                    #pattern_filter
+                    #one_of_filter
+                    #unwrap
+            })
+        },
+        Lit::ByteStr(_) => Ok(quote! {
+            #lookup.map_or(std::borrow::Cow::<'static, [u8]>::Borrowed(#def_val), |s| std::borrow::Cow::Owned(s.into_bytes()))
+        }),
+        Lit::Bool(_) => {
+            let invalid = on_invalid(&env_var, def_val);
+            Ok(quote! {
+                #lookup.map_or(#def_val, |s| if ::envtime::runtime::is_truthy(&s) {
+                    true
+                } else if ::envtime::runtime::is_falsy(&s) {
+                    false
+                } else {
+                    #invalid
+                })
+            })
+        },
+        Lit::Byte(_) => {
+            let parse = byte_parser();
+            Ok(quote! {
+                #lookup.and_then(#parse).unwrap_or(#def_val)
+            })
+        },
+        Lit::Char(_) => {
+            let parse = char_parser();
+            Ok(quote! {
+                #lookup.and_then(#parse).unwrap_or(#def_val)
+            })
+        },
+        Lit::Int(lit_int) => {
+            let parse = int_parser(lit_int.suffix());
+            let min = min.map(|min| int_tokens(min, lit_int.suffix())).into_iter();
+            let max = max.map(|max| int_tokens(max, lit_int.suffix())).into_iter();
+            let invalid = on_invalid(&env_var, def_val);
+            Ok(quote! {
+                #lookup.map(|s| (#parse)(s)
+                    #(.filter(|val| *val >= #min))*
+                    #(.filter(|val| *val <= #max))*
+                    .unwrap_or_else(|| #invalid))
+                    .unwrap_or(#def_val)
+            })
+        },
+        Lit::Float(_) => {
+            let strip = separator_stripper();
+            Ok(quote! {
+                #lookup.and_then(|s| (#strip)(s.trim()).parse().ok()).unwrap_or(#def_val)
+            })
+        }
+        _ => Err(unsupported_default(def_val))
+    }
+}
+
+/// Only a name and an `env` fallback, as an `Option<String>` without a default
+fn envtime_def_fallback_impl(args: &EnvtimeDefArgs) -> syn::Result<proc_macro2::TokenStream> {
+    let env_var = args.sole_name()?;
+    #[cfg(feature = "manifest")]
+    args.record(&env_var);
+    if let Some(bound) = args.min.as_ref().or(args.max.as_ref()) {
+        return Err(syn::Error::new(bound.span(), "`min` and `max` need an integer default"))
+    }
+    if let Some(bound) = args.min_len.as_ref().or(args.max_len.as_ref()) {
+        return Err(syn::Error::new(bound.span(), "`min_len` and `max_len` need a string literal default"))
+    }
+    if let Some(pattern) = &args.pattern {
+        return Err(syn::Error::new(pattern.span(), "`pattern` needs a string literal default"))
+    }
+    if let Some((_, span)) = &args.one_of {
+        return Err(syn::Error::new(*span, "`one_of` needs a string literal default"))
+    }
+    args.check_borrowed()?;
+
+    if let Some((name, comp_env_val)) = args.baked(&env_var) {
+        let val = args.baked_string(&env_var, &name, &comp_env_val)?;
+        return Ok(check_divergence(&name, &comp_env_val, quote! { Some(String::from(#val)) }))
+    }
+    let lookup = args.lookup(&env_var);
+    let normalize = args.string_map();
+    Ok(quote! {
+        #lookup #normalize
+    })
+}
+
+/// A default expression, a `String` unless an explicit type says which `FromStr` type to parse
+fn envtime_def_expr_impl(args: &EnvtimeDefArgs, env_var: &EnvName, def_val: &DefArg) -> syn::Result<proc_macro2::TokenStream> {
+    if let Some(bound) = args.min.as_ref().or(args.max.as_ref()) {
+        return Err(syn::Error::new(bound.span(), "`min` and `max` need an integer default"))
+    }
+    if let Some(bound) = args.min_len.as_ref().or(args.max_len.as_ref()) {
+        return Err(syn::Error::new(bound.span(), "`min_len` and `max_len` need a string literal default"))
+    }
+    if let Some(pattern) = &args.pattern {
+        return Err(syn::Error::new(pattern.span(), "`pattern` needs a string literal default"))
+    }
+    if let Some((_, span)) = &args.one_of {
+        return Err(syn::Error::new(*span, "`one_of` needs a string literal default"))
+    }
+    args.check_borrowed()?;
+    if let Some(ty) = none_type(def_val) {
+        args.check_transform()?;
+        args.check_interpolate()?;
+        return envtime_def_none_impl(args, env_var, &ty)
+    }
+    let ty = args.ty.clone().map(|path| Type::Path(syn::TypePath { qself: None, path }));
+    if ty.is_some() {
+        args.check_transform()?;
+        args.check_interpolate()?;
+    }
+
+    let baked = args.baked(env_var);
+    if let Some((name, comp_env_val)) = baked {
+        let ty = match &ty {
+            Some(ty) => ty,
+            None => {
+                let val = args.baked_string(env_var, &name, &comp_env_val)?;
+                return Ok(check_divergence(&name, &comp_env_val, quote! { String::from(#val) }))
+            }
+        };
+        let val = comp_env_val.trim();
+        if validate_primitive(ty, val) == Some(false) {
+            return Err(syn::Error::new(
+                env_var.span(),
+                format!("Cannot parse compilation env var {} as {}: {:?}", name, quote! { #ty }, comp_env_val)
+            ))
+        }
+        let message = format!("Cannot parse compilation env var {} as {}", name, quote! { #ty });
+        let value = primitive_literal(ty, val).unwrap_or_else(|| quote! {
+            #val.parse::<#ty>().unwrap_or_else(|_| panic!(#message))
+        });
+        return Ok(check_divergence(&name, &comp_env_val, value))
+    }
+
+    let lookup = args.lookup(env_var);
+    match ty {
+        Some(ty) => {
+            let parse = typed_parser(&ty);
+            Ok(quote! {
+                #lookup.and_then(#parse).unwrap_or_else(|| #def_val)
+            })
+        },
+        None => {
+            let normalize = args.string_map();
+            Ok(quote! {
+                #lookup #normalize.unwrap_or_else(|| String::from(#def_val))
+            })
+        }
+    }
+}
+
+/// The `T` of a `None::<T>` or `Option::<T>::None` default
+fn none_type(def_val: &DefArg) -> Option<Type> {
+    let path = match def_val {
+        DefArg::Path(path) => path,
+        _ => return None
+    };
+    let mut segments = path.segments.iter().rev();
+    let last = segments.next().filter(|last| last.ident == "None")?;
+    let args = match &last.arguments {
+        syn::PathArguments::None => segments.next().filter(|option| option.ident == "Option").map(|option| &option.arguments)?,
+        args => args
+    };
+    match args {
+        syn::PathArguments::AngleBracketed(args) if args.args.len() == 1 => match args.args.first() {
+            Some(syn::GenericArgument::Type(ty)) => Some(ty.clone()),
+            _ => None
+        },
+        _ => None
+    }
+}
+
+/// A `None::<T>` default, an `Option<T>` that's `Some` only when the variable is set
+fn envtime_def_none_impl(args: &EnvtimeDefArgs, env_var: &EnvName, ty: &Type) -> syn::Result<proc_macro2::TokenStream> {
+    if let Some((name, comp_env_val)) = args.baked(env_var) {
+        let val = comp_env_val.trim();
+        if validate_primitive(ty, val) == Some(false) {
+            return Err(syn::Error::new(
+                env_var.span(),
+                format!("Cannot parse compilation env var {} as {}: {:?}", name, quote! { #ty }, comp_env_val)
+            ))
+        }
+        let message = format!("Cannot parse compilation env var {} as {}", name, quote! { #ty });
+        let value = primitive_literal(ty, val).unwrap_or_else(|| quote! {
+            #val.parse::<#ty>().unwrap_or_else(|_| panic!(#message))
+        });
+        return Ok(check_divergence(&name, &comp_env_val, quote! { Some::<#ty>(#value) }))
+    }
+
+    let lookup = args.lookup(env_var);
+    let parse = typed_parser(ty);
+    Ok(quote! {
+        #lookup.and_then(#parse)
+    })
+}
+
+/// Gets a environment variable with a default like `envtime_def!`, reading it at most once at runtime
+///
+/// A variable that isn't baked in is resolved on first use and kept in a `static LazyLock`,
+/// so later uses are a plain load, at the cost of never observing `env::set_var` calls made afterwards.
+/// Each invocation has its own cache. String values are a `&'static str` instead of a `String`,
+/// and since the cached type can't be inferred, unsuffixed integers are `i32` and unsuffixed floats are `f64`.
+/// Without a default, only an `env` fallback, it's an `Option<&'static str>`, and a `None::<T>` default
+/// gives a clone of the cached `Option<T>`.
+/// # Example
+/// ```
+/// use std::env;
+/// use envtime::*;
+///
+/// fn verbose() -> bool {
+///     envtime_cached!("VERBOSE", false)
+/// }
+///
+/// // Assuming the variable isn't set at compile time
+/// env::set_var("VERBOSE", "true");
+/// assert_eq!(verbose(), true);
+/// env::set_var("VERBOSE", "false");
+/// assert_eq!(verbose(), true);
+/// ```
+#[proc_macro]
+pub fn envtime_cached(input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(input as EnvtimeDefArgs);
+    envtime_cached_impl(&args).unwrap_or_else(|e| e.to_compile_error()).into()
+}
+
+fn envtime_cached_impl(args: &EnvtimeDefArgs) -> syn::Result<proc_macro2::TokenStream> {
+    if let Some(span) = args.borrowed {
+        return Err(syn::Error::new(span, "envtime_cached! already gives string values as a `&'static str`, `borrowed` isn't supported"))
+    }
+    let expr = envtime_def_impl(args)?;
+    if args.lits.len() == 1 && args.fallback.is_some() {
+        let env_var = args.sole_name()?;
+        if let Some((name, comp_env_val)) = args.baked(&env_var) {
+            let val = args.baked_string(&env_var, &name, &comp_env_val)?;
+            return Ok(check_divergence(&name, &comp_env_val, quote! { Some(#val) }))
+        }
+        return Ok(quote! {
+            {
+                static CACHED: std::sync::LazyLock<Option<String>> = std::sync::LazyLock::new(|| #expr);
+                CACHED.as_deref()
+            }
+        })
+    }
+    let (env_var, def_val) = args.name_and_default()?;
+    let def_val = &def_val;
+    let baked = args.baked(&env_var);
+    let none_ty = none_type(def_val);
+    let suffix_type = |suffix: &str, unsuffixed: &str| {
+        let ident = syn::Ident::new(if suffix.is_empty() { unsuffixed } else { suffix }, proc_macro2::Span::call_site());
+        quote! { #ident }
+    };
+
+    let ty = match def_val {
+        _ if none_ty.is_some() => {
+            if baked.is_some() {
+                return Ok(expr)
+            }
+            quote! { Option<#none_ty> }
+        },
+        DefArg::Lit(Lit::Str(_)) | DefArg::Path(_) | DefArg::Expr(_) if args.ty.as_ref().is_none_or(|ty| ty.is_ident("String")) => {
+            if let Some((name, comp_env_val)) = baked {
+                let val = args.baked_string(&env_var, &name, &comp_env_val)?;
+                return Ok(check_divergence(&name, &comp_env_val, quote! { #val }))
+            }
+            quote! { String }
+        },
+        DefArg::Lit(Lit::ByteStr(_)) => {
+            if let Some((name, comp_env_val)) = baked {
+                let lit = syn::LitByteStr::new(comp_env_val.as_bytes(), env_var.span());
+                return Ok(check_divergence(&name, &comp_env_val, quote! { &#lit[..] }))
+            }
+            quote! { std::borrow::Cow<'static, [u8]> }
+        },
+        _ if baked.is_some() => return Ok(expr),
+        DefArg::Path(_) | DefArg::Expr(_) => {
+            let ty = &args.ty;
+            quote! { #ty }
+        },
+        DefArg::Lit(Lit::Bool(_)) => quote! { bool },
+        DefArg::Lit(Lit::Byte(_)) => quote! { u8 },
+        DefArg::Lit(Lit::Char(_)) => quote! { char },
+        DefArg::Lit(Lit::Int(lit_int)) => suffix_type(lit_int.suffix(), "i32"),
+        DefArg::Lit(Lit::Float(lit_float)) => suffix_type(lit_float.suffix(), "f64"),
+        DefArg::Lit(def_val) => return Err(unsupported_default(def_val))
+    };
+    let value = match ty.to_string().as_str() {
+        _ if none_ty.is_some() => quote! { CACHED.clone() },
+        "String" => quote! { CACHED.as_str() },
+        _ if matches!(def_val, DefArg::Lit(Lit::ByteStr(_))) => quote! { CACHED.as_ref() },
+        _ => quote! { *CACHED }
+    };
+    Ok(quote! {
+        {
+            static CACHED: std::sync::LazyLock<#ty> = std::sync::LazyLock::new(|| #expr);
+            #value
+        }
+    })
+}
+
+/// A compile time value as a literal of the type of a bool, byte, char, integer or float default
+///
+/// The literal is usable in a `const`, the value is expected to be trimmed already.
+fn baked_literal(span: proc_macro2::Span, name: &str, def_val: &Lit, comp_env_val: &str) -> syn::Result<proc_macro2::TokenStream> {
+    let invalid = |ty: &str| syn::Error::new(
+        span,
+        format!("Cannot parse compilation env var {} as {}: {:?}", name, ty, comp_env_val)
+    );
+    match def_val {
+        Lit::Bool(def_bool) => {
+            let val = match recognize_bool(comp_env_val) {
+                Some(val) => val,
+                None if cfg!(any(feature = "strict", feature = "strict-bool")) => return Err(invalid("bool")),
+                None => def_bool.value
+            };
+            let lit = LitBool::new(val, span);
+            Ok(quote! { #lit })
+        },
+        Lit::Byte(_) => {
+            let mut chars = comp_env_val.chars();
+            if let (Some(c), None) = (chars.next(), chars.next()) {
+                if !c.is_ascii() {
+                    return Err(syn::Error::new(
+                        span,
+                        format!("Compilation env var {} is the non-ASCII character {:?}, which has no byte value", name, c)
+                    ))
+                }
+            }
+            let lit = LitByte::new(parse_byte(comp_env_val).ok_or_else(|| invalid("byte"))?, span);
+            Ok(quote! { #lit })
+        },
+        Lit::Char(_) => {
+            let lit = proc_macro2::Literal::character(parse_char(comp_env_val).ok_or_else(|| invalid("char"))?);
+            Ok(quote! { #lit })
+        },
+        Lit::Int(lit_int) => {
+            let suffix = lit_int.suffix();
+            if !suffix.is_empty() {
+                int_literal(comp_env_val, suffix).ok_or_else(|| match out_of_range(comp_env_val) {
+                    Some(direction) => syn::Error::new(
+                        span,
+                        format!("value {:?} for {} {} {}", comp_env_val, name, direction, suffix)
+                    ),
+                    None => invalid(suffix)
+                })
+            } else {
+                let (digits, radix) = split_radix(comp_env_val).ok_or_else(|| invalid("an integer"))?;
+                match i128::from_str_radix(&digits, radix) {
+                    Ok(val) => Ok(int_tokens(val, "")),
+                    Err(_) => {
+                        let lit = proc_macro2::Literal::u128_unsuffixed(
+                            u128::from_str_radix(&digits, radix).map_err(|_| invalid("an integer"))?
+                        );
+                        Ok(quote! { #lit })
+                    }
+                }
+            }
+        }
+        Lit::Float(lit_float) => match lit_float.suffix() {
+            suffix @ ("f32" | "f64") => float_literal(&strip_separators(comp_env_val), suffix).ok_or_else(|| invalid(suffix)),
+            // Unsuffixed defaults are left to inference, which falls back to f64
+            "" => float_literal(&strip_separators(comp_env_val), "").ok_or_else(|| invalid("f64")),
+            _ => Err(unsupported_float_type(lit_float))
+        }
+        _ => Err(unsupported_default(def_val))
+    }
+}
+
+/// Wraps a baked value to warn once at runtime if the variable is set to something else, with the `warn-divergence` feature
+fn check_divergence(name: &str, baked: &str, expr: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    if !cfg!(feature = "warn-divergence") {
+        return expr
+    }
+    let baked = baked.trim();
+    let message = format!("warning: env var {} differs from the value baked in at compile time, the runtime value is ignored", name);
+    quote! {
+        {
+            static CHECKED: std::sync::Once = std::sync::Once::new();
+            CHECKED.call_once(|| {
+                if std::env::var(#name).is_ok_and(|val| val.trim() != #baked) {
+                    eprintln!("{}", #message);
+                }
+            });
+            #expr
+        }
+    }
+}
+
+/// Tokens for what a runtime value that is set but invalid becomes, the default or a panic with the `strict` feature
+fn on_invalid<N: quote::ToTokens, D: quote::ToTokens>(name: N, default: D) -> proc_macro2::TokenStream {
+    if cfg!(feature = "strict") {
+        quote! { panic!("invalid value for {}", #name) }
+    } else {
+        quote! { #default }
+    }
+}
+
+fn unsupported_float_type(lit: &syn::LitFloat) -> syn::Error {
+    syn::Error::new(lit.span(), format!("Unsupported float type {:?}, expected one of: f32, f64", lit.suffix()))
+}
+
+/// What kind of literal a default is, for errors
+fn lit_kind(lit: &Lit) -> &'static str {
+    match lit {
+        Lit::Str(_) => "string",
+        Lit::ByteStr(_) => "byte string",
+        Lit::CStr(_) => "C string",
+        Lit::Byte(_) => "byte",
+        Lit::Char(_) => "char",
+        Lit::Int(_) => "integer",
+        Lit::Float(_) => "float",
+        Lit::Bool(_) => "bool",
+        _ => "literal"
+    }
+}
+
+fn unsupported_default(def_val: &Lit) -> syn::Error {
+    syn::Error::new(def_val.span(), "Unsupported default value, expected a string, bool, byte, char, integer or float literal")
+}
+
+/// Gets a environment variable as a `Cow<'static, str>` with a default, either at compile or runtime
+///
+/// A compile time value is borrowed from a literal without allocating, a runtime value is owned.
+/// Without a default this yields an `Option<Cow<'static, str>>` instead, which is `None` for an unset variable.
+/// Either way a compile time value can be used in a `const`, unless the `warn-divergence` feature is enabled.
+/// ```
+/// use std::borrow::Cow;
+/// use std::env;
+/// use envtime::*;
+///
+/// // Assuming the variable isn't set at compile time
+/// assert_eq!(envtime_str!("BUILD_ID"), None);
+/// env::set_var("BUILD_ID", "local");
+/// assert_eq!(envtime_str!("BUILD_ID"), Some(Cow::Borrowed("local")));
+/// ```
+///
+/// With `require = lower`, `upper` or `ascii` the value has to already be all lowercase, all uppercase
+/// or all ascii. It is not transformed: a compile time value breaking the rule fails the build,
+/// and a runtime value breaking it falls back to the default, which has to follow the rule too.
+/// # Example
+/// ```
+/// use std::env;
+/// use envtime::*;
+///
+/// // Assuming the variable isn't set at compile time
+/// env::set_var("REGION", "US-EAST-1");
+/// assert_eq!(envtime_str!("REGION", "us-east-1", require = lower), "us-east-1");
+/// env::set_var("REGION", "eu-west-2");
+/// assert_eq!(envtime_str!("REGION", "us-east-1", require = lower), "eu-west-2");
+/// ```
+#[proc_macro]
+pub fn envtime_str(input: TokenStream) -> TokenStream {
+    let StrArgs { name, default, require } = parse_macro_input!(input as StrArgs);
+
+    if let (Some(require), Some(default)) = (&require, &default) {
+        if !require.check(&default.value()) {
+            return syn::Error::new(default.span(), format!("Default does not satisfy `require = {}`", require.name()))
+                .to_compile_error().into()
+        }
+    }
+
+    if let Some(comp_env_val) = comp_env(&name.value()) {
+        if let Some(require) = &require {
+            if !require.check(&comp_env_val) {
+                return syn::Error::new(
+                    name.span(),
+                    format!("Compilation env var {} does not satisfy `require = {}`: {:?}", name.value(), require.name(), comp_env_val)
+                ).to_compile_error().into()
+            }
+        }
+        let lit = LitStr::new(&comp_env_val, name.span());
+        let value = quote! {
+            std::borrow::Cow::<'static, str>::Borrowed(#lit)
+        };
+        let value = match default {
+            Some(_) => value,
+            None => quote! { Some(#value) }
+        };
+        return check_divergence(&name.value(), &comp_env_val, value).into()
+    }
+
+    let check = require.map(|require| require.tokens());
+    let check = check.iter();
+    let lookup = runtime_env(&name);
+    let value = quote! {
+        #lookup
+            #(.filter(#check))*
+    };
+    match default {
+        Some(default) => quote! {
+            #value.map_or(std::borrow::Cow::<'static, str>::Borrowed(#default), std::borrow::Cow::Owned)
+        },
+        None => quote! {
+            #value.map(std::borrow::Cow::<'static, str>::Owned)
+        }
+    }.into()
+}
+
+/// A requirement a string value has to satisfy, without being transformed
+enum Require {
+    Lower,
+    Upper,
+    Ascii,
+}
+
+impl Require {
+    fn name(&self) -> &'static str {
+        match self {
+            Require::Lower => "lower",
+            Require::Upper => "upper",
+            Require::Ascii => "ascii",
+        }
+    }
+
+    fn check(&self, s: &str) -> bool {
+        match self {
+            Require::Lower => s == s.to_lowercase(),
+            Require::Upper => s == s.to_uppercase(),
+            Require::Ascii => s.is_ascii(),
+        }
+    }
+
+    /// Tokens for a `Fn(&String) -> bool` closure mirroring `check` at runtime
+    fn tokens(&self) -> proc_macro2::TokenStream {
+        match self {
+            Require::Lower => quote! { |s: &String| *s == s.to_lowercase() },
+            Require::Upper => quote! { |s: &String| *s == s.to_uppercase() },
+            Require::Ascii => quote! { |s: &String| s.is_ascii() },
+        }
+    }
+}
+
+/// A case conversion `envtime_def!` applies to string values with `transform = "..."`
+enum Transform {
+    Lowercase,
+    Uppercase,
+}
+
+impl Transform {
+    fn apply(&self, s: &str) -> String {
+        match self {
+            Transform::Lowercase => s.to_lowercase(),
+            Transform::Uppercase => s.to_uppercase(),
+        }
+    }
+
+    /// Tokens for a `Fn(String) -> String` closure mirroring `apply` at runtime
+    fn tokens(&self) -> proc_macro2::TokenStream {
+        match self {
+            Transform::Lowercase => quote! { |s: String| s.to_lowercase() },
+            Transform::Uppercase => quote! { |s: String| s.to_uppercase() },
+        }
+    }
+}
+
+impl Parse for Transform {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let lit = input.parse::<LitStr>()?;
+        match lit.value().as_str() {
+            "lowercase" => Ok(Transform::Lowercase),
+            "uppercase" => Ok(Transform::Uppercase),
+            other => Err(syn::Error::new(
+                lit.span(),
+                format!("Unknown transform {:?}, expected \"lowercase\" or \"uppercase\"", other)
+            ))
+        }
+    }
+}
+
+struct StrArgs {
+    name: LitStr,
+    default: Option<LitStr>,
+    require: Option<Require>,
+}
+
+impl Parse for StrArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse()?;
+        let mut default = None;
+        if input.parse::<Option<Token![,]>>()?.is_some() && input.peek(LitStr) {
+            default = Some(input.parse()?);
+            input.parse::<Option<Token![,]>>()?;
+        }
+        let mut require = None;
+        if !input.is_empty() {
+            let key = input.parse::<syn::Ident>()?;
+            if key != "require" {
+                return Err(syn::Error::new(key.span(), "Unknown argument, expected `require`"))
+            }
+            input.parse::<Token![=]>()?;
+            let rule = input.parse::<syn::Ident>()?;
+            require = Some(match rule.to_string().as_str() {
+                "lower" => Require::Lower,
+                "upper" => Require::Upper,
+                "ascii" => Require::Ascii,
+                _ => return Err(syn::Error::new(rule.span(), "Expected `lower`, `upper` or `ascii`"))
+            });
+            input.parse::<Option<Token![,]>>()?;
+        }
+        Ok(StrArgs { name, default, require })
+    }
+}
+
+/// Gets a environment variable as an `Option<bool>`, telling recognized values apart from typos
+///
+/// Truthy values (`1`, `true`, `yes`, `y`, `on`) are `Some(true)`, falsy values (`0`, `false`, `no`, `n`, `off`)
+/// are `Some(false)`, ignoring case and surrounding whitespace, and anything else, like an unset variable or `ture`,
+/// is `None` instead of silently false. This makes it a tri-state for settings where unset means "auto" or "inherit".
+/// With the `strict-bool` feature a compile time value that isn't recognized fails the build instead.
+/// # Example
+/// ```
+/// use std::env;
+/// use envtime::*;
+///
+/// // Assuming the variable isn't set at compile time
+/// env::set_var("VERBOSE", "no");
+/// assert_eq!(envtime_bool!("VERBOSE"), Some(false));
+/// env::set_var("VERBOSE", "ture");
+/// assert_eq!(envtime_bool!("VERBOSE"), None);
+/// ```
+#[proc_macro]
+pub fn envtime_bool(input: TokenStream) -> TokenStream {
+    let name = parse_macro_input!(input as LitStr);
+
+    if let Some(comp_env_val) = comp_env(&name.value()) {
+        let value = match recognize_bool(comp_env_val.trim()) {
+            Some(b) => {
+                let lit = LitBool::new(b, name.span());
+                quote! { Some(#lit) }
+            },
+            None if cfg!(feature = "strict-bool") => return syn::Error::new(
+                name.span(),
+                format!("Cannot parse compilation env var {} as bool: {:?}", name.value(), comp_env_val)
+            ).to_compile_error().into(),
+            None => quote! { None::<bool> }
+        };
+        return check_divergence(&name.value(), &comp_env_val, value).into()
+    }
+
+    let lookup = runtime_env(&name);
+    let parse = bool_parser();
+    quote! {
+        #lookup.and_then(#parse)
+    }.into()
+}
+
+/// Gets a environment variable as an enum variant, mapped from its numeric discriminant
+///
+/// The trimmed env value is parsed as an `i64` and converted with `TryFrom<i64>`, which the enum has to implement.
+/// If the variable is not set, is not an integer, or is not a valid discriminant, the default is used.
+/// The default has to be an integer literal fitting an `i64`, and it is also converted with `TryFrom<i64>`.
+/// A compile time value that isn't an integer fails the build.
+///
+/// # Panics
+/// `TryFrom` can't be called during the build, so a default that isn't a valid discriminant is only
+/// noticed when it's used: it panics whenever the default is needed, including for an unset variable.
+/// # Example
+/// ```
+/// use std::convert::TryFrom;
+/// use envtime::*;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum State { Idle = 0, Running = 1 }
+///
+/// impl TryFrom<i64> for State {
+///     type Error = ();
+///     fn try_from(n: i64) -> Result<Self, ()> {
+///         match n {
+///             0 => Ok(State::Idle),
+///             1 => Ok(State::Running),
+///             _ => Err(()),
+///         }
+///     }
+/// }
+///
+/// // Assuming the variable isn't set
+/// let state = envtime_enum_num!("STATE", State, 0);
+/// assert_eq!(state, State::Idle);
+/// ```
+#[proc_macro]
+pub fn envtime_enum_num(input: TokenStream) -> TokenStream {
+    let EnumNumArgs { name, ty, default } = parse_macro_input!(input as EnumNumArgs);
+
+    let default_val = match default.base10_parse::<i64>() {
+        Ok(val) => proc_macro2::Literal::i64_suffixed(val),
+        Err(_) => return syn::Error::new(default.span(), "Default discriminant has to fit in an i64")
+            .to_compile_error().into()
+    };
+    let default_expr = quote! {
+        <#ty as ::core::convert::TryFrom<i64>>::try_from(#default_val).ok()
+            .expect(concat!("Default discriminant of ", #name, " is not a valid variant"))
+    };
+
+    if let Some(comp_env_val) = comp_env(&name.value()) {
+        let val = match comp_env_val.trim().parse::<i64>() {
+            Ok(val) => proc_macro2::Literal::i64_suffixed(val),
+            Err(_) => return syn::Error::new(
+                name.span(),
+                format!("Cannot parse compilation env var {} as a discriminant: {:?}", name.value(), comp_env_val)
+            ).to_compile_error().into()
+        };
+        return check_divergence(&name.value(), &comp_env_val, quote! {
+            <#ty as ::core::convert::TryFrom<i64>>::try_from(#val).ok()
+                .unwrap_or_else(|| #default_expr)
+        }).into()
+    }
+
+    let lookup = runtime_env(&name);
+    quote! {
+        #lookup
+            .and_then(|s| s.trim().parse::<i64>().ok())
+            .and_then(|n| <#ty as ::core::convert::TryFrom<i64>>::try_from(n).ok())
+            .unwrap_or_else(|| #default_expr)
+    }.into()
+}
+
+/// Checks at runtime whether a file exists, for marker file toggles
+///
+/// This touches the filesystem every time it is evaluated, and is never resolved at compile time.
+/// # Example
+/// ```
+/// use envtime::*;
+///
+/// // Assuming the marker file doesn't exist
+/// let enabled = envtime_file_exists!("/etc/myapp/enabled");
+/// assert_eq!(enabled, false);
+/// ```
+#[proc_macro]
+pub fn envtime_file_exists(input: TokenStream) -> TokenStream {
+    let path = parse_macro_input!(input as LitStr);
+    quote! {
+        std::path::Path::new(#path).exists()
+    }.into()
+}
+
+/// Checks at runtime whether the file named by an environment variable exists
+///
+/// Evaluates to false if the variable is not set or blank, whitespace around the path is ignored.
+/// Like `envtime_file_exists!`, this touches the filesystem and both the variable and the file are checked at runtime.
+/// # Example
+/// ```
+/// use envtime::*;
+///
+/// // Assuming the variable isn't set
+/// let enabled = envtime_file_exists_env!("MARKER_PATH");
+/// assert_eq!(enabled, false);
+/// ```
+#[proc_macro]
+pub fn envtime_file_exists_env(input: TokenStream) -> TokenStream {
+    let lit_str = parse_macro_input!(input as LitStr);
+    let lookup = runtime_env(&lit_str);
+    quote! {
+        #lookup.is_some_and(|path| std::path::Path::new(path.trim()).exists())
+    }.into()
+}
+
+/// Gets a environment variable as a `PathBuf`, either at compile or runtime
+///
+/// The default has to be a string literal. With the `check_exists` flag, a compile time value that doesn't
+/// exist fails the build, relative paths being checked against the directory of the crate's `Cargo.toml`.
+/// Runtime values and the default are never checked.
+/// # Example
+/// ```
+/// use std::env;
+/// use std::path::PathBuf;
+/// use envtime::*;
+///
+/// // Assuming the variable isn't set at compile time
+/// assert_eq!(envtime_path!("ASSET_DIR", "./assets"), PathBuf::from("./assets"));
+/// env::set_var("ASSET_DIR", "/srv/assets");
+/// assert_eq!(envtime_path!("ASSET_DIR", "./assets", check_exists), PathBuf::from("/srv/assets"));
+/// ```
+#[proc_macro]
+pub fn envtime_path(input: TokenStream) -> TokenStream {
+    let PathArgs { name, default, check_exists } = parse_macro_input!(input as PathArgs);
+
+    if let Some(comp_env_val) = comp_env(&name.value()) {
+        if check_exists && manifest_relative(&comp_env_val).is_some_and(|path| !path.exists()) {
+            return syn::Error::new(
+                name.span(),
+                format!("Path {:?} from compilation env var {} doesn't exist", comp_env_val, name.value())
+            ).to_compile_error().into()
+        }
+        let value = quote! {
+            std::path::PathBuf::from(#comp_env_val)
+        };
+        return check_divergence(&name.value(), &comp_env_val, value).into()
+    }
+
+    let lookup = runtime_env(&name);
+    quote! {
+        #lookup.map_or_else(|| std::path::PathBuf::from(#default), std::path::PathBuf::from)
+    }.into()
+}
+
+/// Gets the trimmed contents of the file an environment variable points at, either at compile or runtime
+///
+/// Meant for secrets mounted as files, with a variable like `DB_PASSWORD_FILE` holding the path.
+/// If the variable is set during compilation the file is read then and its contents baked in,
+/// a relative path being relative to the `Cargo.toml` of the crate being compiled, and a file that
+/// can't be read fails the build. Without `CARGO_MANIFEST_DIR` a relative path can't be located and is
+/// left for runtime with a warning. Changes to the file rebuild the crate. At runtime the file is read
+/// when the macro is evaluated, relative to the working directory, and is `None` if it can't be read.
+/// Either way a file that is empty or only whitespace is `None`, like an empty variable.
+/// # Example
+/// ```
+/// use std::env;
+/// use envtime::*;
+///
+/// // Assuming the variable isn't set at compile time
+/// assert_eq!(envtime_file!("DB_PASSWORD_FILE"), None);
+/// env::set_var("DB_PASSWORD_FILE", "/does/not/exist");
+/// assert_eq!(envtime_file!("DB_PASSWORD_FILE"), None);
+/// ```
+///
+/// A file that isn't valid UTF-8 fails the build, and is `None` at runtime like a file that can't be read.
+/// With `utf8 = "strict"` it panics at runtime as well, with `utf8 = "lossy"` invalid bytes are replaced
+/// with `U+FFFD` instead, like `String::from_utf8_lossy` does, at compile time as well as at runtime.
+/// ```
+/// use std::env;
+/// use envtime::*;
+///
+/// // Assuming the variable isn't set at compile time
+/// let path = env::temp_dir().join("envtime-latin1.txt");
+/// std::fs::write(&path, b"caf\xe9").unwrap();
+/// env::set_var("MENU_FILE", &path);
+/// assert_eq!(envtime_file!("MENU_FILE"), None);
+/// assert_eq!(envtime_file!("MENU_FILE", utf8 = "lossy"), Some(String::from("caf\u{FFFD}")));
+/// ```
+#[proc_macro]
+pub fn envtime_file(input: TokenStream) -> TokenStream {
+    let FileArgs { name, utf8 } = parse_macro_input!(input as FileArgs);
+    let utf8 = utf8.as_ref();
+
+    if let Some(comp_env_val) = comp_env(&name.value()) {
+        return match read_comp_file(&name.value(), &comp_env_val, utf8) {
+            Ok(CompFile::Unlocated) => runtime_file(&name, utf8),
+            Ok(CompFile::Read { contents, track }) => {
+                let value = match contents {
+                    Some(contents) => quote! { Some(String::from(#contents)) },
+                    None => quote! { None::<String> }
+                };
+                check_divergence(&name.value(), &comp_env_val, quote! {
+                    {
+                        #track
+                        #value
+                    }
+                })
+            },
+            Err(message) => syn::Error::new(name.span(), message).to_compile_error()
+        }.into()
+    }
+
+    runtime_file(&name, utf8).into()
+}
+
+/// How `envtime_file!` handles a file that isn't valid UTF-8, given with `utf8 = "..."`
+enum Utf8 {
+    /// Fails the build, and panics at runtime
+    Strict,
+    /// Replaces invalid bytes with `U+FFFD`
+    Lossy,
+}
+
+impl Parse for Utf8 {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let lit = input.parse::<LitStr>()?;
+        match lit.value().as_str() {
+            "strict" => Ok(Utf8::Strict),
+            "lossy" => Ok(Utf8::Lossy),
+            other => Err(syn::Error::new(
+                lit.span(),
+                format!("Unknown UTF-8 handling {:?}, expected \"strict\" or \"lossy\"", other)
+            ))
+        }
+    }
+}
+
+/// What `read_comp_file` found at the path in a compile time value
+enum CompFile {
+    /// A relative path that can't be located without `CARGO_MANIFEST_DIR`, to be read at runtime instead
+    Unlocated,
+    /// The file was read
+    Read {
+        /// The contents, trimmed and `None` if blank
+        contents: Option<String>,
+        /// An item having the compiler track the file through `include_bytes!`
+        track: proc_macro2::TokenStream,
+    },
+}
+
+/// Reads the file a compile time value points at, or errors if it can't be read or decoded
+fn read_comp_file(name: &str, path: &str, utf8: Option<&Utf8>) -> Result<CompFile, String> {
+    let full_path = match manifest_relative(path) {
+        Some(full_path) => full_path,
+        None => return Ok(CompFile::Unlocated)
+    };
+    let bytes = std::fs::read(&full_path)
+        .map_err(|err| format!("Cannot read file {:?} from compilation env var {}: {}", path, name, err))?;
+    let contents = match (String::from_utf8(bytes), utf8) {
+        (Ok(contents), _) => contents,
+        (Err(err), Some(Utf8::Lossy)) => String::from_utf8_lossy(err.as_bytes()).into_owned(),
+        (Err(err), _) => return Err(format!("File {:?} from compilation env var {} isn't valid UTF-8: {}", path, name, err.utf8_error()))
+    };
+    let full_path = full_path.to_string_lossy();
+    let contents = Some(contents.trim().to_string()).filter(|contents| !contents.is_empty());
+    Ok(CompFile::Read { contents, track: quote! { const _: &[u8] = include_bytes!(#full_path); } })
+}
+
+/// Tokens for reading the file a variable points at when the program runs, like `runtime_env` for the path
+fn runtime_file<T: quote::ToTokens>(name: T, utf8: Option<&Utf8>) -> proc_macro2::TokenStream {
+    let decode = match utf8 {
+        None => quote! { |bytes: Vec<u8>| String::from_utf8(bytes).ok() },
+        Some(Utf8::Strict) => quote! {
+            |bytes: Vec<u8>| Some(String::from_utf8(bytes).unwrap_or_else(|_| panic!("file from env var {} isn't valid UTF-8", #name)))
+        },
+        Some(Utf8::Lossy) => quote! { |bytes: Vec<u8>| Some(String::from_utf8_lossy(&bytes).into_owned()) }
+    };
+    let lookup = runtime_env(name);
+    quote! {
+        #lookup
+            .and_then(|path| std::fs::read(path.trim()).ok())
+            .and_then(#decode)
+            .map(|contents| contents.trim().to_string())
+            .filter(|contents| !contents.is_empty())
+    }
+}
+
+/// Gets the build time as a `&'static str` RFC 3339 timestamp, always resolved at compile time
+///
+/// If `SOURCE_DATE_EPOCH` is set during compilation it is used (as seconds since the Unix epoch) so
+/// reproducible builds produce identical artifacts, otherwise the current time is used.
+/// The timestamp is in UTC with second precision, formatted as `YYYY-MM-DDTHH:MM:SSZ`.
+/// # Example
+/// ```
+/// use envtime::*;
+///
+/// let built_at: &'static str = envtime_build_time!();
+/// assert_eq!(built_at.len(), "1970-01-01T00:00:00Z".len());
+/// ```
+#[proc_macro]
+pub fn envtime_build_time(input: TokenStream) -> TokenStream {
+    if let Some(token) = proc_macro2::TokenStream::from(input).into_iter().next() {
+        return syn::Error::new(token.span(), "envtime_build_time! takes no arguments")
+            .to_compile_error().into()
+    }
+
+    let secs = match env::var("SOURCE_DATE_EPOCH") {
+        Ok(epoch) => match epoch.parse::<i64>() {
+            Ok(secs) => secs,
+            Err(_) => return syn::Error::new(
+                proc_macro2::Span::call_site(),
+                format!("SOURCE_DATE_EPOCH has to be an integer, found {:?}", epoch)
+            ).to_compile_error().into()
+        },
+        Err(_) => std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs() as i64)
+    };
+
+    let lit = LitStr::new(&format_rfc3339(secs), proc_macro2::Span::call_site());
+    quote! { #lit }.into()
+}
+
+/// Gets a environment variable as an integer clamped into a range, either at compile or runtime
+///
+/// The runtime value, the compile time value and the default are all clamped into the range,
+/// so this always yields an in-range value, even for values that don't fit the type. Values are trimmed
+/// and parsed like `envtime_def!` integers, with `0x`, `0o` and `0b` prefixes and `_` separators.
+/// Runtime values that fail to parse fall back to the default, compile time ones fail the build.
+/// The range bounds have to be integer literals, and either bound may be left open.
+/// # Example
+/// ```
+/// use std::env;
+/// use envtime::*;
+///
+/// // Assuming the variable isn't set at compile time
+/// env::set_var("THREADS", "500");
+/// let threads = envtime_clamped!("THREADS", 8usize, 1..=64);
+/// assert_eq!(threads, 64usize);
+/// ```
+#[proc_macro]
+pub fn envtime_clamped(input: TokenStream) -> TokenStream {
+    let ClampedArgs { name, default, range } = parse_macro_input!(input as ClampedArgs);
+    let suffix = default.suffix();
+
+    let (type_min, type_max) = match int_bounds(suffix) {
+        Some(bounds) => bounds,
+        None => return unsupported_int_type(&default, suffix).to_compile_error().into()
+    };
+    let bound = |lit: &Option<LitInt>| lit.as_ref().map(|lit| lit.base10_parse::<i128>()).transpose();
+    let (lo, hi) = match (bound(&range.lo), bound(&range.hi)) {
+        (Ok(lo), Ok(hi)) => (lo.unwrap_or(type_min), hi.unwrap_or(type_max)),
+        (Err(e), _) | (_, Err(e)) => return e.to_compile_error().into()
+    };
+    let hi = if range.inclusive || range.hi.is_none() { hi } else { hi - 1 };
+    if lo > hi || lo < type_min || hi > type_max {
+        return syn::Error::new(range.span, "Range is empty or out of bounds for the default's type")
+            .to_compile_error().into()
+    }
+
+    let default_val = match default.base10_parse::<i128>() {
+        Ok(val) => int_tokens(val.clamp(lo, hi), suffix),
+        Err(e) => return e.to_compile_error().into()
+    };
+
+    if let Some(comp_env_val) = comp_env(&name.value()) {
+        let parsed = split_radix(comp_env_val.trim()).and_then(|(digits, radix)| i128::from_str_radix(&digits, radix).ok());
+        let val = match parsed {
+            Some(val) => val,
+            None => return syn::Error::new(
+                name.span(),
+                format!("Cannot parse compilation env var {} as an integer: {:?}", name.value(), comp_env_val)
+            ).to_compile_error().into()
+        };
+        return check_divergence(&name.value(), &comp_env_val, int_tokens(val.clamp(lo, hi), suffix)).into()
+    }
+
+    // Parsed as an `i128` first, so values outside of the type are clamped like at compile time
+    let lo = proc_macro2::Literal::i128_suffixed(lo);
+    let hi = proc_macro2::Literal::i128_suffixed(hi);
+    let lookup = runtime_env(&name);
+    quote! {
+        #lookup
+            .and_then(|s| ::envtime::runtime::parse_int::<i128>(&s))
+            .and_then(|n| ::std::convert::TryFrom::try_from(n.clamp(#lo, #hi)).ok())
+            .unwrap_or(#default_val)
+    }.into()
+}
+
+/// Gets a environment variable as a nonzero unsigned integer, like `NonZeroU16`, either at compile or runtime
+///
+/// The type follows the suffix of the default, which has to be one of `u8`, `u16`, `u32`, `u64`, `u128` or `usize`
+/// and nonzero itself. A compile time value of zero fails the build, a runtime value of zero,
+/// or one that fails to parse, falls back to the default.
+/// # Example
+/// ```
+/// use std::env;
+/// use std::num::NonZeroU16;
+/// use envtime::*;
+///
+/// // Assuming the variable isn't set at compile time
+/// env::set_var("LISTEN_PORT", "0");
+/// assert_eq!(envtime_nonzero!("LISTEN_PORT", 8080u16), NonZeroU16::new(8080).unwrap());
+/// env::set_var("LISTEN_PORT", "9090");
+/// assert_eq!(envtime_nonzero!("LISTEN_PORT", 8080u16).get(), 9090);
+/// ```
+#[proc_macro]
+pub fn envtime_nonzero(input: TokenStream) -> TokenStream {
+    let NonZeroArgs { name, default } = parse_macro_input!(input as NonZeroArgs);
+    let suffix = default.suffix();
+
+    let ty = match suffix {
+        "u8" => quote! { std::num::NonZeroU8 },
+        "u16" => quote! { std::num::NonZeroU16 },
+        "u32" => quote! { std::num::NonZeroU32 },
+        "u64" => quote! { std::num::NonZeroU64 },
+        "u128" => quote! { std::num::NonZeroU128 },
+        "usize" => quote! { std::num::NonZeroUsize },
+        _ => return syn::Error::new(
+            default.span(),
+            format!("Unsupported integer type {:?}, expected one of: u8, u16, u32, u64, u128, usize", suffix)
+        ).to_compile_error().into()
+    };
+    if default.base10_parse::<u128>().is_ok_and(|val| val == 0) {
+        return syn::Error::new(default.span(), "The default has to be nonzero").to_compile_error().into()
+    }
+
+    if let Some(comp_env_val) = comp_env(&name.value()) {
+        let comp_env_val = comp_env_val.trim();
+        let lit = match int_literal(comp_env_val, suffix) {
+            Some(lit) => lit,
+            None => return syn::Error::new(
+                name.span(),
+                format!("Cannot parse compilation env var {} as {}: {:?}", name.value(), suffix, comp_env_val)
+            ).to_compile_error().into()
+        };
+        if split_radix(comp_env_val).and_then(|(digits, radix)| u128::from_str_radix(&digits, radix).ok()) == Some(0) {
+            return syn::Error::new(
+                name.span(),
+                format!("Compilation env var {} is zero, expected a nonzero {}", name.value(), suffix)
+            ).to_compile_error().into()
+        }
+        return check_divergence(&name.value(), comp_env_val, quote! { <#ty>::new(#lit).unwrap() }).into()
+    }
+
+    let lookup = runtime_env(&name);
+    let parse = int_parser(suffix);
+    quote! {
+        #lookup.and_then(#parse)
+            .and_then(<#ty>::new)
+            .unwrap_or_else(|| <#ty>::new(#default).unwrap())
+    }.into()
+}
+
+/// Gets a secret environment variable as a String at runtime, never baking it into the binary
+///
+/// The value is always resolved at runtime. With the `regex` feature, an optional `format` pattern
+/// is checked against the compile time value if the variable happens to be set during compilation,
+/// failing the build on a mismatch without the value ever appearing in the generated code.
+/// The runtime value is returned as-is, unless `validate = true` checks it against the format again,
+/// panicking on a mismatch with a message that leaves the value out. A default is never checked.
+/// `regex` has to be a dependency of the calling crate for `validate`, as for the `pattern` of `envtime_def!`.
+/// # Example
+/// ```
+/// use envtime::*;
+///
+/// // Assuming the variable isn't set at runtime
+/// let key = envtime_secret!("API_KEY");
+/// assert_eq!(key, None);
+/// ```
+///
+/// With the `secrets` feature and a default, the value is an `envtime::Secret<String>` instead, whose
+/// `Debug` and `Display` print `[REDACTED]` and whose value is only reachable through `expose()`.
+/// It's still always resolved at runtime.
+/// ```
+/// # #[cfg(feature = "secrets")] {
+/// use std::env;
+/// use envtime::*;
+///
+/// env::set_var("API_TOKEN", "hunter2");
+/// let token = envtime_secret!("API_TOKEN", "");
+/// assert_eq!(format!("{:?}", token), "[REDACTED]");
+/// assert_eq!(token.expose(), "hunter2");
+/// # }
+/// ```
+#[proc_macro]
+pub fn envtime_secret(input: TokenStream) -> TokenStream {
+    let SecretArgs { name, default, format, validate } = parse_macro_input!(input as SecretArgs);
+
+    if let Some(format) = &format {
+        if let Err(e) = check_secret_format(&name, format) {
+            return e.to_compile_error().into()
+        }
+    }
+
+    let lookup = runtime_env(&name);
+    // Like the check during the build, the message leaves the value out
+    let lookup = match format.filter(|_| validate.is_some_and(|validate| validate.value)) {
+        Some(format) => {
+            let message = format!("env var {} does not match the format {:?}", name.value(), format.value());
+            quote! {
+                #lookup.inspect(|s| {
+                    static FORMAT: std::sync::LazyLock<::regex::Regex> =
+                        std::sync::LazyLock::new(|| ::regex::Regex::new(#format).unwrap());
+                    if !FORMAT.is_match(s) {
+                        panic!(#message)
+                    }
+                })
+            }
+        },
+        None => lookup
+    };
+    match default {
+        Some(default) => secret_with_default(&default, lookup).unwrap_or_else(|e| e.to_compile_error()).into(),
+        None => quote! {
+            #lookup
+        }.into()
+    }
+}
+
+#[cfg(feature = "secrets")]
+fn secret_with_default(default: &LitStr, lookup: proc_macro2::TokenStream) -> syn::Result<proc_macro2::TokenStream> {
+    Ok(quote! {
+        ::envtime::Secret::new(#lookup.unwrap_or_else(|| String::from(#default)))
+    })
+}
+
+#[cfg(not(feature = "secrets"))]
+fn secret_with_default(default: &LitStr, _lookup: proc_macro2::TokenStream) -> syn::Result<proc_macro2::TokenStream> {
+    Err(syn::Error::new(default.span(), "A secret with a default requires the `secrets` feature"))
+}
+
+/// Checks compile time values against an `envtime_def!` pattern
+#[cfg(feature = "regex")]
+fn pattern_matcher(pattern: &LitStr) -> syn::Result<impl Fn(&str) -> bool> {
+    let regex = regex::Regex::new(&pattern.value())
+        .map_err(|e| syn::Error::new(pattern.span(), format!("Invalid pattern: {}", e)))?;
+    Ok(move |val: &str| regex.is_match(val))
+}
+
+#[cfg(not(feature = "regex"))]
+fn pattern_matcher(pattern: &LitStr) -> syn::Result<fn(&str) -> bool> {
+    Err(syn::Error::new(pattern.span(), "`pattern` requires the `regex` feature"))
+}
+
+#[cfg(feature = "regex")]
+fn check_secret_format(name: &LitStr, format: &LitStr) -> syn::Result<()> {
+    let regex = regex::Regex::new(&format.value())
+        .map_err(|e| syn::Error::new(format.span(), format!("Invalid format pattern: {}", e)))?;
+    match comp_env(&name.value()) {
+        // The value itself is left out of the message so it doesn't leak into build logs
+        Some(comp_env_val) if !regex.is_match(&comp_env_val) => Err(syn::Error::new(
+            name.span(),
+            format!("Compilation env var {} does not match the format {:?}", name.value(), format.value())
+        )),
+        _ => Ok(())
+    }
+}
+
+#[cfg(not(feature = "regex"))]
+fn check_secret_format(_name: &LitStr, format: &LitStr) -> syn::Result<()> {
+    Err(syn::Error::new(format.span(), "Validating a secret format requires the `regex` feature"))
+}
+
+/// Gets a environment variable as an ordered `Vec<(String, String)>` of `key=value` pairs, either at compile or runtime
+///
+/// Entries are separated by `;`, keys and values are trimmed, and empty entries are ignored.
+/// Unlike a map, the order of the entries is preserved. An unset variable yields an empty `Vec`.
+/// Entries without a `=` or with an empty key are skipped, or with `malformed = error` they fail
+/// the build when the variable is set at compile time, and panic when read at runtime.
+/// # Example
+/// ```
+/// use std::env;
+/// use envtime::*;
+///
+/// // Assuming the variable isn't set at compile time
+/// env::set_var("HEADERS", "accept=text/html; x-trace=1");
+/// let headers = envtime_pairs!("HEADERS");
+/// assert_eq!(headers, vec![
+///     (String::from("accept"), String::from("text/html")),
+///     (String::from("x-trace"), String::from("1")),
+/// ]);
+/// ```
+#[proc_macro]
+pub fn envtime_pairs(input: TokenStream) -> TokenStream {
+    let PairsArgs { name, strict } = parse_macro_input!(input as PairsArgs);
+
+    if let Some(comp_env_val) = comp_env(&name.value()) {
+        let mut pairs = Vec::new();
+        for entry in split_entries(&comp_env_val, ';') {
+            match split_pair(entry) {
+                Some((key, val)) => pairs.push(quote! { (String::from(#key), String::from(#val)) }),
+                None if strict => return syn::Error::new(
+                    name.span(),
+                    format!("Malformed entry in compilation env var {}: {:?}", name.value(), entry)
+                ).to_compile_error().into(),
+                None => {}
+            }
+        }
+        return check_divergence(&name.value(), &comp_env_val, quote! {
+            { let pairs: Vec<(String, String)> = vec![#(#pairs),*]; pairs }
+        }).into()
+    }
+
+    let malformed = if strict {
+        quote! { panic!("Malformed entry in env var {}: {:?}", #name, entry) }
+    } else {
+        quote! { None }
+    };
+    let lookup = runtime_env(&name);
+    quote! {
+        #lookup.map_or_else(Vec::new, |s| s.split(';')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .filter_map(|entry| match entry.split_once('=') {
+                Some((key, val)) if !key.trim().is_empty() =>
+                    Some((String::from(key.trim()), String::from(val.trim()))),
+                _ => #malformed
+            })
+            .collect::<Vec<(String, String)>>())
+    }.into()
+}
+
+/// Gets a environment variable as a `HashMap<String, String>` of `key=value` pairs, either at compile or runtime
+///
+/// Entries are separated by a comma, or the char given with `delim = ';'`, and keys from values by `=`,
+/// or the char given with `pair_delim = ':'`. Keys and values are trimmed and empty entries are ignored.
+/// An entry needs exactly one `=` and a key: a malformed one fails the build when the variable is set
+/// at compile time, and is skipped at runtime. A later entry for the same key replaces an earlier one,
+/// and an unset variable yields an empty map.
+/// # Example
+/// ```
+/// use std::env;
+/// use envtime::*;
+///
+/// // Assuming the variable isn't set at compile time
+/// env::set_var("LABELS", "env=prod, team=core");
+/// let labels = envtime_map!("LABELS");
+/// assert_eq!(labels.get("env").map(String::as_str), Some("prod"));
+/// assert_eq!(labels.get("team").map(String::as_str), Some("core"));
+/// ```
+#[proc_macro]
+pub fn envtime_map(input: TokenStream) -> TokenStream {
+    let MapArgs { name, delim, pair_delim } = parse_macro_input!(input as MapArgs);
+
+    if let Some(comp_env_val) = comp_env(&name.value()) {
+        let mut keys = Vec::new();
+        let mut vals = Vec::new();
+        for entry in split_entries(&comp_env_val, delim) {
+            let mut parts = entry.split(pair_delim);
+            match (parts.next().map(str::trim), parts.next(), parts.next()) {
+                (Some(key), Some(val), None) if !key.is_empty() => {
+                    keys.push(key);
+                    vals.push(val.trim());
+                },
+                _ => return syn::Error::new(
+                    name.span(),
+                    format!("Malformed entry in compilation env var {}, expected one `{}` after a key: {:?}", name.value(), pair_delim, entry)
+                ).to_compile_error().into()
+            }
+        }
+        return check_divergence(&name.value(), &comp_env_val, quote! {
+            {
+                let mut map = std::collections::HashMap::<String, String>::new();
+                #(map.insert(String::from(#keys), String::from(#vals));)*
+                map
+            }
+        }).into()
+    }
+
+    let lookup = runtime_env(&name);
+    quote! {
+        #lookup.map_or_else(std::collections::HashMap::new, |s| s.split(#delim)
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .filter_map(|entry| {
+                let mut parts = entry.split(#pair_delim);
+                match (parts.next().map(str::trim), parts.next(), parts.next()) {
+                    (Some(key), Some(val), None) if !key.is_empty() => Some((String::from(key), String::from(val.trim()))),
+                    _ => None
+                }
+            })
+            .collect::<std::collections::HashMap<String, String>>())
+    }.into()
+}
+
+/// Gets every environment variable starting with a prefix as a `HashMap<String, String>`, either at compile or runtime
+///
+/// Keys have the prefix stripped, and variables with an empty value are left out like unset ones.
+/// If any variable with the prefix is set at compile time, the whole group is baked in, including the ones
+/// from the `.env` file, and the build environment's set of keys is all the map ever has.
+/// Otherwise the runtime environment is filtered where the macro is used.
+/// # Example
+/// ```
+/// use std::env;
+/// use envtime::*;
+///
+/// // Assuming no variable starting with "FLAG_" is set at compile time
+/// env::set_var("FLAG_SEARCH", "on");
+/// env::set_var("FLAG_EXPORT", "off");
+/// let flags = envtime_group!("FLAG_");
+/// assert_eq!(flags.get("SEARCH").map(String::as_str), Some("on"));
+/// assert_eq!(flags.get("EXPORT").map(String::as_str), Some("off"));
+/// ```
+#[proc_macro]
+pub fn envtime_group(input: TokenStream) -> TokenStream {
+    let prefix = parse_macro_input!(input as LitStr);
+    if prefix.value().is_empty() {
+        return syn::Error::new(prefix.span(), "The prefix can't be empty").to_compile_error().into()
+    }
+
+    let group = comp_env_group(&prefix.value());
+    if !group.is_empty() {
+        let (keys, vals): (Vec<_>, Vec<_>) = group.into_iter().unzip();
+        return quote! {
+            {
+                let mut group = std::collections::HashMap::<String, String>::new();
+                #(group.insert(String::from(#keys), String::from(#vals));)*
+                group
+            }
+        }.into()
+    }
+
+    quote! {
+        std::env::vars_os()
+            .filter_map(|(key, val)| Some((key.into_string().ok()?, val.into_string().ok()?)))
+            .filter_map(|(key, val)| Some((key.strip_prefix(#prefix)?.to_string(), val)))
+            .filter(|(key, val)| !key.is_empty() && !val.trim().is_empty())
+            .collect::<std::collections::HashMap<String, String>>()
+    }.into()
+}
+
+/// The variables starting with a prefix during the build, with the prefix stripped and sorted by key,
+/// the environment taking precedence over the `.env` file like `comp_env`
+fn comp_env_group(prefix: &str) -> std::collections::BTreeMap<String, String> {
+    let mut group = std::collections::BTreeMap::new();
+    if cfg!(feature = "runtime-only") {
+        return group
+    }
+    let vars = env::vars_os().filter_map(|(key, val)| Some((key.into_string().ok()?, val.into_string().ok()?)));
+    for (key, val) in dotenv::with_prefix(prefix).into_iter().chain(vars) {
+        let stripped = match key.strip_prefix(prefix) {
+            Some(stripped) if !stripped.is_empty() => stripped.to_string(),
+            _ => continue
+        };
+        // An empty value in the environment hides the `.env` one, as with a single variable
+        if val.trim().is_empty() {
+            group.remove(&stripped);
+        } else {
+            group.insert(stripped, val);
+        }
+    }
+    #[cfg(feature = "trace")]
+    trace(&format!("{}*", prefix), !group.is_empty());
+    group
+}
+
+/// Gets a environment variable with a literal prefix prepended, either at compile or runtime
+///
+/// When the variable is set at compile time, the whole concatenation is baked into a single literal
+/// and the macro evaluates to `Some(&'static str)`. Otherwise it evaluates to an `Option<String>`
+/// built at runtime, which is `None` if the variable isn't set. Use `.as_deref()` to handle both alike.
+/// # Example
+/// ```
+/// use std::env;
+/// use envtime::*;
+///
+/// // Assuming the variable isn't set at compile time
+/// env::set_var("INSTANCE_ID", "42");
+/// let id = envtime_with_prefix_value!("service-", "INSTANCE_ID");
+/// assert_eq!(id.as_deref(), Some("service-42"));
+/// ```
+#[proc_macro]
+pub fn envtime_with_prefix_value(input: TokenStream) -> TokenStream {
+    let PrefixValueArgs { prefix, name } = parse_macro_input!(input as PrefixValueArgs);
+
+    if let Some(comp_env_val) = comp_env(&name.value()) {
+        let literal = LitStr::new(&format!("{}{}", prefix.value(), comp_env_val), name.span());
+        return check_divergence(&name.value(), &comp_env_val, quote! { Some(#literal) }).into()
+    }
+
+    let lookup = runtime_env(&name);
+    quote! {
+        #lookup.map(|val| format!("{}{}", #prefix, val))
+    }.into()
+}
+
+/// Gets a environment variable as a char, given either as the character itself or as a code point
+///
+/// A value that is a single character is always taken literally, so `5` is the character `'5'`.
+/// Otherwise, a value of only decimal digits (`59`), a `0x` prefixed hex value (`0x3B`) or a
+/// `\u{3B}` escape is taken as a unicode code point. Anything else, invalid code points included, fails the build when set at compile time,
+/// and falls back to the default at runtime.
+/// # Example
+/// ```
+/// use std::env;
+/// use envtime::*;
+///
+/// // Assuming the variable isn't set at compile time
+/// env::set_var("SEP", "0x3B");
+/// assert_eq!(envtime_char!("SEP", ','), ';');
+/// ```
+#[proc_macro]
+pub fn envtime_char(input: TokenStream) -> TokenStream {
+    let CharArgs { name, default } = parse_macro_input!(input as CharArgs);
+
+    if let Some(comp_env_val) = comp_env(&name.value()) {
+        return match parse_char(&comp_env_val) {
+            Some(c) => {
+                let lit = syn::LitChar::new(c, name.span());
+                check_divergence(&name.value(), &comp_env_val, quote! { #lit }).into()
+            },
+            None => syn::Error::new(
+                name.span(),
+                format!("Cannot parse compilation env var {} as a char: {:?}", name.value(), comp_env_val)
+            ).to_compile_error().into()
+        }
+    }
+
+    let parse = char_parser();
+    let lookup = runtime_env(&name);
+    quote! {
+        #lookup.and_then(#parse).unwrap_or(#default)
+    }.into()
+}
+
+/// Gets a base64 encoded environment variable as an `Option<Cow<'static, [u8]>>`, decoded at compile or runtime
+///
+/// The value uses the standard alphabet, with or without `=` padding, and surrounding whitespace is ignored.
+/// A compile time value is decoded during the build and borrowed from a byte string literal,
+/// failing the build if it isn't valid base64, the value itself is left out of the error.
+/// A runtime value is decoded into an owned `Vec<u8>`, and is `None` if it isn't valid base64.
+/// # Example
+/// ```
+/// use std::env;
+/// use envtime::*;
+///
+/// // Assuming the variable isn't set at compile time
+/// assert_eq!(envtime_bytes_b64!("SIGNING_KEY"), None);
+/// env::set_var("SIGNING_KEY", "3q2+7w==");
+/// assert_eq!(envtime_bytes_b64!("SIGNING_KEY").as_deref(), Some(&[0xde, 0xad, 0xbe, 0xef][..]));
+/// ```
+#[proc_macro]
+pub fn envtime_bytes_b64(input: TokenStream) -> TokenStream {
+    let name = parse_macro_input!(input as LitStr);
+    envtime_bytes_impl(&name, "base64", decode_base64, base64_decoder()).into()
+}
+
+/// Gets a hex encoded environment variable as an `Option<Cow<'static, [u8]>>`, decoded at compile or runtime
+///
+/// The value is pairs of hex digits in either case, like `001122aabbcc`, with an optional `0x` prefix
+/// and surrounding whitespace ignored. Like `envtime_bytes_b64!`, a compile time value is decoded during the build
+/// and fails it if it has an odd length or a non-hex digit, while a runtime value like that is `None`.
+/// # Example
+/// ```
+/// use std::env;
+/// use envtime::*;
+///
+/// // Assuming the variable isn't set at compile time
+/// env::set_var("MAC_ADDR", "0x001122AABBCC");
+/// assert_eq!(envtime_bytes_hex!("MAC_ADDR").as_deref(), Some(&[0x00, 0x11, 0x22, 0xaa, 0xbb, 0xcc][..]));
+/// ```
+#[proc_macro]
+pub fn envtime_bytes_hex(input: TokenStream) -> TokenStream {
+    let name = parse_macro_input!(input as LitStr);
+    envtime_bytes_impl(&name, "hex", decode_hex, hex_decoder()).into()
+}
+
+/// Bytes decoded by `decode` at compile time, or by the mirroring `decoder` closure tokens at runtime
+fn envtime_bytes_impl(
+    name: &LitStr,
+    encoding: &str,
+    decode: fn(&str) -> Option<Vec<u8>>,
+    decoder: proc_macro2::TokenStream
+) -> proc_macro2::TokenStream {
+    if let Some(comp_env_val) = comp_env(&name.value()) {
+        return match decode(&comp_env_val) {
+            Some(bytes) => {
+                let lit = syn::LitByteStr::new(&bytes, name.span());
+                check_divergence(&name.value(), &comp_env_val, quote! {
+                    Some(std::borrow::Cow::<'static, [u8]>::Borrowed(#lit))
+                })
+            },
+            None => syn::Error::new(
+                name.span(),
+                format!("Cannot decode compilation env var {} as {}", name.value(), encoding)
+            ).to_compile_error()
+        }
+    }
+
+    let lookup = runtime_env(name);
+    quote! {
+        #lookup.and_then(#decoder).map(std::borrow::Cow::<'static, [u8]>::Owned)
+    }
+}
+
+/// Gets a delimited environment variable as a `Vec<T>`, either at compile or runtime
+///
+/// Entries are split on a comma, or the char given with `delim = ';'`, trimmed and parsed with `FromStr`.
+/// Empty entries and entries that fail to parse at runtime are skipped, and an unset variable yields an empty `Vec`.
+/// For primitive element types, a compile time value is baked in with every entry validated during the build.
+/// # Example
+/// ```
+/// use std::env;
+/// use envtime::*;
+///
+/// // Assuming the variables aren't set at compile time
+/// env::set_var("UPSTREAMS", "a.example.com, b.example.com");
+/// assert_eq!(envtime_vec!("UPSTREAMS", String), vec!["a.example.com", "b.example.com"]);
+/// env::set_var("UPSTREAM_PORTS", "80;443;http");
+/// assert_eq!(envtime_vec!("UPSTREAM_PORTS", u16, delim = ';'), vec![80, 443]);
+/// ```
+#[proc_macro]
+pub fn envtime_vec(input: TokenStream) -> TokenStream {
+    let VecArgs { name, ty, delim } = parse_macro_input!(input as VecArgs);
+
+    if let Some(comp_env_val) = comp_env(&name.value()) {
+        let entries = split_entries(&comp_env_val, delim).collect::<Vec<_>>();
+        if let Some(entry) = entries.iter().find(|entry| validate_primitive(&ty, entry) == Some(false)) {
+            return syn::Error::new(
+                name.span(),
+                format!("Cannot parse entry {:?} of compilation env var {} as {}", entry, name.value(), quote! { #ty })
+            ).to_compile_error().into()
+        }
+        let value = match entries.iter().map(|entry| primitive_literal(&ty, entry)).collect::<Option<Vec<_>>>() {
+            Some(lits) => quote! {
+                <std::vec::Vec<#ty>>::from([#(#lits),*])
+            },
+            None => quote! {
+                [#(#entries),*].iter()
+                    .filter_map(|s| s.parse::<#ty>().ok())
+                    .collect::<std::vec::Vec<#ty>>()
+            }
+        };
+        return check_divergence(&name.value(), &comp_env_val, value).into()
+    }
+
+    let lookup = runtime_env(&name);
+    let parse = typed_parser(&ty);
+    quote! {
+        #lookup.map_or_else(std::vec::Vec::new, |s| s.split(#delim)
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .filter_map(|entry| (#parse)(entry.to_string()))
+            .collect::<std::vec::Vec<#ty>>())
+    }.into()
+}
+
+/// Gets a comma separated environment variable as a `HashSet<T>`, either at compile or runtime
+///
+/// Entries are trimmed and parsed with `FromStr`, empty entries and entries that fail to parse are skipped,
+/// and duplicates collapse. An unset variable yields an empty set.
+/// For primitive element types, entries of a compile time value are validated during the build.
+/// # Example
+/// ```
+/// use std::env;
+/// use envtime::*;
+///
+/// // Assuming the variable isn't set at compile time
+/// env::set_var("FEATURES", "search, export, search");
+/// let features = envtime_set!("FEATURES", String);
+/// assert!(features.contains("export"));
+/// assert_eq!(features.len(), 2);
+/// ```
+#[proc_macro]
+pub fn envtime_set(input: TokenStream) -> TokenStream {
+    let NameTypeArgs { name, ty } = parse_macro_input!(input as NameTypeArgs);
+
+    if let Some(comp_env_val) = comp_env(&name.value()) {
+        let entries = split_entries(&comp_env_val, ',').collect::<Vec<_>>();
+        if let Some(entry) = entries.iter().find(|entry| validate_primitive(&ty, entry) == Some(false)) {
+            return syn::Error::new(
+                name.span(),
+                format!("Cannot parse entry {:?} of compilation env var {} as {}", entry, name.value(), quote! { #ty })
+            ).to_compile_error().into()
+        }
+        let value = match entries.iter().map(|entry| primitive_literal(&ty, entry)).collect::<Option<Vec<_>>>() {
+            Some(lits) => quote! {
+                <std::vec::Vec<#ty>>::from([#(#lits),*]).into_iter()
+                    .collect::<std::collections::HashSet<#ty>>()
+            },
+            None => quote! {
+                [#(#entries),*].iter()
+                    .filter_map(|s| s.parse::<#ty>().ok())
+                    .collect::<std::collections::HashSet<#ty>>()
+            }
+        };
+        return check_divergence(&name.value(), &comp_env_val, value).into()
+    }
+
+    let lookup = runtime_env(&name);
+    let parse = typed_parser(&ty);
+    quote! {
+        #lookup.map_or_else(std::collections::HashSet::new, |s| s.split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .filter_map(|entry| (#parse)(entry.to_string()))
+            .collect::<std::collections::HashSet<#ty>>())
+    }.into()
+}
+
+/// Gets a comma separated environment variable as a fixed size array, with an array default, either at compile or runtime
+///
+/// The default is an array of bool, char, integer or float literals and sets both the element type and the length.
+/// Entries are trimmed and parsed like `envtime_def!` values. A compile time value with a different number of
+/// entries, or an entry that doesn't parse, fails the build, and at runtime either falls back to the default.
+/// # Example
+/// ```
+/// use std::env;
+/// use envtime::*;
+///
+/// // Assuming the variable isn't set at compile time
+/// assert_eq!(envtime_array!("COLOR", [0u8, 0u8, 0u8]), [0, 0, 0]);
+/// env::set_var("COLOR", "255, 128, 0");
+/// assert_eq!(envtime_array!("COLOR", [0u8, 0u8, 0u8]), [255, 128, 0]);
+/// env::set_var("COLOR", "255, 128");
+/// assert_eq!(envtime_array!("COLOR", [0u8, 0u8, 0u8]), [0, 0, 0]);
+/// ```
+#[proc_macro]
+pub fn envtime_array(input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(input as ArrayArgs);
+    envtime_array_impl(&args).unwrap_or_else(|e| e.to_compile_error()).into()
+}
+
+fn envtime_array_impl(args: &ArrayArgs) -> syn::Result<proc_macro2::TokenStream> {
+    let ArrayArgs { name, default } = args;
+    let first = default.first().ok_or_else(|| syn::Error::new(name.span(), "The default array needs at least one element"))?;
+    for elem in default {
+        let (supported, same_kind) = match (first, elem) {
+            (Lit::Bool(_), Lit::Bool(_)) | (Lit::Char(_), Lit::Char(_)) => (true, true),
+            (Lit::Int(_), Lit::Int(lit_int)) => (int_bounds(lit_int.suffix()).is_some(), true),
+            (Lit::Float(_), Lit::Float(lit_float)) => (matches!(lit_float.suffix(), "" | "f32" | "f64"), true),
+            (_, elem) => (matches!(elem, Lit::Bool(_) | Lit::Char(_) | Lit::Int(_) | Lit::Float(_)), false)
+        };
+        if !supported {
+            return Err(syn::Error::new(elem.span(), "Unsupported array element, expected a bool, char, integer or float literal"))
+        }
+        if !same_kind {
+            return Err(syn::Error::new(elem.span(), "The elements of the default array have to be the same kind of literal"))
+        }
+    }
+
+    if let Some(comp_env_val) = comp_env(&name.value()) {
+        let entries = comp_env_val.split(',').map(str::trim).collect::<Vec<_>>();
+        if entries.len() != default.len() {
+            return Err(syn::Error::new(
+                name.span(),
+                format!("Compilation env var {} has {} entries, expected {}: {:?}", name.value(), entries.len(), default.len(), comp_env_val)
+            ))
+        }
+        let lits = default.iter().zip(&entries).map(|(elem, entry)| match elem {
+            // Unlike `envtime_def!`, an unrecognized bool isn't silently replaced by the default's element
+            Lit::Bool(_) if recognize_bool(entry).is_none() => Err(syn::Error::new(
+                name.span(),
+                format!("Cannot parse compilation env var {} as bool: {:?}", name.value(), entry)
+            )),
+            elem => baked_literal(name.span(), &name.value(), elem, entry)
+        }).collect::<syn::Result<Vec<_>>>()?;
+        return Ok(check_divergence(&name.value(), &comp_env_val, quote! { [#(#lits),*] }))
+    }
+
+    let parse = match first {
+        Lit::Bool(_) => bool_parser(),
+        Lit::Char(_) => char_parser(),
+        Lit::Int(lit_int) => int_parser(lit_int.suffix()),
+        _ => {
+            let strip = separator_stripper();
+            quote! { |s: String| (#strip)(&s).parse().ok() }
+        }
+    };
+    let lookup = runtime_env(name);
+    let default = default.iter();
+    Ok(quote! {
+        #lookup.and_then(|s| s.split(',')
+            .map(|entry| (#parse)(entry.trim().to_string()))
+            .collect::<Option<std::vec::Vec<_>>>())
+            .and_then(|entries| ::std::convert::TryFrom::try_from(entries).ok())
+            .unwrap_or([#(#default),*])
+    })
+}
+
+/// Gets a environment variable parsed as an `Option<T>`, either at compile or runtime
+///
+/// Unlike `envtime_def!` there's no default, an unset variable or a value that fails to parse is `None`,
+/// so an unset variable can be told apart from a configured one.
+/// Primitive types are parsed like `envtime_def!` literals, with the recognized bool words and integer radix prefixes,
+/// other types with `FromStr`. A primitive compile time value is baked in as a literal, or as `None` if it doesn't parse,
+/// other types parse the baked value where it's used. A baked value is never replaced by the runtime one.
+/// # Example
+/// ```
+/// use std::env;
+/// use envtime::*;
+///
+/// // Assuming the variable isn't set at compile time
+/// assert_eq!(envtime_opt!("LISTEN_PORT", u16), None);
+/// env::set_var("LISTEN_PORT", "8080");
+/// assert_eq!(envtime_opt!("LISTEN_PORT", u16), Some(8080));
+/// ```
+#[proc_macro]
+pub fn envtime_opt(input: TokenStream) -> TokenStream {
+    let NameTypeArgs { name, ty } = parse_macro_input!(input as NameTypeArgs);
+    envtime_opt_impl(&name, &ty).into()
+}
+
+fn envtime_opt_impl(name: &LitStr, ty: &Type) -> proc_macro2::TokenStream {
+    if let Some(comp_env_val) = comp_env(&name.value()) {
+        // A baked value that doesn't parse is `None` like at runtime, the runtime value is never looked at
+        let value = match (primitive_name(ty), primitive_literal(ty, &comp_env_val)) {
+            (_, Some(lit)) => quote! { Some(#lit) },
+            (Some(_), None) => quote! { None::<#ty> },
+            (None, None) => quote! { #comp_env_val.parse::<#ty>().ok() }
+        };
+        return check_divergence(&name.value(), &comp_env_val, value)
+    }
+
+    let lookup = runtime_env(name);
+    let parse = primitive_parser(ty).unwrap_or_else(|| quote! { |s: String| s.parse::<#ty>().ok() });
+    quote! {
+        #lookup.and_then(#parse)
+    }
+}
+
+/// Gets a environment variable parsed as a `Result<T, envtime::ParseError>`, either at compile or runtime
+///
+/// Like `envtime_opt!`, but an unset variable is `Err(ParseError::NotSet)` and a value that fails to parse
+/// is `Err(ParseError::Invalid(value))` with the value as it was set, for building your own error messages.
+/// Values are parsed and baked in like with `envtime_opt!`, a primitive compile time value that doesn't parse
+/// is baked in as the `Err`.
+/// # Example
+/// ```
+/// use std::env;
+/// use envtime::*;
+///
+/// // Assuming the variable isn't set at compile time
+/// assert_eq!(envtime_try!("LISTEN_PORT", u16), Err(ParseError::NotSet));
+/// env::set_var("LISTEN_PORT", "http");
+/// assert_eq!(envtime_try!("LISTEN_PORT", u16), Err(ParseError::Invalid(String::from("http"))));
+/// env::set_var("LISTEN_PORT", "8080");
+/// assert_eq!(envtime_try!("LISTEN_PORT", u16), Ok(8080));
+/// ```
+#[proc_macro]
+pub fn envtime_try(input: TokenStream) -> TokenStream {
+    let NameTypeArgs { name, ty } = parse_macro_input!(input as NameTypeArgs);
+
+    if let Some(comp_env_val) = comp_env(&name.value()) {
+        let value = match (primitive_name(&ty), primitive_literal(&ty, &comp_env_val)) {
+            (_, Some(lit)) => quote! {
+                Ok::<#ty, ::envtime::ParseError>(#lit)
+            },
+            (Some(_), None) => quote! {
+                Err::<#ty, _>(::envtime::ParseError::Invalid(String::from(#comp_env_val)))
+            },
+            (None, None) => quote! {
+                #comp_env_val.parse::<#ty>().map_err(|_| ::envtime::ParseError::Invalid(String::from(#comp_env_val)))
+            }
+        };
+        return check_divergence(&name.value(), &comp_env_val, value).into()
+    }
+
+    let lookup = runtime_env(&name);
+    let parse = match primitive_parser(&ty) {
+        Some(parse) => quote! { (#parse)(s.clone()).ok_or(::envtime::ParseError::Invalid(s)) },
+        None => quote! { s.parse::<#ty>().map_err(|_| ::envtime::ParseError::Invalid(s)) }
+    };
+    quote! {
+        match #lookup {
+            Some(s) => #parse,
+            None => Err(::envtime::ParseError::NotSet)
+        }
+    }.into()
+}
+
+/// Gets a environment variable parsed as any `FromStr` type, either at compile or runtime
+///
+/// Without a default this yields an `Option<T>` like `envtime_opt!`, with a default expression as the third argument
+/// it yields a `T`, falling back to the default when the variable is unset or fails to parse at runtime.
+/// A compile time value is parsed where it's used, for primitive types it's validated during the build,
+/// for other types a compile time value that fails to parse panics rather than falling back.
+/// # Example
+/// ```
+/// use std::env;
+/// use std::net::SocketAddr;
+/// use envtime::*;
+///
+/// // Assuming the variable isn't set at compile time
+/// let addr = envtime_parse!("BIND_ADDR", SocketAddr, SocketAddr::from(([127, 0, 0, 1], 8080)));
+/// assert_eq!(addr.port(), 8080);
+/// env::set_var("BIND_ADDR", "0.0.0.0:80");
+/// assert_eq!(envtime_parse!("BIND_ADDR", SocketAddr), Some(SocketAddr::from(([0, 0, 0, 0], 80))));
+/// ```
+#[proc_macro]
+pub fn envtime_parse(input: TokenStream) -> TokenStream {
+    let ParseArgs { name, ty, default } = parse_macro_input!(input as ParseArgs);
+
+    if let Some(comp_env_val) = comp_env(&name.value()) {
+        if validate_primitive(&ty, &comp_env_val) == Some(false) {
+            return syn::Error::new(
+                name.span(),
+                format!("Cannot parse compilation env var {} as {}: {:?}", name.value(), quote! { #ty }, comp_env_val)
+            ).to_compile_error().into()
+        }
+        let message = format!("Cannot parse compilation env var {} as {}", name.value(), quote! { #ty });
+        let value = primitive_literal(&ty, &comp_env_val).unwrap_or_else(|| quote! {
+            #comp_env_val.parse::<#ty>().unwrap_or_else(|_| panic!(#message))
+        });
+        let value = match default {
+            Some(_) => value,
+            None => quote! { Some(#value) }
+        };
+        return check_divergence(&name.value(), &comp_env_val, value).into()
+    }
+
+    let lookup = runtime_env(&name);
+    let parse = primitive_parser(&ty).unwrap_or_else(|| quote! { |s: String| s.parse::<#ty>().ok() });
+    let parsed = quote! {
+        #lookup.and_then(#parse)
+    };
+    match default {
+        Some(default) => quote! { #parsed.unwrap_or_else(|| #default) },
+        None => parsed
+    }.into()
+}
+
+/// Gets a environment variable as a `Duration` with a default, either at compile or runtime
+///
+/// Durations are written as an integer with an optional `ms`, `s`, `m` or `h` unit, bare numbers are seconds.
+/// The default is written the same way and checked during the build, as is a compile time value.
+/// A runtime value that fails to parse falls back to the default.
+/// # Example
+/// ```
+/// use std::env;
+/// use std::time::Duration;
+/// use envtime::*;
+///
+/// // Assuming the variable isn't set at compile time
+/// assert_eq!(envtime_duration!("REQUEST_TIMEOUT", "30s"), Duration::from_secs(30));
+/// env::set_var("REQUEST_TIMEOUT", "500ms");
+/// assert_eq!(envtime_duration!("REQUEST_TIMEOUT", "30s"), Duration::from_millis(500));
+/// ```
+#[proc_macro]
+pub fn envtime_duration(input: TokenStream) -> TokenStream {
+    let NameStrArgs { name, default } = parse_macro_input!(input as NameStrArgs);
+
+    let default_millis = match parse_duration_millis(&default.value()) {
+        Some(millis) => proc_macro2::Literal::u64_suffixed(millis),
+        None => return syn::Error::new(
+            default.span(),
+            format!("Cannot parse default {:?} as a duration, expected a number with an optional ms, s, m or h unit", default.value())
+        ).to_compile_error().into()
+    };
+
+    if let Some(comp_env_val) = comp_env(&name.value()) {
+        return match parse_duration_millis(&comp_env_val) {
+            Some(millis) => {
+                let millis = proc_macro2::Literal::u64_suffixed(millis);
+                check_divergence(&name.value(), &comp_env_val, quote! { std::time::Duration::from_millis(#millis) }).into()
+            },
+            None => syn::Error::new(
+                name.span(),
+                format!("Cannot parse compilation env var {} as a duration: {:?}", name.value(), comp_env_val)
+            ).to_compile_error().into()
+        }
+    }
+
+    let parse = duration_parser();
+    let lookup = runtime_env(&name);
+    quote! {
+        #lookup.and_then(#parse).unwrap_or(std::time::Duration::from_millis(#default_millis))
+    }.into()
+}
+
+/// Gets a environment variable as an `IpAddr` with a default, either at compile or runtime
+///
+/// Both IPv4 and IPv6 addresses work, surrounding whitespace is ignored.
+/// The default is checked during the build, as is a compile time value,
+/// and a runtime value that fails to parse falls back to the default.
+/// # Example
+/// ```
+/// use std::env;
+/// use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+/// use envtime::*;
+///
+/// // Assuming the variable isn't set at compile time
+/// assert_eq!(envtime_ip!("BIND_IP", "127.0.0.1"), IpAddr::V4(Ipv4Addr::LOCALHOST));
+/// env::set_var("BIND_IP", "::1");
+/// assert_eq!(envtime_ip!("BIND_IP", "127.0.0.1"), IpAddr::V6(Ipv6Addr::LOCALHOST));
+/// ```
+#[proc_macro]
+pub fn envtime_ip(input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(input as NameStrArgs);
+    envtime_addr_impl::<std::net::IpAddr>(&args, "IpAddr").into()
+}
+
+/// Gets a environment variable as a `SocketAddr` with a default, either at compile or runtime
+///
+/// An IPv6 address needs brackets, like `[::1]:8080`. Otherwise this works the same as `envtime_ip!`.
+/// # Example
+/// ```
+/// use std::env;
+/// use std::net::SocketAddr;
+/// use envtime::*;
+///
+/// // Assuming the variable isn't set at compile time
+/// assert_eq!(envtime_socket!("BIND_ADDR", "0.0.0.0:8080"), SocketAddr::from(([0, 0, 0, 0], 8080)));
+/// env::set_var("BIND_ADDR", "not an address");
+/// assert_eq!(envtime_socket!("BIND_ADDR", "0.0.0.0:8080").port(), 8080);
+/// ```
+#[proc_macro]
+pub fn envtime_socket(input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(input as NameStrArgs);
+    envtime_addr_impl::<std::net::SocketAddr>(&args, "SocketAddr").into()
+}
+
+/// A `std::net` address type, parsed from the validated string where it's used
+fn envtime_addr_impl<T: std::str::FromStr>(args: &NameStrArgs, ty: &str) -> proc_macro2::TokenStream {
+    let NameStrArgs { name, default } = args;
+    let ty_ident = syn::Ident::new(ty, proc_macro2::Span::call_site());
+    if default.value().parse::<T>().is_err() {
+        return syn::Error::new(default.span(), format!("Cannot parse default {:?} as {}", default.value(), ty))
+            .to_compile_error()
+    }
+
+    if let Some(comp_env_val) = comp_env(&name.value()) {
+        let val = comp_env_val.trim();
+        if val.parse::<T>().is_err() {
+            return syn::Error::new(
+                name.span(),
+                format!("Cannot parse compilation env var {} as {}: {:?}", name.value(), ty, comp_env_val)
+            ).to_compile_error()
+        }
+        return check_divergence(&name.value(), &comp_env_val, quote! {
+            #val.parse::<std::net::#ty_ident>().unwrap()
+        })
+    }
+
+    let lookup = runtime_env(name);
+    quote! {
+        #lookup.and_then(|s| s.trim().parse::<std::net::#ty_ident>().ok())
+            .unwrap_or_else(|| #default.parse::<std::net::#ty_ident>().unwrap())
+    }
+}
+
+/// Gets a environment variable as a `semver::Version` with a default, either at compile or runtime
+///
+/// Requires the `semver` feature, and `semver` has to be a dependency of the calling crate.
+/// The default and a compile time value are validated during the build, a malformed one fails it.
+/// A plain `MAJOR.MINOR.PATCH` version is constructed directly, one with a pre-release or build metadata
+/// is parsed from the validated string where it's used. A runtime value that doesn't parse falls back to the default.
+/// # Example
+/// ```
+/// # #[cfg(feature = "semver")] {
+/// use std::env;
+/// use envtime::*;
+///
+/// // Assuming the variable isn't set at compile time
+/// assert_eq!(envtime_semver!("MIN_VERSION", "1.2.3"), semver::Version::new(1, 2, 3));
+/// env::set_var("MIN_VERSION", "2.0.0-rc.1");
+/// assert_eq!(envtime_semver!("MIN_VERSION", "1.2.3").pre.as_str(), "rc.1");
+/// env::set_var("MIN_VERSION", "2.0");
+/// assert_eq!(envtime_semver!("MIN_VERSION", "1.2.3"), semver::Version::new(1, 2, 3));
+/// # }
+/// ```
+#[proc_macro]
+pub fn envtime_semver(input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(input as NameStrArgs);
+    semver(&args).unwrap_or_else(|e| e.to_compile_error()).into()
+}
+
+#[cfg(feature = "semver")]
+fn semver(args: &NameStrArgs) -> syn::Result<proc_macro2::TokenStream> {
+    let NameStrArgs { name, default } = args;
+    let default_version = default.value().parse::<semver::Version>().map_err(|e| {
+        syn::Error::new(default.span(), format!("Cannot parse default {:?} as a version ({})", default.value(), e))
+    })?;
+    let default = version_tokens(&default_version, &default.value());
+
+    if let Some(comp_env_val) = comp_env(&name.value()) {
+        let val = comp_env_val.trim();
+        let version = val.parse::<semver::Version>().map_err(|e| syn::Error::new(
+            name.span(),
+            format!("Cannot parse compilation env var {} as a version: {:?} ({})", name.value(), comp_env_val, e)
+        ))?;
+        return Ok(check_divergence(&name.value(), &comp_env_val, version_tokens(&version, val)))
+    }
+
+    let lookup = runtime_env(name);
+    Ok(quote! {
+        #lookup.and_then(|s| s.trim().parse::<::semver::Version>().ok())
+            .unwrap_or_else(|| #default)
+    })
+}
+
+/// Tokens for a validated version, constructed directly unless it has a pre-release or build metadata
+#[cfg(feature = "semver")]
+fn version_tokens(version: &semver::Version, val: &str) -> proc_macro2::TokenStream {
+    if !version.pre.is_empty() || !version.build.is_empty() {
+        return quote! { #val.parse::<::semver::Version>().unwrap() }
+    }
+    let (major, minor, patch) = (
+        proc_macro2::Literal::u64_suffixed(version.major),
+        proc_macro2::Literal::u64_suffixed(version.minor),
+        proc_macro2::Literal::u64_suffixed(version.patch)
+    );
+    quote! { ::semver::Version::new(#major, #minor, #patch) }
+}
+
+#[cfg(not(feature = "semver"))]
+fn semver(args: &NameStrArgs) -> syn::Result<proc_macro2::TokenStream> {
+    Err(syn::Error::new(args.name.span(), "envtime_semver! requires the `semver` feature"))
+}
+
+/// Gets a environment variable as a `chrono::DateTime<Utc>` with a default, either at compile or runtime
+///
+/// Requires the `chrono` feature, and `chrono` has to be a dependency of the calling crate.
+/// Values are RFC 3339 timestamps, one with an offset is converted to UTC.
+/// The default and a compile time value are validated during the build, a malformed one fails it,
+/// and are constructed from their timestamp. A runtime value that doesn't parse falls back to the default.
+/// # Example
+/// ```
+/// # #[cfg(feature = "chrono")] {
+/// use std::env;
+/// use envtime::*;
+///
+/// // Assuming the variable isn't set at compile time
+/// assert_eq!(envtime_datetime!("CUTOFF", "2024-01-01T00:00:00Z").timestamp(), 1704067200);
+/// env::set_var("CUTOFF", "2024-01-01T02:00:00+02:00");
+/// assert_eq!(envtime_datetime!("CUTOFF", "2023-06-01T00:00:00Z").timestamp(), 1704067200);
+/// env::set_var("CUTOFF", "2024-01-01");
+/// assert_eq!(envtime_datetime!("CUTOFF", "2024-01-01T00:00:00Z").timestamp(), 1704067200);
+/// # }
+/// ```
+#[proc_macro]
+pub fn envtime_datetime(input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(input as NameStrArgs);
+    datetime(&args).unwrap_or_else(|e| e.to_compile_error()).into()
+}
+
+#[cfg(feature = "chrono")]
+fn datetime(args: &NameStrArgs) -> syn::Result<proc_macro2::TokenStream> {
+    let NameStrArgs { name, default } = args;
+    let default_datetime = chrono::DateTime::parse_from_rfc3339(default.value().trim()).map_err(|e| {
+        syn::Error::new(default.span(), format!("Cannot parse default {:?} as an RFC 3339 timestamp ({})", default.value(), e))
+    })?;
+    let default = datetime_tokens(&default_datetime);
+
+    if let Some(comp_env_val) = comp_env(&name.value()) {
+        let datetime = chrono::DateTime::parse_from_rfc3339(comp_env_val.trim()).map_err(|e| syn::Error::new(
+            name.span(),
+            format!("Cannot parse compilation env var {} as an RFC 3339 timestamp: {:?} ({})", name.value(), comp_env_val, e)
+        ))?;
+        return Ok(check_divergence(&name.value(), &comp_env_val, datetime_tokens(&datetime)))
+    }
+
+    let lookup = runtime_env(name);
+    Ok(quote! {
+        #lookup.and_then(|s| ::chrono::DateTime::parse_from_rfc3339(s.trim()).ok())
+            .map(|datetime| datetime.with_timezone(&::chrono::Utc))
+            .unwrap_or_else(|| #default)
+    })
+}
+
+/// Tokens constructing a validated timestamp in UTC
+#[cfg(feature = "chrono")]
+fn datetime_tokens(datetime: &chrono::DateTime<chrono::FixedOffset>) -> proc_macro2::TokenStream {
+    let secs = proc_macro2::Literal::i64_suffixed(datetime.timestamp());
+    let nanos = proc_macro2::Literal::u32_suffixed(datetime.timestamp_subsec_nanos());
+    quote! { ::chrono::DateTime::<::chrono::Utc>::from_timestamp(#secs, #nanos).unwrap() }
+}
+
+#[cfg(not(feature = "chrono"))]
+fn datetime(args: &NameStrArgs) -> syn::Result<proc_macro2::TokenStream> {
+    Err(syn::Error::new(args.name.span(), "envtime_datetime! requires the `chrono` feature"))
+}
+
+/// Gets a environment variable as a `log::LevelFilter` with a default, either at compile or runtime
+///
+/// Requires the `log` feature, and `log` has to be a dependency of the calling crate.
+/// The level is one of `off`, `error`, `warn`, `info`, `debug` or `trace`, in any case.
+/// The default and a compile time value are checked during the build, an unknown level fails it.
+/// A runtime value that isn't a level falls back to the default.
+/// # Example
+/// ```
+/// # #[cfg(feature = "log")] {
+/// use std::env;
+/// use envtime::*;
+///
+/// // Assuming the variable isn't set at compile time
+/// assert_eq!(envtime_log_level!("LOG_LEVEL", "info"), log::LevelFilter::Info);
+/// env::set_var("LOG_LEVEL", "DEBUG");
+/// assert_eq!(envtime_log_level!("LOG_LEVEL", "info"), log::LevelFilter::Debug);
+/// env::set_var("LOG_LEVEL", "verbose");
+/// assert_eq!(envtime_log_level!("LOG_LEVEL", "info"), log::LevelFilter::Info);
+/// # }
+/// ```
+#[proc_macro]
+pub fn envtime_log_level(input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(input as NameStrArgs);
+    log_level(&args).unwrap_or_else(|e| e.to_compile_error()).into()
+}
+
+/// The level names `envtime_log_level!` accepts, with their `log::LevelFilter` variant
+#[cfg(feature = "log")]
+const LOG_LEVELS: &[(&str, &str)] = &[
+    ("off", "Off"),
+    ("error", "Error"),
+    ("warn", "Warn"),
+    ("info", "Info"),
+    ("debug", "Debug"),
+    ("trace", "Trace"),
+];
+
+#[cfg(feature = "log")]
+fn log_level(args: &NameStrArgs) -> syn::Result<proc_macro2::TokenStream> {
+    let NameStrArgs { name, default } = args;
+    let expected = LOG_LEVELS.iter().map(|(level, _)| *level).collect::<Vec<_>>().join(", ");
+    let default = level_variant(&default.value()).ok_or_else(|| syn::Error::new(
+        default.span(),
+        format!("Unknown default log level {:?}, expected one of: {}", default.value(), expected)
+    ))?;
+
+    if let Some(comp_env_val) = comp_env(&name.value()) {
+        return level_variant(&comp_env_val).map(|level| check_divergence(&name.value(), &comp_env_val, level)).ok_or_else(|| syn::Error::new(
+            name.span(),
+            format!("Compilation env var {} is not a log level: {:?}, expected one of: {}", name.value(), comp_env_val, expected)
+        ))
+    }
+
+    let lookup = runtime_env(name);
+    let (levels, variants): (Vec<_>, Vec<_>) = LOG_LEVELS.iter()
+        .map(|(level, variant)| (*level, syn::Ident::new(variant, proc_macro2::Span::call_site())))
+        .unzip();
+    Ok(quote! {
+        #lookup.and_then(|s| match s.trim().to_ascii_lowercase().as_str() {
+            #(#levels => Some(::log::LevelFilter::#variants),)*
+            _ => None
+        }).unwrap_or(#default)
+    })
+}
+
+/// Tokens for the `log::LevelFilter` variant a level name stands for, ignoring case and surrounding whitespace
+#[cfg(feature = "log")]
+fn level_variant(val: &str) -> Option<proc_macro2::TokenStream> {
+    let val = val.trim().to_ascii_lowercase();
+    LOG_LEVELS.iter().find(|(level, _)| *level == val).map(|(_, variant)| {
+        let variant = syn::Ident::new(variant, proc_macro2::Span::call_site());
+        quote! { ::log::LevelFilter::#variant }
+    })
+}
+
+#[cfg(not(feature = "log"))]
+fn log_level(args: &NameStrArgs) -> syn::Result<proc_macro2::TokenStream> {
+    Err(syn::Error::new(args.name.span(), "envtime_log_level! requires the `log` feature"))
+}
+
+/// Gets a environment variable as an `Option<Duration>`, either at compile or runtime
+///
+/// Durations are written as an integer with an optional `ms`, `s`, `m` or `h` unit, bare numbers are seconds.
+/// An unset variable is `None`, meaning no duration was configured, as is a runtime value that fails to parse.
+/// A compile time value that fails to parse fails the build.
+/// # Example
+/// ```
+/// use std::env;
+/// use std::time::Duration;
+/// use envtime::*;
+///
+/// // Assuming the variable isn't set at compile time
+/// assert_eq!(envtime_opt_duration!("TIMEOUT"), None);
+/// env::set_var("TIMEOUT", "1500ms");
+/// assert_eq!(envtime_opt_duration!("TIMEOUT"), Some(Duration::from_millis(1500)));
+/// ```
+#[proc_macro]
+pub fn envtime_opt_duration(input: TokenStream) -> TokenStream {
+    let name = parse_macro_input!(input as LitStr);
+
+    if let Some(comp_env_val) = comp_env(&name.value()) {
+        return match parse_duration_millis(&comp_env_val) {
+            Some(millis) => {
+                let millis = proc_macro2::Literal::u64_suffixed(millis);
+                check_divergence(&name.value(), &comp_env_val, quote! { Some(std::time::Duration::from_millis(#millis)) }).into()
+            },
+            None => syn::Error::new(
+                name.span(),
+                format!("Cannot parse compilation env var {} as a duration: {:?}", name.value(), comp_env_val)
+            ).to_compile_error().into()
+        }
+    }
+
+    let parse = duration_parser();
+    let lookup = runtime_env(&name);
+    quote! {
+        #lookup.and_then(#parse)
+    }.into()
+}
+
+/// Gets a JSON environment variable deserialized into an `Option<T>`, either at compile or runtime
+///
+/// Requires the `json` feature, the type has to implement `serde::Deserialize` and `serde_json` has to be
+/// a dependency of the calling crate. A compile time value that isn't well-formed JSON is a compile error,
+/// and is otherwise baked in as a string deserialized on use, which panics if it doesn't fit the type.
+/// An unset variable, or a runtime value that doesn't deserialize, is `None`.
+/// # Example
+/// ```
+/// # #[cfg(feature = "json")] {
+/// use std::env;
+/// use envtime::*;
+///
+/// // Assuming the variable isn't set at compile time
+/// env::set_var("FEATURES", r#"{"search": true, "export": false}"#);
+/// let features = envtime_json!("FEATURES", std::collections::HashMap<String, bool>).unwrap_or_default();
+/// assert_eq!(features.get("search"), Some(&true));
+/// # }
+/// ```
+#[proc_macro]
+pub fn envtime_json(input: TokenStream) -> TokenStream {
+    let NameTypeArgs { name, ty } = parse_macro_input!(input as NameTypeArgs);
+    json(name, ty).unwrap_or_else(|e| e.to_compile_error()).into()
+}
+
+#[cfg(feature = "json")]
+fn json(name: LitStr, ty: Type) -> syn::Result<proc_macro2::TokenStream> {
+    if let Some(comp_env_val) = comp_env(&name.value()) {
+        if let Err(e) = serde_json::from_str::<serde_json::Value>(&comp_env_val) {
+            return Err(syn::Error::new(
+                name.span(),
+                format!("Cannot parse compilation env var {} as JSON: {}", name.value(), e)
+            ))
+        }
+        let message = format!("Cannot parse compilation env var {} as {}", name.value(), quote! { #ty });
+        return Ok(check_divergence(&name.value(), &comp_env_val, quote! {
+            Some(::serde_json::from_str::<#ty>(#comp_env_val).unwrap_or_else(|e| panic!("{}: {}", #message, e)))
+        }))
+    }
+
+    let lookup = runtime_env(&name);
+    Ok(quote! {
+        #lookup.and_then(|s| ::serde_json::from_str::<#ty>(&s).ok())
+    })
+}
+
+#[cfg(not(feature = "json"))]
+fn json(name: LitStr, _ty: Type) -> syn::Result<proc_macro2::TokenStream> {
+    Err(syn::Error::new(name.span(), "envtime_json! requires the `json` feature"))
+}
+
+/// Gets a single value out of a JSON environment variable by JSON pointer, either at compile or runtime
+///
+/// Requires the `json` feature, and `serde_json` has to be a dependency of the calling crate for the runtime path.
+/// The value at the pointer is parsed into the type of the default, which can be a string, bool, integer or float literal.
+/// JSON strings are parsed from their contents, other JSON values from their JSON text.
+/// Malformed JSON, a missing pointer or a value of the wrong type all fall back to the default.
+/// # Example
+/// ```
+/// # #[cfg(feature = "json")] {
+/// use std::env;
+/// use envtime::*;
+///
+/// // Assuming the variable isn't set at compile time
+/// env::set_var("CONFIG", r#"{"server": {"port": 9000}}"#);
+/// assert_eq!(envtime_json_path!("CONFIG", "/server/port", 8080u16), 9000u16);
+/// assert_eq!(envtime_json_path!("CONFIG", "/server/host", "localhost"), "localhost");
+/// # }
+/// ```
+#[proc_macro]
+pub fn envtime_json_path(input: TokenStream) -> TokenStream {
+    let JsonPathArgs { name, pointer, default } = parse_macro_input!(input as JsonPathArgs);
+    json_path(name, pointer, default).unwrap_or_else(|e| e.to_compile_error()).into()
+}
+
+#[cfg(feature = "json")]
+fn json_path(name: LitStr, pointer: LitStr, default: Lit) -> syn::Result<proc_macro2::TokenStream> {
+    let default_tokens = match &default {
+        Lit::Str(lit) => quote! { String::from(#lit) },
+        Lit::Bool(_) | Lit::Int(_) | Lit::Float(_) => quote! { #default },
+        _ => return Err(syn::Error::new(default.span(), "Default has to be a string, bool, integer or float literal"))
+    };
+
+    if let Some(comp_env_val) = comp_env(&name.value()) {
+        let found = serde_json::from_str::<serde_json::Value>(&comp_env_val).ok()
+            .and_then(|json| json.pointer(&pointer.value()).map(|val| match val {
+                serde_json::Value::String(s) => s.clone(),
+                other => other.to_string()
+            }));
+        let baked = found.and_then(|val| match &default {
+            Lit::Str(_) => {
+                let lit = LitStr::new(&val, name.span());
+                Some(quote! { String::from(#lit) })
+            },
+            Lit::Bool(_) => val.parse::<bool>().ok().map(|b| {
+                let lit = LitBool::new(b, name.span());
+                quote! { #lit }
+            }),
+            Lit::Int(lit_int) => {
+                let (min, max) = int_bounds(lit_int.suffix())?;
+                val.parse::<i128>().ok()
+                    .filter(|n| min <= *n && *n <= max)
+                    .map(|n| int_tokens(n, lit_int.suffix()))
+            },
+            Lit::Float(lit_float) => val.parse::<f64>().ok().filter(|f| f.is_finite()).map(|f| {
+                let lit = syn::LitFloat::new(&format!("{:?}{}", f, lit_float.suffix()), name.span());
+                quote! { #lit }
+            }),
+            _ => None
+        });
+        return Ok(check_divergence(&name.value(), &comp_env_val, baked.unwrap_or(default_tokens)))
+    }
+
+    let lookup = runtime_env(&name);
+    Ok(quote! {
+        #lookup
+            .and_then(|s| ::serde_json::from_str::<::serde_json::Value>(&s).ok())
+            .and_then(|json| match json.pointer(#pointer)? {
+                ::serde_json::Value::String(s) => s.parse().ok(),
+                other => other.to_string().parse().ok()
+            })
+            .unwrap_or_else(|| #default_tokens)
+    })
+}
+
+#[cfg(not(feature = "json"))]
+fn json_path(name: LitStr, _pointer: LitStr, _default: Lit) -> syn::Result<proc_macro2::TokenStream> {
+    Err(syn::Error::new(name.span(), "envtime_json_path! requires the `json` feature"))
+}
+
+struct JsonPathArgs {
+    name: LitStr,
+    pointer: LitStr,
+    default: Lit,
+}
+
+impl Parse for JsonPathArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let pointer = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let default = input.parse()?;
+        input.parse::<Option<Token![,]>>()?;
+        Ok(JsonPathArgs { name, pointer, default })
+    }
+}
+
+struct EnumArgs {
+    name: LitStr,
+    ty: syn::Path,
+    default: LitStr,
+}
+
+impl Parse for EnumArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let ty = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let default = input.parse()?;
+        input.parse::<Option<Token![,]>>()?;
+        Ok(EnumArgs { name, ty, default })
+    }
+}
+
+/// A variable name followed by a type, like `"PORT", u16`
+struct NameTypeArgs {
+    name: LitStr,
+    ty: Type,
+}
+
+impl Parse for NameTypeArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let ty = input.parse()?;
+        input.parse::<Option<Token![,]>>()?;
+        Ok(NameTypeArgs { name, ty })
+    }
+}
+
+/// Derives a `from_env()` constructor resolving every field from the environment, either at compile or runtime
+///
+/// Each field needs an `#[envtime(name = "...")]` attribute. With a `default = ...` literal the field
+/// is resolved like `envtime_def!`, without one the field has to be an `Option<T>` resolved like `envtime_opt!`.
+/// # Example
+/// ```
+/// use std::env;
+/// use envtime::*;
+///
+/// #[derive(Envtime)]
+/// struct Config {
+///     #[envtime(name = "SERVER_HOST", default = "localhost")]
+///     host: String,
+///     #[envtime(name = "SERVER_PORT", default = 8080u16)]
+///     port: u16,
+///     #[envtime(name = "SERVER_WORKERS")]
+///     workers: Option<u8>,
+/// }
+///
+/// // Assuming the variables aren't set at compile time
+/// env::set_var("SERVER_PORT", "9090");
+/// let config = Config::from_env();
+/// assert_eq!(config.host, "localhost");
+/// assert_eq!(config.port, 9090);
+/// assert_eq!(config.workers, None);
+/// ```
+#[proc_macro_derive(Envtime, attributes(envtime))]
+pub fn derive_envtime(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as syn::DeriveInput);
+    derive_envtime_impl(&input).unwrap_or_else(|e| e.to_compile_error()).into()
+}
+
+fn derive_envtime_impl(input: &syn::DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let fields = match &input.data {
+        syn::Data::Struct(syn::DataStruct { fields: syn::Fields::Named(fields), .. }) => &fields.named,
+        _ => return Err(syn::Error::new(input.ident.span(), "`Envtime` can only be derived for structs with named fields"))
+    };
+    let inits = fields.iter().map(|field| {
+        let ident = field.ident.as_ref().unwrap();
+        let attr = field.attrs.iter().find(|attr| attr.path().is_ident("envtime")).ok_or_else(|| {
+            syn::Error::new(ident.span(), "Missing `#[envtime(name = \"...\")]` attribute")
+        })?;
+        let FieldArgs { name, default } = attr.parse_args()?;
+        let value = match default {
+            Some(default) => {
+                let args = EnvtimeDefArgs::with_defaults(vec![DefArg::Lit(Lit::Str(name)), DefArg::Lit(default)]);
+                envtime_def_impl(&args)?
+            },
+            None => {
+                let ty = option_inner(&field.ty).ok_or_else(|| {
+                    syn::Error::new(field.ty.span(), "A field without a `default` has to be an `Option<T>`")
+                })?;
+                // Fields with a default get the prefix from `envtime_def_impl`
+                let name = LitStr::new(&format!("{}{}", name_prefix().unwrap_or_default(), name.value()), name.span());
+                envtime_opt_impl(&name, ty)
+            }
+        };
+        Ok(quote! { #ident: #value })
+    }).collect::<syn::Result<Vec<_>>>()?;
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            /// Resolves every field from the environment
+            pub fn from_env() -> Self {
+                Self {
+                    #(#inits),*
+                }
+            }
+        }
+    })
+}
+
+/// Generates a `<name>_with_env_defaults` companion for a function, resolving some of its parameters from the environment
+///
+/// Each `param = "NAME", default` pair resolves that parameter like `envtime_def!("NAME", default)`, either at
+/// compile or runtime, and the companion takes the remaining parameters in their order. The function itself is
+/// left as is. Methods taking `self` aren't supported.
+/// # Example
+/// ```
+/// use std::env;
+/// use envtime::*;
+///
+/// #[envtime_default(port = "SERVICE_PORT", 8080u16, host = "SERVICE_HOST", "localhost")]
+/// fn address(scheme: &str, host: String, port: u16) -> String {
+///     format!("{}://{}:{}", scheme, host, port)
+/// }
+///
+/// // Assuming the variables aren't set at compile time
+/// assert_eq!(address_with_env_defaults("http"), "http://localhost:8080");
+/// env::set_var("SERVICE_PORT", "9090");
+/// assert_eq!(address_with_env_defaults("https"), "https://localhost:9090");
+/// assert_eq!(address("https", String::from("example.com"), 443), "https://example.com:443");
+/// ```
+#[proc_macro_attribute]
+pub fn envtime_default(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(attr as EnvDefaultArgs);
+    let item = parse_macro_input!(item as syn::ItemFn);
+    envtime_default_impl(&args, &item).unwrap_or_else(|e| e.to_compile_error()).into()
+}
+
+fn envtime_default_impl(args: &EnvDefaultArgs, item: &syn::ItemFn) -> syn::Result<proc_macro2::TokenStream> {
+    let sig = &item.sig;
+    let (mut params, mut call_args, mut resolved) = (Vec::new(), Vec::new(), Vec::new());
+    for input in &sig.inputs {
+        let pat_type = match input {
+            syn::FnArg::Typed(pat_type) => pat_type,
+            syn::FnArg::Receiver(receiver) => return Err(syn::Error::new(
+                receiver.span(),
+                "`envtime_default` doesn't support methods taking `self`"
+            ))
+        };
+        let ident = match &*pat_type.pat {
+            syn::Pat::Ident(pat) => &pat.ident,
+            pat => return Err(syn::Error::new(pat.span(), "`envtime_default` needs plain identifiers as parameters"))
+        };
+        match args.params.iter().find(|(param, ..)| param == ident) {
+            Some((_, name, default)) => {
+                let args = EnvtimeDefArgs::with_defaults(vec![DefArg::Lit(Lit::Str(name.clone())), DefArg::Lit(default.clone())]);
+                call_args.push(envtime_def_impl(&args)?);
+                resolved.push(ident);
+            },
+            None => {
+                let ty = &pat_type.ty;
+                params.push(quote! { #ident: #ty });
+                call_args.push(quote! { #ident });
+            }
+        }
+    }
+    if let Some((param, ..)) = args.params.iter().find(|(param, ..)| !resolved.contains(&param)) {
+        return Err(syn::Error::new(param.span(), format!("`{}` has no parameter named `{}`", sig.ident, param)))
+    }
+
+    let ident = &sig.ident;
+    let companion = syn::Ident::new(&format!("{}_with_env_defaults", ident), ident.span());
+    let doc = format!(
+        "Calls [`{}`] with {} resolved from the environment",
+        ident,
+        args.params.iter().map(|(param, ..)| format!("`{}`", param)).collect::<Vec<_>>().join(", ")
+    );
+    let vis = &item.vis;
+    let (asyncness, unsafety, generics, output) = (&sig.asyncness, &sig.unsafety, &sig.generics, &sig.output);
+    let where_clause = &generics.where_clause;
+    let await_call = asyncness.map(|_| quote! { .await });
+    Ok(quote! {
+        #item
+
+        #[doc = #doc]
+        #vis #asyncness #unsafety fn #companion #generics(#(#params),*) #output #where_clause {
+            #ident(#(#call_args),*) #await_call
+        }
+    })
+}
+
+/// The `param = "NAME", default` pairs of `envtime_default`
+struct EnvDefaultArgs {
+    params: Vec<(syn::Ident, LitStr, Lit)>,
+}
+
+impl Parse for EnvDefaultArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut params: Vec<(syn::Ident, LitStr, Lit)> = Vec::new();
+        while !input.is_empty() {
+            let param = input.parse::<syn::Ident>()?;
+            input.parse::<Token![=]>()?;
+            let name = input.parse()?;
+            input.parse::<Token![,]>()?;
+            let default = input.parse()?;
+            if params.iter().any(|(other, ..)| *other == param) {
+                return Err(syn::Error::new(param.span(), format!("`{}` is given more than once", param)))
+            }
+            params.push((param, name, default));
+            if input.is_empty() {
+                break
+            }
+            input.parse::<Token![,]>()?;
+        }
+        if params.is_empty() {
+            return Err(input.error("Expected at least one `param = \"NAME\", default` pair"))
+        }
+        Ok(EnvDefaultArgs { params })
+    }
+}
+
+/// Gets a environment variable as a variant of an enum deriving `EnvtimeEnum`, either at compile or runtime
+///
+/// The value is matched against the variant names ignoring ascii case, so `warn` and `WARN` are both `Warn`.
+/// The default is a variant name as well. It's checked during the build, as is a compile time value,
+/// and a runtime value that doesn't name a variant falls back to the default.
+/// # Example
+/// ```
+/// use std::env;
+/// use envtime::*;
+///
+/// #[derive(EnvtimeEnum, Debug, PartialEq)]
+/// enum LogLevel {
+///     Error,
+///     Warn,
+///     Info,
+/// }
+///
+/// // Assuming the variable isn't set at compile time
+/// assert_eq!(envtime_enum!("LOG_LEVEL", LogLevel, "info"), LogLevel::Info);
+/// env::set_var("LOG_LEVEL", "WARN");
+/// assert_eq!(envtime_enum!("LOG_LEVEL", LogLevel, "info"), LogLevel::Warn);
+/// env::set_var("LOG_LEVEL", "verbose");
+/// assert_eq!(envtime_enum!("LOG_LEVEL", LogLevel, "info"), LogLevel::Info);
+/// ```
+#[proc_macro]
+pub fn envtime_enum(input: TokenStream) -> TokenStream {
+    let EnumArgs { name, ty, default } = parse_macro_input!(input as EnumArgs);
+    let type_name = quote! { #ty }.to_string().replace(' ', "");
+    // The variants are only known to the derived `envtime_variant`, a const panics during the build for a wrong name
+    let variant = |item: proc_macro2::TokenStream, value: &str, message: String| {
+        let message = message.replace('{', "{{").replace('}', "}}");
+        quote! {
+            const #item: #ty = match <#ty>::envtime_variant(#value) {
+                Some(variant) => variant,
+                None => panic!(#message)
+            };
+        }
+    };
+    let default_message = format!("Default {:?} is not a variant of {}", default.value(), type_name);
+
+    if let Some(comp_env_val) = comp_env(&name.value()) {
+        let val = comp_env_val.trim();
+        let default_check = variant(quote! { _ }, &default.value(), default_message);
+        let value = variant(
+            quote! { VALUE },
+            val,
+            format!("Compilation env var {} is not a variant of {}: {:?}", name.value(), type_name, val)
+        );
+        return check_divergence(&name.value(), &comp_env_val, quote! {
+            {
+                #default_check
+                #value
+                VALUE
+            }
+        }).into()
+    }
+
+    let default_variant = variant(quote! { DEFAULT }, &default.value(), default_message);
+    let lookup = runtime_env(&name);
+    quote! {
+        {
+            #default_variant
+            #lookup.and_then(|s| <#ty>::envtime_variant(s.trim())).unwrap_or(DEFAULT)
+        }
+    }.into()
+}
+
+/// Derives the variant lookup `envtime_enum!` needs, for enums with only unit variants
+#[proc_macro_derive(EnvtimeEnum)]
+pub fn derive_envtime_enum(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as syn::DeriveInput);
+    derive_envtime_enum_impl(&input).unwrap_or_else(|e| e.to_compile_error()).into()
+}
+
+fn derive_envtime_enum_impl(input: &syn::DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let variants = match &input.data {
+        syn::Data::Enum(data) => &data.variants,
+        _ => return Err(syn::Error::new(input.ident.span(), "`EnvtimeEnum` can only be derived for enums"))
+    };
+    if let Some(variant) = variants.iter().find(|variant| !matches!(variant.fields, syn::Fields::Unit)) {
+        return Err(syn::Error::new(variant.ident.span(), "`EnvtimeEnum` needs every variant to be a unit variant"))
+    }
+    let idents = variants.iter().map(|variant| &variant.ident).collect::<Vec<_>>();
+    let names = idents.iter()
+        .map(|ident| syn::LitByteStr::new(ident.to_string().as_bytes(), ident.span()))
+        .collect::<Vec<_>>();
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            /// The variant named `name`, ignoring ascii case
+            #[doc(hidden)]
+            pub const fn envtime_variant(name: &str) -> Option<Self> {
+                const fn eq_ignore_case(a: &[u8], b: &[u8]) -> bool {
+                    if a.len() != b.len() {
+                        return false
+                    }
+                    let mut i = 0;
+                    while i < a.len() {
+                        if !a[i].eq_ignore_ascii_case(&b[i]) {
+                            return false
+                        }
+                        i += 1;
+                    }
+                    true
+                }
+                let name = name.as_bytes();
+                #(
+                    if eq_ignore_case(name, #names) {
+                        return Some(Self::#idents)
+                    }
+                )*
+                None
+            }
+        }
+    })
+}
+
+/// The `T` of an `Option<T>` type
+fn option_inner(ty: &Type) -> Option<&Type> {
+    let segment = match ty {
+        Type::Path(path) if path.qself.is_none() => path.path.segments.last()?,
+        _ => return None
+    };
+    match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) if segment.ident == "Option" && args.args.len() == 1 => {
+            match args.args.first()? {
+                syn::GenericArgument::Type(ty) => Some(ty),
+                _ => None
+            }
+        },
+        _ => None
+    }
+}
+
+struct FieldArgs {
+    name: LitStr,
+    default: Option<Lit>,
+}
+
+impl Parse for FieldArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let (mut name, mut default) = (None, None);
+        while !input.is_empty() {
+            let key = input.parse::<syn::Ident>()?;
+            input.parse::<Token![=]>()?;
+            match key.to_string().as_str() {
+                "name" => name = Some(input.parse()?),
+                "default" => default = Some(input.parse()?),
+                _ => return Err(syn::Error::new(key.span(), "Unknown argument, expected `name` or `default`"))
+            }
+            if input.is_empty() {
+                break
+            }
+            input.parse::<Token![,]>()?;
+        }
+        let name = name.ok_or_else(|| input.error("Missing `name = \"...\"`"))?;
+        Ok(FieldArgs { name, default })
+    }
+}
+
+struct ParseArgs {
+    name: LitStr,
+    ty: Type,
+    default: Option<proc_macro2::TokenStream>,
+}
+
+impl Parse for ParseArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let ty = input.parse()?;
+        let mut default = None;
+        if input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            // The default is an arbitrary expression, taken verbatim up to the end of the input
+            default = Some(input.parse()?);
+        }
+        Ok(ParseArgs { name, ty, default })
+    }
+}
+
+struct VecArgs {
+    name: LitStr,
+    ty: Type,
+    delim: char,
+}
+
+impl Parse for VecArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let ty = input.parse()?;
+        let mut delim = ',';
+        if input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            let key = input.parse::<syn::Ident>()?;
+            if key != "delim" {
+                return Err(syn::Error::new(key.span(), "Unknown argument, expected `delim`"))
+            }
+            input.parse::<Token![=]>()?;
+            delim = input.parse::<syn::LitChar>()?.value();
+            input.parse::<Option<Token![,]>>()?;
+        }
+        Ok(VecArgs { name, ty, delim })
+    }
+}
+
+/// A variable name followed by an array of literals, like `"COLOR", [0u8, 0u8, 0u8]`
+struct ArrayArgs {
+    name: LitStr,
+    default: Punctuated<Lit, Token![,]>,
+}
+
+impl Parse for ArrayArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let content;
+        syn::bracketed!(content in input);
+        let default = Punctuated::parse_terminated(&content)?;
+        input.parse::<Option<Token![,]>>()?;
+        Ok(ArrayArgs { name, default })
+    }
+}
+
+/// A variable name followed by a panic message, like `"DATABASE_URL", "set DATABASE_URL"`
+struct ExpectArgs {
+    name: EnvName,
+    message: LitStr,
+}
+
+impl Parse for ExpectArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let message = input.parse()?;
+        input.parse::<Option<Token![,]>>()?;
+        Ok(ExpectArgs { name, message })
+    }
+}
+
+struct MatchesArgs {
+    name: EnvName,
+    expected: LitStr,
+    ignore_case: bool,
+}
+
+impl Parse for MatchesArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let expected = input.parse()?;
+        let mut ignore_case = false;
+        if input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            let key = input.parse::<syn::Ident>()?;
+            if key != "ignore_case" {
+                return Err(syn::Error::new(key.span(), "Unknown argument, expected `ignore_case`"))
+            }
+            input.parse::<Token![=]>()?;
+            ignore_case = input.parse::<LitBool>()?.value;
+            input.parse::<Option<Token![,]>>()?;
+        }
+        Ok(MatchesArgs { name, expected, ignore_case })
+    }
+}
+
+struct HashArgs {
+    name: LitStr,
+    default: Option<LitStr>,
+}
+
+impl Parse for HashArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse()?;
+        let mut default = None;
+        if input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            default = Some(input.parse()?);
+            input.parse::<Option<Token![,]>>()?;
+        }
+        Ok(HashArgs { name, default })
+    }
+}
+
+struct NameStrArgs {
+    name: LitStr,
+    default: LitStr,
+}
+
+impl Parse for NameStrArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let default = input.parse()?;
+        input.parse::<Option<Token![,]>>()?;
+        Ok(NameStrArgs { name, default })
+    }
+}
+
+struct PathArgs {
+    name: LitStr,
+    default: LitStr,
+    check_exists: bool,
+}
+
+impl Parse for PathArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let default = input.parse()?;
+        let mut check_exists = false;
+        if input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            let flag = input.parse::<syn::Ident>()?;
+            if flag != "check_exists" {
+                return Err(syn::Error::new(flag.span(), "Unknown flag, expected `check_exists`"))
+            }
+            check_exists = true;
+            input.parse::<Option<Token![,]>>()?;
+        }
+        Ok(PathArgs { name, default, check_exists })
+    }
+}
+
+struct ConstArgs {
+    name: LitStr,
+    default: Lit,
+}
+
+impl Parse for ConstArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let default = input.parse()?;
+        input.parse::<Option<Token![,]>>()?;
+        Ok(ConstArgs { name, default })
+    }
+}
+
+struct FileArgs {
+    name: LitStr,
+    utf8: Option<Utf8>,
+}
+
+impl Parse for FileArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse()?;
+        let mut utf8 = None;
+        if input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            let key = input.parse::<syn::Ident>()?;
+            if key != "utf8" {
+                return Err(syn::Error::new(key.span(), "Unknown argument, expected `utf8`"))
+            }
+            input.parse::<Token![=]>()?;
+            utf8 = Some(input.parse()?);
+            input.parse::<Option<Token![,]>>()?;
+        }
+        Ok(FileArgs { name, utf8 })
+    }
+}
+
+struct CfgArgs {
+    name: LitStr,
+    body: proc_macro2::TokenStream,
+}
+
+impl Parse for CfgArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let content;
+        syn::braced!(content in input);
+        let body = content.parse()?;
+        input.parse::<Option<Token![,]>>()?;
+        Ok(CfgArgs { name, body })
+    }
+}
+
+struct NonZeroArgs {
+    name: LitStr,
+    default: LitInt,
+}
+
+impl Parse for NonZeroArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let default = input.parse()?;
+        input.parse::<Option<Token![,]>>()?;
+        Ok(NonZeroArgs { name, default })
+    }
+}
+
+struct CharArgs {
+    name: LitStr,
+    default: syn::LitChar,
+}
+
+impl Parse for CharArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let default = input.parse()?;
+        input.parse::<Option<Token![,]>>()?;
+        Ok(CharArgs { name, default })
+    }
+}
+
+struct PrefixValueArgs {
+    prefix: LitStr,
+    name: LitStr,
+}
+
+impl Parse for PrefixValueArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let prefix = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let name = input.parse()?;
+        input.parse::<Option<Token![,]>>()?;
+        Ok(PrefixValueArgs { prefix, name })
+    }
+}
+
+struct PairsArgs {
+    name: LitStr,
+    strict: bool,
+}
+
+impl Parse for PairsArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse()?;
+        let mut strict = false;
+        if input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            let key = input.parse::<syn::Ident>()?;
+            if key != "malformed" {
+                return Err(syn::Error::new(key.span(), "Unknown argument, expected `malformed`"))
+            }
+            input.parse::<Token![=]>()?;
+            let policy = input.parse::<syn::Ident>()?;
+            strict = match policy.to_string().as_str() {
+                "skip" => false,
+                "error" => true,
+                _ => return Err(syn::Error::new(policy.span(), "Expected `skip` or `error`"))
+            };
+            input.parse::<Option<Token![,]>>()?;
+        }
+        Ok(PairsArgs { name, strict })
+    }
+}
+
+struct MapArgs {
+    name: LitStr,
+    delim: char,
+    pair_delim: char,
+}
+
+impl Parse for MapArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name: LitStr = input.parse()?;
+        let (mut delim, mut pair_delim) = (',', '=');
+        while input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            let key = input.parse::<syn::Ident>()?;
+            input.parse::<Token![=]>()?;
+            match key.to_string().as_str() {
+                "delim" => delim = input.parse::<syn::LitChar>()?.value(),
+                "pair_delim" => pair_delim = input.parse::<syn::LitChar>()?.value(),
+                _ => return Err(syn::Error::new(key.span(), "Unknown argument, expected `delim` or `pair_delim`"))
+            }
+        }
+        if delim == pair_delim {
+            return Err(syn::Error::new(name.span(), "`delim` and `pair_delim` have to be different chars"))
+        }
+        Ok(MapArgs { name, delim, pair_delim })
+    }
+}
+
+struct SecretArgs {
+    name: LitStr,
+    default: Option<LitStr>,
+    format: Option<LitStr>,
+    validate: Option<LitBool>,
+}
+
+impl Parse for SecretArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse()?;
+        let (mut default, mut format, mut validate) = (None, None, None);
+        if input.parse::<Option<Token![,]>>()?.is_some() && input.peek(LitStr) {
+            default = Some(input.parse()?);
+            input.parse::<Option<Token![,]>>()?;
+        }
+        while !input.is_empty() {
+            let key = input.parse::<syn::Ident>()?;
+            input.parse::<Token![=]>()?;
+            match key.to_string().as_str() {
+                "format" => format = Some(input.parse()?),
+                "validate" => validate = Some(input.parse::<LitBool>()?),
+                _ => return Err(syn::Error::new(key.span(), "Unknown argument, expected `format` or `validate`"))
+            }
+            input.parse::<Option<Token![,]>>()?;
+        }
+        if let (Some(validate), None) = (&validate, &format) {
+            return Err(syn::Error::new(validate.span(), "`validate` needs a `format` to check the runtime value against"))
+        }
+        Ok(SecretArgs { name, default, format, validate })
+    }
+}
+
+struct ClampedArgs {
+    name: LitStr,
+    default: LitInt,
+    range: IntRange,
+}
+
+/// A range with integer literal bounds, like `1..=64`, `0..10` or `1..`
+struct IntRange {
+    lo: Option<LitInt>,
+    hi: Option<LitInt>,
+    inclusive: bool,
+    span: proc_macro2::Span,
+}
+
+impl Parse for IntRange {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let span = input.span();
+        let lo = if input.peek(Token![..]) { None } else { Some(input.parse()?) };
+        let inclusive = if input.peek(Token![..=]) {
+            input.parse::<Token![..=]>()?;
+            true
+        } else {
+            input.parse::<Token![..]>()?;
+            false
+        };
+        let hi = if input.is_empty() || input.peek(Token![,]) { None } else { Some(input.parse()?) };
+        if inclusive && hi.is_none() {
+            return Err(input.error("Expected an upper bound after `..=`"))
+        }
+        Ok(IntRange { lo, hi, inclusive, span })
+    }
+}
+
+impl Parse for ClampedArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let default = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let range = input.parse()?;
+        input.parse::<Option<Token![,]>>()?;
+        Ok(ClampedArgs { name, default, range })
+    }
+}
+
+struct EnumNumArgs {
+    name: LitStr,
+    ty: Type,
+    default: LitInt,
+}
+
+impl Parse for EnumNumArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let ty = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let default = input.parse()?;
+        input.parse::<Option<Token![,]>>()?;
+        Ok(EnumNumArgs { name, ty, default })
+    }
+}
+
+/// Looks up a variable to bake in during macro expansion, which the `runtime-only` feature turns off
+fn comp_env(name: &str) -> Option<String> {
+    let val = build_env(name).filter(|_| !cfg!(feature = "runtime-only"));
+    #[cfg(feature = "trace")]
+    trace(name, val.is_some());
+    val
+}
+
+/// Looks up a variable during macro expansion, in the process environment and then in the crate's `.env` file
+///
+/// Empty values are treated as unset, as an empty value usually means "not configured".
+fn build_env(name: &str) -> Option<String> {
+    env::var(name).ok()
+        .or_else(|| dotenv::get(name))
+        .filter(|val| !val.trim().is_empty())
+}
+
+/// Expands the `${NAME}` references in the value of a variable baked in, from the variables set during the build
+///
+/// A referenced value has its own references expanded, one that isn't set or refers back to a variable
+/// being expanded fails the build. A `${` without a closing `}` is kept as it is.
+fn interpolate(name: &str, val: &str) -> Result<String, String> {
+    interpolate_refs(val, &mut vec![name.to_string()])
+}
+
+fn interpolate_refs(val: &str, expanding: &mut Vec<String>) -> Result<String, String> {
+    let mut expanded = String::new();
+    let mut rest = val;
+    while let Some(start) = rest.find("${") {
+        let end = match rest[start + 2..].find('}') {
+            Some(end) => start + 2 + end,
+            None => break
+        };
+        expanded.push_str(&rest[..start]);
+        let reference = &rest[start + 2..end];
+        if expanding.iter().any(|name| name == reference) {
+            return Err(format!("Compilation env var {} has a cyclic reference to ${{{}}}", expanding[0], reference))
+        }
+        let referenced = comp_env(reference).ok_or_else(|| {
+            format!("Compilation env var {} references ${{{}}}, which isn't set at compile time", expanding[0], reference)
+        })?;
+        expanding.push(reference.to_string());
+        expanded.push_str(&interpolate_refs(&referenced, expanding)?);
+        expanding.pop();
+        rest = &rest[end + 1..];
+    }
+    expanded.push_str(rest);
+    Ok(expanded)
+}
+
+/// Fails with the first `${NAME}` reference in a value that loops back to a variable being expanded
+///
+/// References are followed through the variables set during the build, unlike `interpolate` ones that aren't set are fine.
+fn check_cycles(val: &str, expanding: &mut Vec<String>) -> Result<(), String> {
+    let mut rest = val;
+    while let Some(start) = rest.find("${") {
+        let end = match rest[start + 2..].find('}') {
+            Some(end) => start + 2 + end,
+            None => break
+        };
+        let reference = &rest[start + 2..end];
+        if expanding.iter().any(|name| name == reference) {
+            return Err(reference.to_string())
+        }
+        if let Some(referenced) = build_env(reference) {
+            expanding.push(reference.to_string());
+            check_cycles(&referenced, expanding)?;
+            expanding.pop();
+        }
+        rest = &rest[end + 1..];
+    }
+    Ok(())
+}
+
+/// Notes on stderr how a variable was resolved, values are left out so secrets don't end up in build logs
+#[cfg(feature = "trace")]
+fn trace(name: &str, baked: bool) {
+    if baked {
+        eprintln!("note: envtime: {} resolved at compile time", name);
+    } else {
+        eprintln!("note: envtime: {} not set at compile time, left for runtime", name);
+    }
+}
+
+/// Tokens looking up a variable at runtime as an `Option<String>`, treating empty values as unset
+fn runtime_env<T: quote::ToTokens>(name: T) -> proc_macro2::TokenStream {
+    quote! {
+        std::env::var(#name).ok().filter(|s| !s.trim().is_empty())
+    }
+}
+
+/// The exact, uppercased and lowercased forms of a name, without duplicates
+fn case_variants(name: &str) -> Vec<String> {
+    let mut names = vec![name.to_string()];
+    for variant in [name.to_uppercase(), name.to_lowercase()] {
+        if !names.contains(&variant) {
+            names.push(variant);
+        }
+    }
+    names
+}
+
+/// Strips a single matching pair of `"` or `'` quotes around a value, inner quotes are kept,
+/// the same as `envtime::runtime::unquote`
+fn unquote(s: &str) -> &str {
+    for quote in ['"', '\''] {
+        if s.len() >= 2 && s.starts_with(quote) && s.ends_with(quote) {
+            return &s[1..s.len() - 1]
+        }
+    }
+    s
+}
+
+/// Tokens for a `FnOnce(String) -> String` closure calling `envtime::runtime::unquote`
+fn unquoter() -> proc_macro2::TokenStream {
+    quote! {
+        |s: String| ::envtime::runtime::unquote(&s).to_string()
+    }
+}
+
+/// A path from a compile time value, relative paths being relative to the `Cargo.toml` of the crate being compiled
+///
+/// Outside of Cargo, as in some tools expanding macros, `CARGO_MANIFEST_DIR` may not be set and a relative path
+/// is `None` with a warning, as it can't be located.
+fn manifest_relative(path: &str) -> Option<std::path::PathBuf> {
+    let path = std::path::Path::new(path);
+    if path.is_absolute() {
+        return Some(path.to_path_buf())
+    }
+    match env::var_os("CARGO_MANIFEST_DIR") {
+        Some(manifest_dir) => Some(std::path::Path::new(&manifest_dir).join(path)),
+        None => {
+            warn_once("CARGO_MANIFEST_DIR isn't set during the build, files relative to the crate root are skipped");
+            None
+        }
+    }
+}
+
+/// Prints a warning on stderr during the build, once however many macro invocations run into it
+fn warn_once(message: &str) {
+    static WARNED: std::sync::OnceLock<std::sync::Mutex<std::collections::HashSet<String>>> = std::sync::OnceLock::new();
+    let mut warned = WARNED.get_or_init(Default::default).lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+    if warned.insert(message.to_string()) {
+        eprintln!("warning: envtime: {}", message);
+    }
+}
+
+/// Parses a char from a single character, a `\u{...}` escape, a decimal code point or a `0x` prefixed hex code point,
+/// the same as `envtime::runtime::parse_char`
+fn parse_char(s: &str) -> Option<char> {
+    let mut chars = s.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Some(c)
+    }
+    let hex = s.strip_prefix("\\u{").and_then(|s| s.strip_suffix('}'));
+    let code = match hex.or_else(|| s.strip_prefix("0x")).or_else(|| s.strip_prefix("0X")) {
+        Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+        None if !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit()) => s.parse().ok()?,
+        None => return None
+    };
+    std::char::from_u32(code)
+}
+
+/// Tokens for a `FnOnce(String) -> Option<char>` closure calling `envtime::runtime::parse_char`
+fn char_parser() -> proc_macro2::TokenStream {
+    quote! {
+        |s: String| ::envtime::runtime::parse_char(&s)
+    }
+}
+
+/// Parses a byte from a decimal, `0x`, `0o` or `0b` prefixed number, or a single ASCII character as its code
+///
+/// A single digit is a number, not the character's code.
+fn parse_byte(s: &str) -> Option<u8> {
+    let mut chars = s.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        if !c.is_ascii_digit() {
+            return Some(c as u8).filter(|_| c.is_ascii())
+        }
+    }
+    let (digits, radix) = split_radix(s)?;
+    u8::from_str_radix(&digits, radix).ok()
+}
+
+/// Tokens for a `FnOnce(String) -> Option<u8>` closure mirroring `parse_byte` at runtime, trimming the value first
+fn byte_parser() -> proc_macro2::TokenStream {
+    let parse = int_parser("u8");
+    quote! {
+        |s: String| {
+            let mut chars = s.trim().chars();
+            if let (Some(c), None) = (chars.next(), chars.next()) {
+                if !c.is_ascii_digit() {
+                    return Some(c as u8).filter(|_| c.is_ascii())
+                }
+            }
+            (#parse)(s)
+        }
+    }
+}
+
+/// Decodes standard base64, with optional padding and surrounding whitespace, the same as `envtime::runtime::decode_base64`
+fn decode_base64(s: &str) -> Option<Vec<u8>> {
+    let s = s.trim();
+    let data = s.trim_end_matches('=');
+    let padding = s.len() - data.len();
+    if padding > 2 || (padding > 0 && !s.len().is_multiple_of(4)) {
+        return None
+    }
+    let mut bytes = Vec::with_capacity(data.len() * 3 / 4);
+    let (mut acc, mut bits) = (0u32, 0u32);
+    for b in data.bytes() {
+        let val = match b {
+            b'A'..=b'Z' => b - b'A',
+            b'a'..=b'z' => b - b'a' + 26,
+            b'0'..=b'9' => b - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            _ => return None
+        };
+        acc = (acc << 6) | u32::from(val);
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((acc >> bits) as u8);
+            acc &= (1 << bits) - 1;
+        }
+    }
+    // A lone trailing character can't hold a byte, and the bits left over have to be zero
+    if bits >= 6 || acc != 0 {
+        return None
+    }
+    Some(bytes)
+}
+
+/// Tokens for a `FnOnce(String) -> Option<Vec<u8>>` closure calling `envtime::runtime::decode_base64`
+fn base64_decoder() -> proc_macro2::TokenStream {
+    quote! {
+        |s: String| ::envtime::runtime::decode_base64(&s)
+    }
+}
+
+/// Decodes pairs of hex digits, with an optional `0x` prefix and surrounding whitespace, the same as `envtime::runtime::decode_hex`
+fn decode_hex(s: &str) -> Option<Vec<u8>> {
+    let s = s.trim();
+    let s = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")).unwrap_or(s);
+    if !s.len().is_multiple_of(2) || !s.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None
+    }
+    (0..s.len()).step_by(2).map(|i| u8::from_str_radix(&s[i..i + 2], 16).ok()).collect()
+}
+
+/// Tokens for a `FnOnce(String) -> Option<Vec<u8>>` closure calling `envtime::runtime::decode_hex`
+fn hex_decoder() -> proc_macro2::TokenStream {
+    quote! {
+        |s: String| ::envtime::runtime::decode_hex(&s)
+    }
+}
+
+/// Parses a duration like `500ms`, `30s`, `5m`, `2h` or a bare number of seconds into milliseconds,
+/// the same as `envtime::runtime::parse_duration`
+fn parse_duration_millis(s: &str) -> Option<u64> {
+    let s = s.trim();
+    let (num, factor) = if let Some(num) = s.strip_suffix("ms") {
+        (num, 1)
+    } else if let Some(num) = s.strip_suffix('s') {
+        (num, 1_000)
+    } else if let Some(num) = s.strip_suffix('m') {
+        (num, 60_000)
+    } else if let Some(num) = s.strip_suffix('h') {
+        (num, 3_600_000)
+    } else {
+        (s, 1_000)
+    };
+    if num.is_empty() || !num.bytes().all(|b| b.is_ascii_digit()) {
+        return None
+    }
+    num.parse::<u64>().ok()?.checked_mul(factor)
+}
+
+/// Tokens for a `FnOnce(String) -> Option<Duration>` closure calling `envtime::runtime::parse_duration`
+fn duration_parser() -> proc_macro2::TokenStream {
+    quote! {
+        |s: String| ::envtime::runtime::parse_duration(&s)
+    }
+}
+
+/// Lowercase words recognized as true or false, matched case-insensitively, the same as `envtime::runtime`
+const TRUTHY: &[&str] = &["true", "yes", "y", "on"];
+const FALSY: &[&str] = &["false", "no", "n", "off"];
+
+/// `1` is always true and `0` always false, apart from the words, any other number isn't a bool
+fn recognize_bool(s: &str) -> Option<bool> {
+    match s {
+        "1" => return Some(true),
+        "0" => return Some(false),
+        _ => {}
+    }
+    let s = s.to_lowercase();
+    if TRUTHY.contains(&s.as_str()) {
+        Some(true)
+    } else if FALSY.contains(&s.as_str()) {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+/// Tokens for a `FnOnce(String) -> Option<bool>` closure mirroring `recognize_bool` at runtime
+fn bool_parser() -> proc_macro2::TokenStream {
+    quote! {
+        |s: String| if ::envtime::runtime::is_truthy(&s) {
+            Some(true)
+        } else if ::envtime::runtime::is_falsy(&s) {
+            Some(false)
+        } else {
+            None
+        }
+    }
+}
+
+/// Splits a delimited value into trimmed, non-empty entries
+fn split_entries(s: &str, delim: char) -> impl Iterator<Item = &str> {
+    s.split(delim).map(str::trim).filter(|entry| !entry.is_empty())
+}
+
+/// Splits a `key=value` entry on the first `=`, rejecting entries without a key
+fn split_pair(entry: &str) -> Option<(&str, &str)> {
+    match entry.split_once('=') {
+        Some((key, val)) if !key.trim().is_empty() => Some((key.trim(), val.trim())),
+        _ => None
+    }
+}
+
+/// The name of a primitive type that's parsed like `envtime_def!` literals, or `None` for other types
+fn primitive_name(ty: &Type) -> Option<String> {
+    let ident = match ty {
+        Type::Path(path) if path.qself.is_none() => path.path.get_ident()?.to_string(),
+        _ => return None
+    };
+    match ident.as_str() {
+        "String" | "bool" | "char" | "f32" | "f64" => Some(ident),
+        _ => int_bounds(&ident).map(|_| ident)
+    }
+}
+
+/// Checks whether a value parses as a primitive type, or `None` if the type isn't a known primitive
+///
+/// It agrees with `primitive_literal`, which yields a literal for exactly the values accepted here.
+fn validate_primitive(ty: &Type, s: &str) -> Option<bool> {
+    primitive_name(ty)?;
+    Some(primitive_literal(ty, s).is_some())
+}
+
+/// Literal tokens for a value of a primitive type, or `None` if the type isn't a known primitive or it doesn't parse
+///
+/// Values other than strings are trimmed and parsed like `envtime_def!` literals: bools take the recognized words,
+/// chars take escapes and code points, integers take radix prefixes and numbers take `_` separators.
+fn primitive_literal(ty: &Type, s: &str) -> Option<proc_macro2::TokenStream> {
+    let ident = primitive_name(ty)?;
+    let span = proc_macro2::Span::call_site();
+    let trimmed = s.trim();
+    Some(match ident.as_str() {
+        "String" => quote! { String::from(#s) },
+        "bool" => {
+            let lit = LitBool::new(recognize_bool(trimmed)?, span);
+            quote! { #lit }
+        },
+        "char" => {
+            let lit = syn::LitChar::new(parse_char(trimmed)?, span);
+            quote! { #lit }
+        },
+        "f32" | "f64" => float_literal(&strip_separators(trimmed), &ident)?,
+        _ => int_literal(trimmed, &ident)?
+    })
+}
+
+/// Tokens for a `FnOnce(String) -> Option<T>` closure parsing a runtime value like `primitive_literal`,
+/// or `None` if the type isn't a known primitive
+fn primitive_parser(ty: &Type) -> Option<proc_macro2::TokenStream> {
+    let ident = primitive_name(ty)?;
+    Some(match ident.as_str() {
+        "String" => quote! { |s: String| Some(s) },
+        "bool" => bool_parser(),
+        "char" => quote! { |s: String| ::envtime::runtime::parse_char(s.trim()) },
+        "f32" | "f64" => {
+            let strip = separator_stripper();
+            quote! { |s: String| (#strip)(s.trim()).parse::<#ty>().ok() }
+        },
+        _ => int_parser(&ident)
+    })
+}
+
+/// Like `primitive_parser`, with other types trimmed and parsed with `FromStr`
+fn typed_parser(ty: &Type) -> proc_macro2::TokenStream {
+    primitive_parser(ty).unwrap_or_else(|| quote! { |s: String| s.trim().parse::<#ty>().ok() })
+}
+
+/// Tokens for a float of the type of a suffix, or an unsuffixed one for an empty suffix, or `None` if it doesn't parse
+///
+/// Like `f64::from_str`, this takes scientific notation, `inf` and `nan`. Infinities and NaN have no literal,
+/// so they become the type's constants, or a division by zero for an unsuffixed one to keep it inferred.
+fn float_literal(s: &str, suffix: &str) -> Option<proc_macro2::TokenStream> {
+    let val = match suffix {
+        "f32" => f64::from(s.parse::<f32>().ok()?),
+        _ => s.parse::<f64>().ok()?
+    };
+    if val.is_finite() {
+        let lit = match suffix {
+            "f32" => proc_macro2::Literal::f32_suffixed(val as f32),
+            "f64" => proc_macro2::Literal::f64_suffixed(val),
+            _ => proc_macro2::Literal::f64_unsuffixed(val)
+        };
+        return Some(quote! { #lit })
+    }
+    let (constant, unsuffixed) = if val.is_nan() {
+        ("NAN", quote! {
+            {
+                #[allow(clippy::zero_divided_by_zero)]
+                let nan = 0.0 / 0.0;
+                nan
+            }
+        })
+    } else if val > 0.0 {
+        ("INFINITY", quote! { (1.0 / 0.0) })
+    } else {
+        ("NEG_INFINITY", quote! { (-1.0 / 0.0) })
+    };
+    if suffix.is_empty() {
+        return Some(unsuffixed)
+    }
+    let ty = syn::Ident::new(suffix, proc_macro2::Span::call_site());
+    let constant = syn::Ident::new(constant, proc_macro2::Span::call_site());
+    Some(quote! { #ty::#constant })
+}
+
+/// The value range of an integer type suffix, an empty suffix is treated as `i32`
+fn int_bounds(suffix: &str) -> Option<(i128, i128)> {
+    Some(match suffix {
+        "u8" => (u8::MIN as i128, u8::MAX as i128),
+        "i8" => (i8::MIN as i128, i8::MAX as i128),
+        "u16" => (u16::MIN as i128, u16::MAX as i128),
+        "i16" => (i16::MIN as i128, i16::MAX as i128),
+        "u32" => (u32::MIN as i128, u32::MAX as i128),
+        "" | "i32" => (i32::MIN as i128, i32::MAX as i128),
+        "u64" => (u64::MIN as i128, u64::MAX as i128),
+        "i64" => (i64::MIN as i128, i64::MAX as i128),
+        "u128" => (0, i128::MAX),
+        "i128" => (i128::MIN, i128::MAX),
+        "usize" => (usize::MIN as i128, usize::MAX as i128),
+        "isize" => (isize::MIN as i128, isize::MAX as i128),
+        _ => return None
+    })
+}
+
+/// The type an integer suffix stands for, an empty suffix being `i32`
+fn int_type_name(suffix: &str) -> &str {
+    if suffix.is_empty() { "i32" } else { suffix }
+}
+
+fn in_range(val: i128, min: Option<i128>, max: Option<i128>) -> bool {
+    min.is_none_or(|min| min <= val) && max.is_none_or(|max| val <= max)
+}
+
+/// A range like `1024..=49151`, with either end left open
+fn range_text(min: Option<i128>, max: Option<i128>) -> String {
+    let min = min.map_or_else(String::new, |min| min.to_string());
+    let max = max.map_or_else(String::new, |max| format!("={}", max));
+    format!("{}..{}", min, max)
+}
+
+const SUPPORTED_INT_TYPES: &str = "u8, i8, u16, i16, u32, i32, u64, i64, u128, i128, usize, isize";
+
+fn unsupported_int_type(lit: &LitInt, suffix: &str) -> syn::Error {
+    syn::Error::new(
+        lit.span(),
+        format!("Unsupported integer type {:?}, expected one of: {}", suffix, SUPPORTED_INT_TYPES)
+    )
+}
+
+/// A suffixed integer literal for a value, or `None` if it doesn't parse or doesn't fit the type
+fn int_literal(s: &str, suffix: &str) -> Option<proc_macro2::TokenStream> {
+    let (digits, radix) = split_radix(s)?;
+    if suffix == "u128" {
+        let lit = proc_macro2::Literal::u128_suffixed(u128::from_str_radix(&digits, radix).ok()?);
+        return Some(quote! { #lit })
+    }
+    let (min, max) = int_bounds(suffix)?;
+    let val = i128::from_str_radix(&digits, radix).ok().filter(|val| min <= *val && *val <= max)?;
+    Some(int_tokens(val, suffix))
+}
+
+/// Whether a value `int_literal` rejected is still a well-formed integer, as the way it doesn't fit the type
+fn out_of_range(s: &str) -> Option<&'static str> {
+    let (digits, radix) = split_radix(s)?;
+    let (negative, magnitude) = match digits.strip_prefix('-') {
+        Some(magnitude) => (true, magnitude),
+        None => (false, digits.as_str())
+    };
+    if magnitude.is_empty() || !magnitude.chars().all(|c| c.is_digit(radix)) {
+        return None
+    }
+    Some(if negative { "underflows" } else { "overflows" })
+}
+
+/// Splits a `0x`, `0o` or `0b` radix prefix off an integer, keeping the sign and dropping `_` separators, for use with `from_str_radix`,
+/// the same as `envtime::runtime::split_radix`
+fn split_radix(s: &str) -> Option<(String, u32)> {
+    let s = &*strip_separators(s);
+    let (sign, digits) = match s.strip_prefix('-') {
+        Some(digits) => ("-", digits),
+        None => ("", s.strip_prefix('+').unwrap_or(s))
+    };
+    let (radix, digits) = match digits.get(..2) {
+        Some("0x") | Some("0X") => (16, &digits[2..]),
+        Some("0o") | Some("0O") => (8, &digits[2..]),
+        Some("0b") | Some("0B") => (2, &digits[2..]),
+        _ => (10, digits)
+    };
+    if digits.starts_with(['+', '-']) {
+        return None
+    }
+    Some((format!("{}{}", sign, digits), radix))
+}
+
+/// A number without its `_` digit separators, like in Rust literals, the same as `envtime::runtime::strip_separators`
+///
+/// A value starting or ending with one is left as is, so it stays malformed.
+fn strip_separators(s: &str) -> std::borrow::Cow<'_, str> {
+    if s.contains('_') && !s.starts_with('_') && !s.ends_with('_') {
+        std::borrow::Cow::Owned(s.replace('_', ""))
+    } else {
+        std::borrow::Cow::Borrowed(s)
+    }
+}
+
+/// Tokens for `envtime::runtime::strip_separators`, a `Fn(&str) -> Cow<str>` at runtime
+fn separator_stripper() -> proc_macro2::TokenStream {
+    quote! { ::envtime::runtime::strip_separators }
+}
+
+/// Tokens for a `FnOnce(String) -> Option<T>` closure calling `envtime::runtime::parse_int`,
+/// unsuffixed integers are left to inference
+fn int_parser(suffix: &str) -> proc_macro2::TokenStream {
+    if suffix.is_empty() {
+        return quote! { |s: String| ::envtime::runtime::parse_int(&s) }
+    }
+    let ty = syn::Ident::new(suffix, proc_macro2::Span::call_site());
+    quote! {
+        |s: String| ::envtime::runtime::parse_int::<#ty>(&s)
+    }
+}
+
+/// Integer literal tokens with the given suffix, negative values are emitted as a negation
+fn int_tokens(val: i128, suffix: &str) -> proc_macro2::TokenStream {
+    let lit = LitInt::new(&format!("{}{}", val.unsigned_abs(), suffix), proc_macro2::Span::call_site());
+    if val < 0 {
+        quote! { -#lit }
+    } else {
+        quote! { #lit }
+    }
+}
+
+fn format_rfc3339(secs: i64) -> String {
+    let days = secs.div_euclid(86_400);
+    let rem = secs.rem_euclid(86_400);
+
+    // Civil date from days since 1970-01-01, see http://howardhinnant.github.io/date_algorithms.html
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!("{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year, month, day, rem / 3600, rem % 3600 / 60, rem % 60)
+}
@@ -0,0 +1,36 @@
+//! Records the variables the macros look up in the file `ENVTIME_MANIFEST_OUT` points at, with the `manifest` feature
+
+use std::collections::HashSet;
+use std::env;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::sync::{Mutex, OnceLock, PoisonError};
+
+/// Names this compiler process already recorded, so each is appended once however often it's used
+static RECORDED: OnceLock<Mutex<HashSet<String>>> = OnceLock::new();
+
+/// Appends a variable name on its own line to the manifest, if `ENVTIME_MANIFEST_OUT` is set during the build
+///
+/// Appends from macros expanding on different threads are serialized by the lock, and appending keeps the names
+/// other crates recorded. A manifest that can't be written is skipped with a warning instead of failing the build.
+pub(crate) fn record(name: &str) {
+    let path = match env::var_os("ENVTIME_MANIFEST_OUT") {
+        Some(path) if !path.is_empty() => path,
+        _ => return
+    };
+    let mut recorded = RECORDED.get_or_init(Default::default).lock().unwrap_or_else(PoisonError::into_inner);
+    if recorded.contains(name) {
+        return
+    }
+    let written = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .and_then(|mut file| writeln!(file, "{}", name));
+    match written {
+        Ok(()) => {
+            recorded.insert(name.to_string());
+        },
+        Err(err) => crate::warn_once(&format!("Cannot write the manifest {:?} from ENVTIME_MANIFEST_OUT: {}", path, err))
+    }
+}
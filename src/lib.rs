@@ -45,182 +45,263 @@
 //! env::set_var("TEST_U8_RUN_ENV", "53");
 //! assert_eq!(envtime_def!("TEST_U8_RUN_ENV", 77u8), 53u8);
 //! ```
+//!
+//! ## Empty values
+//!
+//! A variable set to an empty value, or only whitespace, is treated the same as an unset variable,
+//! both at compile time and at runtime, as that usually means it isn't configured.
+//!
+//! ## Runtime only
+//!
+//! With the `runtime-only` feature enabled, nothing is baked in and every macro that can fall back to the
+//! runtime environment always does, for binaries built once and deployed to many environments.
+//! Macros that only work at compile time, like `envtime_req!`, `envtime_const!` and `envtime_build_time!`, are unaffected.
+//!
+//! ## Divergence warnings
+//!
+//! With the `warn-divergence` feature enabled, a value baked in from a single variable, by `envtime!`, `envtime_def!`
+//! or any of the typed macros like `envtime_str!`, `envtime_opt!` or `envtime_vec!`, still reads the runtime variable once,
+//! the first time it's used, and prints a warning to stderr if it's set to something else, as the runtime value is
+//! silently ignored otherwise. The values aren't printed. This is meant for debugging, and keeps the macros from
+//! being used in a `const`. `envtime_const!` and `envtime_cfg!` never read the runtime environment and aren't checked,
+//! and neither are the prefix groups of `envtime_group!`.
+//!
+//! ## Name prefix
+//!
+//! If `ENVTIME_PREFIX` is set during the build, it's prepended to every name `envtime!`, `envtime_cow!`,
+//! `envtime_def!`, `envtime_cached!`, `envtime_req_release!`, `envtime_expect!`, `envtime_matches!`,
+//! `#[envtime_default]` and the `Envtime` derive look up, `env` fallbacks included, both at compile time and
+//! at runtime: with `ENVTIME_PREFIX=MYAPP_`, `envtime!("PORT")` looks up `MYAPP_PORT`. The other macros,
+//! like `envtime_str!` or `envtime_bool!`, and the runtime helpers look up names as given.
+//! The prefix is read from the environment or the `.env` file, and as with other baked values
+//! changing it only applies once the calling crate is rebuilt.
+//!
+//! ## Tracing
+//!
+//! With the `trace` feature enabled, every lookup during the build prints a note telling whether the
+//! variable was resolved at compile time or left for runtime, the value itself is never printed.
+//! As with any macro output, notes only appear when the calling crate is actually recompiled.
+//!
+//! ## Manifest
+//!
+//! With the `manifest` feature enabled and `ENVTIME_MANIFEST_OUT` set to a file path during the build, every name
+//! `envtime!` and `envtime_def!` look up is appended to that file on its own line, `env` fallbacks and prefixes
+//! included, listing the variables a binary consults. Names given as a const aren't known during the build and
+//! are left out. The file is only appended to, by every crate being compiled, and only when a crate is recompiled,
+//! so clear it ahead of a clean build for a complete list and deduplicate the lines when reading it.
+//!
+//! ## .env files
+//!
+//! Variables missing from the environment during compilation are also looked up in a `.env` file
+//! next to the `Cargo.toml` of the crate being compiled, and baked in just the same.
+//! The file holds `KEY=VALUE` lines, blank lines and lines starting with `#` are ignored.
+//! Like in shell scripts, a leading `export` is skipped, a `#` after whitespace starts a comment,
+//! and values in `"` or `'` quotes are taken verbatim without the quotes.
+//! The environment always takes precedence over the `.env` file, which is never read at runtime.
+//!
+//! Files are layered, each overriding the ones before it: `.env`, then `.env.local`, and with
+//! `ENVTIME_PROFILE` set during the build, `.env.{profile}` and then `.env.{profile}.local`,
+//! so `ENVTIME_PROFILE=staging` reads `.env.staging` over the base files. The environment still comes first.
+//!
+//! Outside of Cargo, in tools expanding macros without `CARGO_MANIFEST_DIR` set, there's no crate root to find the
+//! files in, so they're skipped with a warning during the build, as are files relative to the crate root
+//! that `envtime!` and `envtime_file!` read. Those values are left for runtime instead of failing the build.
+//!
+//! ## Rebuilds
+//!
+//! Cargo doesn't know which variables were baked in, so changing one doesn't rebuild the crate using it.
+//! The companion `envtime_build` crate has helpers for a build script to track them:
+//! `envtime_build::track("PORT")` rebuilds whenever `PORT` changes, and `envtime_build::track_dotenv()`
+//! whenever the `.env` file does.
+//!
+//! ## Runtime helpers
+//!
+//! The macros need the variable name as a literal, for names only known when the program runs
+//! there are plain functions resolving them the same way the macros do at runtime, never at compile time.
+//!
+//! ```
+//! use std::env;
+//!
+//! let service = "billing";
+//! env::set_var("BILLING_PORT", "8080");
+//! assert_eq!(envtime::get_string(&format!("{}_PORT", service.to_uppercase())), Some(String::from("8080")));
+//! assert_eq!(envtime::get_parsed(&format!("{}_PORT", service.to_uppercase()), 80u16), 8080);
+//! assert_eq!(envtime::get_parsed("BILLING_DEBUG", false), false);
+//! ```
 
-extern crate proc_macro;
-use proc_macro::{Literal, TokenStream, TokenTree};
+use std::any::Any;
 use std::env;
-use syn::{parse_macro_input, Lit, LitStr, Token, LitBool, LitByte, LitInt};
-use quote::{quote};
-use syn::punctuated::Punctuated;
-use syn::spanned::Spanned;
+use std::fmt;
+use std::str::FromStr;
 
-/// Gets a environment variable as a String either at compile or runtime
-/// # Example
-/// ```
-/// use std::env;
-/// use envtime::*;
+pub use envtime_macros::*;
+
+pub mod runtime;
+
+/// Why `envtime_try!` has no value for a variable
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// The variable isn't set, or is set to an empty value
+    NotSet,
+    /// The variable is set to a value that doesn't parse, kept as it was set
+    Invalid(String),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::NotSet => write!(f, "not set"),
+            ParseError::Invalid(value) => write!(f, "invalid value {:?}", value)
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// A value `envtime_secret!` read, printed as `[REDACTED]` by `Debug` and `Display` so it stays out of logs
 ///
-/// // Assuming the variable isn't set
-/// let var = envtime!("DOMAIN");
-/// assert_eq!(var, None);
-/// let var = var.unwrap_or_else(|| String::from("example.com"));
-/// assert_eq!(var, String::from("example.com"));
+/// The value itself is only reachable through `expose()` or `into_inner()`.
+/// ```
+/// let token = envtime::Secret::new(String::from("hunter2"));
+/// assert_eq!(format!("{:?} {}", token, token), "[REDACTED] [REDACTED]");
+/// assert_eq!(token.expose(), "hunter2");
 /// ```
-#[proc_macro]
-pub fn envtime(input: TokenStream) -> TokenStream {
-    let lit_str = parse_macro_input!(input as LitStr);
-    let comp_env = env::var(lit_str.value());
-    if let Ok(comp_env_val) = comp_env {
-        let literal = LitStr::new(comp_env_val.as_str(), lit_str.span());
-        return quote! {
-            Some(String::from(#literal))
-        }.into()
+#[cfg(feature = "secrets")]
+#[derive(Clone, Default)]
+pub struct Secret<T>(T);
+
+#[cfg(feature = "secrets")]
+impl<T> Secret<T> {
+    /// Wraps a value
+    pub fn new(value: T) -> Self {
+        Secret(value)
+    }
+
+    /// The wrapped value, to be used where it's needed and not logged
+    pub fn expose(&self) -> &T {
+        &self.0
+    }
+
+    /// Unwraps the value, for passing it on to something that takes ownership
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+#[cfg(feature = "secrets")]
+impl<T> From<T> for Secret<T> {
+    fn from(value: T) -> Self {
+        Secret(value)
+    }
+}
+
+#[cfg(feature = "secrets")]
+impl<T> fmt::Debug for Secret<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[REDACTED]")
+    }
+}
+
+#[cfg(feature = "secrets")]
+impl<T> fmt::Display for Secret<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[REDACTED]")
     }
-    return  quote! {
-        std::env::var(#lit_str).ok()
-    }.into()
 }
 
-/// Gets a environment variable as the type specified by the default value, either at compile or runtime
-/// # Example
+/// Gets a variable from the runtime environment, like `envtime!` without a compile time value
+///
+/// Empty or whitespace only values are treated as unset, and an unset variable falls back to the
+/// trimmed contents of the file its `_FILE` sibling points at, so `get_string("DB_PASSWORD")`
+/// also reads the file named by `DB_PASSWORD_FILE`.
+/// ```
+/// std::env::set_var("GET_STRING_DOC", "value");
+/// assert_eq!(envtime::get_string("GET_STRING_DOC"), Some(String::from("value")));
+/// assert_eq!(envtime::get_string("GET_STRING_DOC_UNSET"), None);
 /// ```
-/// use std::env;
-/// use envtime::*;
+pub fn get_string(name: &str) -> Option<String> {
+    lookup(name).or_else(|| {
+        lookup(&format!("{}_FILE", name))
+            .and_then(|path| std::fs::read_to_string(path.trim()).ok())
+            .map(|contents| contents.trim().to_string())
+            .filter(|contents| !contents.is_empty())
+    })
+}
+
+/// Gets a variable from the runtime environment parsed as `T`, like `envtime_def!` without a compile time value
 ///
-/// // Assuming we set the value of "PORT" to 5678 at compile or runtime
-/// let var = envtime_def!("PORT", 1234u16);
-/// assert_eq!(var, 5678u16);
+/// The value is trimmed, and the default is used if it's unset, empty or doesn't parse.
+/// Bools accept the same `1`/`0`, `true`/`false`, `yes`/`no`, `y`/`n` and `on`/`off` set as the macros,
+/// integers accept `0x`, `0o` and `0b` prefixes, numbers accept `_` separators, and chars accept `\u{...}` escapes and code points.
+/// With the `strict` feature a bool or integer value that doesn't parse panics instead.
 /// ```
-#[proc_macro]
-pub fn envtime_def(input: TokenStream) -> TokenStream {
-    let input : Punctuated<Lit,Token![,]> = parse_macro_input!(input with Punctuated<Lit,Token![,]>::parse_terminated);
-    if input.len() != 2 {
-        panic!("A env variable name and a default value is required. 2 arguments expected!");
+/// std::env::set_var("GET_PARSED_DOC", "0x1F");
+/// assert_eq!(envtime::get_parsed("GET_PARSED_DOC", 0u8), 31);
+/// std::env::set_var("GET_PARSED_DOC", "Yes");
+/// assert_eq!(envtime::get_parsed("GET_PARSED_DOC", false), true);
+/// ```
+pub fn get_parsed<T: FromStr + 'static>(name: &str, default: T) -> T {
+    let s = match lookup(name) {
+        Some(s) => s,
+        None => return default
+    };
+    match parse(s.trim()) {
+        (Some(val), _) => val,
+        (None, true) if cfg!(feature = "strict") => panic!("invalid value for {}", name),
+        (None, _) => default
     }
-    let env_var = match input.first().unwrap() {
-        Lit::Str(lit) => lit,
-        _ => panic!("First parameter has to be a string literal")
+}
+
+fn lookup(name: &str) -> Option<String> {
+    env::var(name).ok().filter(|s| !s.trim().is_empty())
+}
+
+/// Parses a trimmed value, with the macros' own rules for bools, chars, integers and floats and `FromStr` otherwise,
+/// along with whether the `strict` feature checks values of the type, which like the macros is only bools and integers
+fn parse<T: FromStr + 'static>(s: &str) -> (Option<T>, bool) {
+    let mut parsed: Option<T> = None;
+    let slot = &mut parsed as &mut dyn Any;
+    let checked = if let Some(slot) = slot.downcast_mut::<Option<bool>>() {
+        *slot = parse_bool(s);
+        true
+    } else if let Some(slot) = slot.downcast_mut::<Option<char>>() {
+        *slot = runtime::parse_char(s);
+        false
+    } else if parse_any_int(slot, s) {
+        true
+    } else if let Some(slot) = slot.downcast_mut::<Option<f64>>() {
+        *slot = runtime::strip_separators(s).parse().ok();
+        false
+    } else if let Some(slot) = slot.downcast_mut::<Option<f32>>() {
+        *slot = runtime::strip_separators(s).parse().ok();
+        false
+    } else {
+        parsed = s.parse().ok();
+        false
     };
+    (parsed, checked)
+}
 
-    let def_val = input.last().unwrap();
-
-    let comp_env = env::var(env_var.value());
-    if let Ok(comp_env_val) = comp_env {
-        return match def_val {
-            Lit::Str(_) => {
-                let lit = LitStr::new(comp_env_val.as_str(), input.span());
-                (quote! { String::from(#lit) }).into()
-            },
-            Lit::Bool(_) => {
-                let lit = LitBool::new(match comp_env_val.as_str() {
-                    "y" | "Y" | "Yes" | "yes" | "true" => true,
-                    _ => false
-                }, input.span());
-                (quote! { #lit }).into()
-            },
-            Lit::Byte(_) => {
-                let lit = LitByte::new(
-                    comp_env_val
-                        .parse()
-                        .expect("Cannot parse compilation env var (byte)"),
-                    input.span());
-                (quote! { #lit }).into()
-            },
-            Lit::Char(_) => {
-                TokenStream::from(TokenTree::Literal(Literal::character(
-                    comp_env_val.parse()
-                        .expect("Cannot parse compilation env var (char)"))))
-            },
-            Lit::Int(lit_int) => {
-                let s = lit_int.to_string();
-                let type_index = find_int_type_index(&s);
-
-                if let None = type_index {
-                    let lit = LitInt::new(&comp_env_val, input.span());
-                    return quote! {
-                        #lit
-                    }.into()
-                }
-
-                let type_index = type_index.unwrap();
-                let type_str = &s[type_index .. s.len()];
-
-                TokenStream::from(TokenTree::Literal(match type_str {
-                    "u8" => Literal::u8_suffixed(
-                        comp_env_val.parse::<u8>().expect("Invalid u8")
-                    ),
-                    "i8" => Literal::i8_suffixed(
-                        comp_env_val.parse::<i8>().expect("Invalid i8")
-                    ),
-                    "u16" => Literal::u16_suffixed(
-                        comp_env_val.parse::<u16>().expect("Invalid u16")
-                    ),
-                    "i16" => Literal::i16_suffixed(
-                        comp_env_val.parse::<i16>().expect("Invalid i16")
-                    ),
-                    "u32" => Literal::u32_suffixed(
-                        comp_env_val.parse::<u32>().expect("Invalid u32")
-                    ),
-                    "i32" => Literal::i32_suffixed(
-                        comp_env_val.parse::<i32>().expect("Invalid i32")
-                    ),
-                    "u64" => Literal::u64_suffixed(
-                        comp_env_val.parse::<u64>().expect("Invalid u64")
-                    ),
-                    "i64" => Literal::i64_suffixed(
-                        comp_env_val.parse::<i64>().expect("Invalid i64")
-                    ),
-                    "u128" => Literal::u128_suffixed(
-                        comp_env_val.parse::<u128>().expect("Invalid u128")
-                    ),
-                    "i128" => Literal::i128_suffixed(
-                        comp_env_val.parse::<i128>().expect("Invalid i128")
-                    ),
-                    "usize" => Literal::usize_suffixed(
-                        comp_env_val.parse::<usize>().expect("Invalid usize")
-                    ),
-                    "isize" => Literal::isize_suffixed(
-                        comp_env_val.parse::<isize>().expect("Invalid isize")
-                    ),
-                    _ => panic!("Unknown type: {:?}", type_str)
-                }))
-            }
-            _ => panic!("Unknown type of default value")
-        };
+fn parse_bool(s: &str) -> Option<bool> {
+    if runtime::is_truthy(s) {
+        Some(true)
+    } else if runtime::is_falsy(s) {
+        Some(false)
+    } else {
+        None
     }
+}
 
-    return match def_val {
-        Lit::Str(_) => {
-            (quote! {
-                std::env::var(#env_var).unwrap_or(String::from(#def_val))
-            }).into()
-        },
-        Lit::Bool(_) => {
-            (quote! {
-                std::env::var(#env_var).map_or(#def_val, |s| match s.as_str() {
-                    "y" | "Y" | "Yes" | "yes" | "true" => true,
-                    _ => false
-                })
-            }).into()
-        },
-        Lit::Byte(_) => {
-            (quote! {
-                std::env::var(#env_var).ok().and_then(|s| s.parse::<u8>().ok()).unwrap_or(#def_val)
-            }).into()
-        },
-        Lit::Int(_) => {
-            (quote! {
-                std::env::var(#env_var).ok().and_then(|s| s.parse().ok()).unwrap_or(#def_val)
-            }).into()
-        }
-        _ => panic!("Unknown default value type")
+/// Fills `slot` if it's an `Option` of an integer type, returning whether it was one
+fn parse_any_int(slot: &mut dyn Any, s: &str) -> bool {
+    macro_rules! parse_ints {
+        ($($ty:ty),*) => {
+            $(if let Some(slot) = slot.downcast_mut::<Option<$ty>>() {
+                *slot = runtime::parse_int::<$ty>(s);
+                return true
+            })*
+        };
     }
+    parse_ints!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+    false
 }
-
-fn find_int_type_index(s: &str) -> Option<usize> {
-    s.find(|c|
-        match c {
-            'u' | 'i' => true,
-            _ => false
-        })
-}
\ No newline at end of file
@@ -0,0 +1,311 @@
+//! Helpers the macros call from their generated runtime code, public so hand written parsing can match them
+
+use std::borrow::Cow;
+use std::time::Duration;
+
+/// Lowercase words recognized as true or false, matched case-insensitively
+const TRUTHY: &[&str] = &["true", "yes", "y", "on"];
+const FALSY: &[&str] = &["false", "no", "n", "off"];
+
+/// Whether a value reads as true: `1`, `true`, `yes`, `y` or `on`, in any case and with surrounding whitespace
+///
+/// `1` being true and `0` being false is a stable guarantee, independent of the words recognized.
+/// No other number is a bool, `2` or `01` are neither truthy nor falsy.
+/// ```
+/// use envtime::runtime::is_truthy;
+///
+/// assert!(is_truthy(" Yes\n"));
+/// assert!(is_truthy("1"));
+/// assert!(!is_truthy("no"));
+/// assert!(!is_truthy("ture"));
+/// assert!(!is_truthy("2"));
+/// ```
+pub fn is_truthy(s: &str) -> bool {
+    match s.trim() {
+        "1" => true,
+        s => TRUTHY.contains(&s.to_lowercase().as_str())
+    }
+}
+
+/// Whether a value reads as false: `0`, `false`, `no`, `n` or `off`, in any case and with surrounding whitespace
+///
+/// A value can be neither truthy nor falsy, which the macros treat as invalid.
+/// ```
+/// use envtime::runtime::is_falsy;
+///
+/// assert!(is_falsy("OFF"));
+/// assert!(is_falsy("0"));
+/// assert!(!is_falsy("on"));
+/// assert!(!is_falsy(""));
+/// ```
+pub fn is_falsy(s: &str) -> bool {
+    match s.trim() {
+        "0" => true,
+        s => FALSY.contains(&s.to_lowercase().as_str())
+    }
+}
+
+/// Expands the `${NAME}` references in a value from the environment, what `interpolate = true` does at runtime
+///
+/// A referenced value has its own references expanded. A reference to a variable that isn't set,
+/// or that refers back to a variable being expanded, is left as written instead of failing.
+/// ```
+/// use std::env;
+/// use envtime::runtime::interpolate;
+///
+/// env::set_var("DOC_HOST", "localhost");
+/// env::set_var("DOC_ADDR", "${DOC_HOST}:8080");
+/// assert_eq!(interpolate("http://${DOC_ADDR}/"), "http://localhost:8080/");
+/// assert_eq!(interpolate("${DOC_UNSET}"), "${DOC_UNSET}");
+/// ```
+pub fn interpolate(value: &str) -> String {
+    interpolate_refs(value, &mut Vec::new())
+}
+
+fn interpolate_refs(value: &str, expanding: &mut Vec<String>) -> String {
+    let mut expanded = String::new();
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        let end = match rest[start + 2..].find('}') {
+            Some(end) => start + 2 + end,
+            None => break
+        };
+        expanded.push_str(&rest[..start]);
+        let reference = &rest[start + 2..end];
+        let referenced = std::env::var(reference).ok().filter(|val| !val.trim().is_empty());
+        match referenced {
+            Some(referenced) if !expanding.iter().any(|name| name == reference) => {
+                expanding.push(reference.to_string());
+                expanded.push_str(&interpolate_refs(&referenced, expanding));
+                expanding.pop();
+            },
+            _ => expanded.push_str(&rest[start..=end])
+        }
+        rest = &rest[end + 1..];
+    }
+    expanded.push_str(rest);
+    expanded
+}
+
+/// The 64-bit FNV-1a hash of a value as 16 lowercase hex digits, what `envtime_hash!` gives at runtime
+///
+/// It's stable across builds and platforms, but not meant to be cryptographically secure.
+/// ```
+/// use envtime::runtime::fnv_hash;
+///
+/// assert_eq!(fnv_hash(""), "cbf29ce484222325");
+/// assert_eq!(fnv_hash("foobar"), "85944171f73967e8");
+/// ```
+pub fn fnv_hash(value: &str) -> String {
+    let hash = value.bytes().fold(0xcbf2_9ce4_8422_2325u64, |hash, byte| (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3));
+    format!("{:016x}", hash)
+}
+
+/// Parses a char from a single character, a `\u{...}` escape, a decimal code point or a `0x` prefixed hex code point
+///
+/// The value isn't trimmed, as a space is a char of its own.
+/// ```
+/// use envtime::runtime::parse_char;
+///
+/// assert_eq!(parse_char("a"), Some('a'));
+/// assert_eq!(parse_char("\\u{1F600}"), Some('😀'));
+/// assert_eq!(parse_char("65"), Some('A'));
+/// assert_eq!(parse_char("ab"), None);
+/// ```
+pub fn parse_char(s: &str) -> Option<char> {
+    let mut chars = s.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Some(c)
+    }
+    let hex = s.strip_prefix("\\u{").and_then(|s| s.strip_suffix('}'));
+    let code = match hex.or_else(|| s.strip_prefix("0x")).or_else(|| s.strip_prefix("0X")) {
+        Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+        None if !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit()) => s.parse().ok()?,
+        None => return None
+    };
+    std::char::from_u32(code)
+}
+
+/// A number without its `_` digit separators, like in Rust literals
+///
+/// A value starting or ending with one is left as is, so it stays malformed.
+/// ```
+/// use envtime::runtime::strip_separators;
+///
+/// assert_eq!(strip_separators("1_000_000"), "1000000");
+/// assert_eq!(strip_separators("_1"), "_1");
+/// ```
+pub fn strip_separators(s: &str) -> Cow<'_, str> {
+    if s.contains('_') && !s.starts_with('_') && !s.ends_with('_') {
+        Cow::Owned(s.replace('_', ""))
+    } else {
+        Cow::Borrowed(s)
+    }
+}
+
+/// Splits a `0x`, `0o` or `0b` radix prefix off an integer, keeping the sign and dropping `_` separators, for use with `from_str_radix`
+/// ```
+/// use envtime::runtime::split_radix;
+///
+/// assert_eq!(split_radix("-0x1F"), Some((String::from("-1F"), 16)));
+/// assert_eq!(split_radix("+1_000"), Some((String::from("1000"), 10)));
+/// assert_eq!(split_radix("0x-1"), None);
+/// ```
+pub fn split_radix(s: &str) -> Option<(String, u32)> {
+    let s = &*strip_separators(s);
+    let (sign, digits) = match s.strip_prefix('-') {
+        Some(digits) => ("-", digits),
+        None => ("", s.strip_prefix('+').unwrap_or(s))
+    };
+    let (radix, digits) = match digits.get(..2) {
+        Some("0x") | Some("0X") => (16, &digits[2..]),
+        Some("0o") | Some("0O") => (8, &digits[2..]),
+        Some("0b") | Some("0B") => (2, &digits[2..]),
+        _ => (10, digits)
+    };
+    if digits.starts_with(['+', '-']) {
+        return None
+    }
+    Some((format!("{}{}", sign, digits), radix))
+}
+
+/// The primitive integer types `parse_int` parses into
+pub trait Int: Sized {
+    fn from_str_radix(digits: &str, radix: u32) -> Option<Self>;
+}
+
+macro_rules! impl_int {
+    ($($ty:ty),*) => {
+        $(impl Int for $ty {
+            fn from_str_radix(digits: &str, radix: u32) -> Option<Self> {
+                <$ty>::from_str_radix(digits, radix).ok()
+            }
+        })*
+    };
+}
+
+impl_int!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+/// Parses an integer with surrounding whitespace, an optional sign, a `0x`, `0o` or `0b` prefix and `_` separators
+///
+/// `None` if it's malformed or doesn't fit the type.
+/// ```
+/// use envtime::runtime::parse_int;
+///
+/// assert_eq!(parse_int::<u8>(" 0x1F "), Some(31));
+/// assert_eq!(parse_int::<i32>("-1_000"), Some(-1000));
+/// assert_eq!(parse_int::<u8>("256"), None);
+/// ```
+pub fn parse_int<T: Int>(s: &str) -> Option<T> {
+    let (digits, radix) = split_radix(s.trim())?;
+    T::from_str_radix(&digits, radix)
+}
+
+/// Decodes standard base64, with optional padding and surrounding whitespace, what `envtime_bytes_b64!` does at runtime
+/// ```
+/// use envtime::runtime::decode_base64;
+///
+/// assert_eq!(decode_base64(" aGk= "), Some(b"hi".to_vec()));
+/// assert_eq!(decode_base64("aGk"), Some(b"hi".to_vec()));
+/// assert_eq!(decode_base64("aGk=="), None);
+/// ```
+pub fn decode_base64(s: &str) -> Option<Vec<u8>> {
+    let s = s.trim();
+    let data = s.trim_end_matches('=');
+    let padding = s.len() - data.len();
+    if padding > 2 || (padding > 0 && !s.len().is_multiple_of(4)) {
+        return None
+    }
+    let mut bytes = Vec::with_capacity(data.len() * 3 / 4);
+    let (mut acc, mut bits) = (0u32, 0u32);
+    for b in data.bytes() {
+        let val = match b {
+            b'A'..=b'Z' => b - b'A',
+            b'a'..=b'z' => b - b'a' + 26,
+            b'0'..=b'9' => b - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            _ => return None
+        };
+        acc = (acc << 6) | u32::from(val);
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((acc >> bits) as u8);
+            acc &= (1 << bits) - 1;
+        }
+    }
+    // A lone trailing character can't hold a byte, and the bits left over have to be zero
+    if bits >= 6 || acc != 0 {
+        return None
+    }
+    Some(bytes)
+}
+
+/// Decodes pairs of hex digits in either case, with an optional `0x` prefix and surrounding whitespace,
+/// what `envtime_bytes_hex!` does at runtime
+/// ```
+/// use envtime::runtime::decode_hex;
+///
+/// assert_eq!(decode_hex("0xDEad"), Some(vec![0xde, 0xad]));
+/// assert_eq!(decode_hex("abc"), None);
+/// ```
+pub fn decode_hex(s: &str) -> Option<Vec<u8>> {
+    let s = s.trim();
+    let s = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")).unwrap_or(s);
+    if !s.len().is_multiple_of(2) || !s.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None
+    }
+    (0..s.len()).step_by(2).map(|i| u8::from_str_radix(&s[i..i + 2], 16).ok()).collect()
+}
+
+/// Strips a single matching pair of `"` or `'` quotes around a value, what `unquote = true` does at runtime
+///
+/// Inner quotes are kept, and a value with unmatched quotes is left as is.
+/// ```
+/// use envtime::runtime::unquote;
+///
+/// assert_eq!(unquote("\"a 'b' c\""), "a 'b' c");
+/// assert_eq!(unquote("'a\""), "'a\"");
+/// assert_eq!(unquote("\""), "\"");
+/// ```
+pub fn unquote(s: &str) -> &str {
+    for quote in ['"', '\''] {
+        if s.len() >= 2 && s.starts_with(quote) && s.ends_with(quote) {
+            return &s[1..s.len() - 1]
+        }
+    }
+    s
+}
+
+/// Parses a duration like `500ms`, `30s`, `5m`, `2h` or a bare number of seconds, with surrounding whitespace,
+/// what `envtime_duration!` does at runtime
+///
+/// `None` if it's malformed or overflows a `u64` of milliseconds.
+/// ```
+/// use std::time::Duration;
+/// use envtime::runtime::parse_duration;
+///
+/// assert_eq!(parse_duration(" 500ms "), Some(Duration::from_millis(500)));
+/// assert_eq!(parse_duration("90"), Some(Duration::from_secs(90)));
+/// assert_eq!(parse_duration("2h"), Some(Duration::from_secs(7200)));
+/// assert_eq!(parse_duration("1.5s"), None);
+/// ```
+pub fn parse_duration(s: &str) -> Option<Duration> {
+    let s = s.trim();
+    let (num, factor) = if let Some(num) = s.strip_suffix("ms") {
+        (num, 1)
+    } else if let Some(num) = s.strip_suffix('s') {
+        (num, 1_000)
+    } else if let Some(num) = s.strip_suffix('m') {
+        (num, 60_000)
+    } else if let Some(num) = s.strip_suffix('h') {
+        (num, 3_600_000)
+    } else {
+        (s, 1_000)
+    };
+    if num.is_empty() || !num.bytes().all(|b| b.is_ascii_digit()) {
+        return None
+    }
+    num.parse::<u64>().ok()?.checked_mul(factor).map(Duration::from_millis)
+}
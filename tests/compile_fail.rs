@@ -0,0 +1,47 @@
+#[test]
+fn compile_fail() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/compile_fail/*.rs");
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn compile_fail_chrono() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/compile_fail/chrono/*.rs");
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn compile_fail_json() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/compile_fail/json/*.rs");
+}
+
+#[cfg(feature = "log")]
+#[test]
+fn compile_fail_log() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/compile_fail/log/*.rs");
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn compile_fail_regex() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/compile_fail/regex/*.rs");
+}
+
+#[cfg(feature = "strict-bool")]
+#[test]
+fn compile_fail_strict_bool() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/compile_fail/strict_bool/*.rs");
+}
+
+#[cfg(feature = "semver")]
+#[test]
+fn compile_fail_semver() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/compile_fail/semver/*.rs");
+}
@@ -0,0 +1,11 @@
+use envtime::*;
+
+fn main() {
+    // TEST_ARRAY_COMP_ENV is set to "255, 128, 0" at compile time
+    let _short = envtime_array!("TEST_ARRAY_COMP_ENV", [0u8, 0u8]);
+    let _narrow = envtime_array!("TEST_ARRAY_COMP_ENV", [0i8, 0i8, 0i8]);
+    let _flags = envtime_array!("TEST_ARRAY_COMP_ENV", [false, false, false]);
+    let _mixed = envtime_array!("TEST_ARRAY_RUN_ENV", [0u8, false]);
+    let _names = envtime_array!("TEST_ARRAY_RUN_ENV", ["a", "b"]);
+    let _empty: [u8; 0] = envtime_array!("TEST_ARRAY_RUN_ENV", []);
+}
@@ -0,0 +1,6 @@
+use envtime::*;
+
+fn main() {
+    // TEST_B64_INVALID_COMP_ENV is set to "not base64!" at compile time
+    let _key = envtime_bytes_b64!("TEST_B64_INVALID_COMP_ENV");
+}
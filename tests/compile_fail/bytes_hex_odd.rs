@@ -0,0 +1,6 @@
+use envtime::*;
+
+fn main() {
+    // TEST_HEX_BYTES_ODD_COMP_ENV is set to "abc" at compile time
+    let _mac = envtime_bytes_hex!("TEST_HEX_BYTES_ODD_COMP_ENV");
+}
@@ -0,0 +1,7 @@
+use envtime::*;
+
+fn main() {
+    // TEST_DATETIME_INVALID_COMP_ENV is set to "2024-13-01T00:00:00Z" at compile time
+    let _cutoff = envtime_datetime!("TEST_DATETIME_INVALID_COMP_ENV", "2024-01-01T00:00:00Z");
+    let _cutoff = envtime_datetime!("TEST_DATETIME_RUN_ENV", "2024-01-01");
+}
@@ -0,0 +1,8 @@
+use envtime::*;
+
+fn main() {
+    // TEST_DEF_INVALID_COMP_ENV is set to "abc" at compile time
+    const _COUNT: usize = envtime_const!("TEST_DEF_INVALID_COMP_ENV", 8usize);
+    const _SMALL: u8 = envtime_const!("TEST_OVERFLOW_U8_COMP_ENV", 0u8);
+    const _BYTES: &[u8] = envtime_const!("TEST_CONST_RUN_ENV", b"bytes");
+}
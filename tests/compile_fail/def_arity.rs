@@ -0,0 +1,9 @@
+use envtime::*;
+
+fn main() {
+    let _port = envtime_def!();
+    let _port = envtime_def!("TEST_DEF_ARITY_ENV");
+    let _port = envtime_def!("TEST_DEF_ARITY_ENV", 8080u16, 8081u16);
+    let _port = envtime_def!("TEST_DEF_ARITY_ENV", 8080, u16, u32);
+    let _port = envtime_cached!("TEST_DEF_ARITY_ENV");
+}
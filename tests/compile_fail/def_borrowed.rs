@@ -0,0 +1,7 @@
+use envtime::*;
+
+fn main() {
+    let _port = envtime_def!("TEST_BORROWED_RUN_ENV", 8080u16, borrowed = true);
+    let _format = envtime_def!("TEST_BORROWED_RUN_ENV", String::new(), borrowed = true);
+    let _format = envtime_cached!("TEST_BORROWED_RUN_ENV", "text", borrowed = true);
+}
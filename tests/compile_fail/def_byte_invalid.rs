@@ -0,0 +1,7 @@
+use envtime::*;
+
+fn main() {
+    // TEST_BYTE_NON_ASCII_COMP_ENV is set to "é" and TEST_DEF_INVALID_COMP_ENV to "abc" at compile time
+    let _delim = envtime_def!("TEST_BYTE_NON_ASCII_COMP_ENV", b',');
+    let _delim = envtime_def!("TEST_DEF_INVALID_COMP_ENV", b',');
+}
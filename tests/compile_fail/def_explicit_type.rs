@@ -0,0 +1,14 @@
+use envtime::*;
+
+fn main() {
+    let _count = envtime_def!("TEST_EXPLICIT_RUN_ENV", 0u8, u32);
+    let _count = envtime_def!("TEST_EXPLICIT_RUN_ENV", 0.5, u32);
+    let _name = envtime_def!("TEST_EXPLICIT_RUN_ENV", "none", String);
+    let _offset = envtime_def!("TEST_EXPLICIT_RUN_ENV", b'\xFF', i8);
+    let _count = envtime_def!("TEST_EXPLICIT_RUN_ENV", "hello", u32);
+    let _count = envtime_def!("TEST_EXPLICIT_RUN_ENV", true, u8);
+    let _count = envtime_def!("TEST_EXPLICIT_RUN_ENV", 'x', usize);
+    let _ratio = envtime_def!("TEST_EXPLICIT_RUN_ENV", "0.5", f64);
+    let _ratio = envtime_def!("TEST_EXPLICIT_RUN_ENV", b'x', f32);
+    let _ratio = envtime_def!("TEST_EXPLICIT_RUN_ENV", 1f32, f64);
+}
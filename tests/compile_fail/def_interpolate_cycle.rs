@@ -0,0 +1,8 @@
+use envtime::*;
+
+fn main() {
+    let _url = envtime_def!("TEST_COMPOSED_RUN_ENV", "${TEST_COMPOSED_RUN_ENV}/v1", interpolate = true);
+    let _url = envtime_def!("TEST_COMPOSED_RUN_ENV", "${TEST_COMPOSED_LEGACY_RUN_ENV}", env = "TEST_COMPOSED_LEGACY_RUN_ENV", interpolate = true);
+    // TEST_INTERP_CYCLE_COMP_ENV is set to "${TEST_INTERP_CYCLE_COMP_ENV}" at compile time
+    let _url = envtime_def!("TEST_COMPOSED_RUN_ENV", "https://${TEST_INTERP_CYCLE_COMP_ENV}", interpolate = true);
+}
@@ -0,0 +1,8 @@
+use envtime::*;
+
+fn main() {
+    // TEST_DEF_INVALID_COMP_ENV is set to "abc" at compile time
+    let _port = envtime_def!("TEST_DEF_INVALID_COMP_ENV", 8080u16);
+    let _ratio = envtime_def!("TEST_DEF_INVALID_COMP_ENV", 0.5f64);
+    let _delim = envtime_def!("TEST_DEF_INVALID_COMP_ENV", ',');
+}
@@ -0,0 +1,9 @@
+use envtime::*;
+
+fn main() {
+    // TEST_LEN_COMP_ENV is set to a 19 char token at compile time
+    let _short = envtime_def!("TEST_LEN_COMP_ENV", "", min_len = 32);
+    let _long = envtime_def!("TEST_LEN_COMP_ENV", "", max_len = 8);
+    let _port = envtime_def!("TEST_LEN_RUN_ENV", 8080u16, min_len = 2);
+    let _bounds = envtime_def!("TEST_LEN_RUN_ENV", "", min_len = 8, max_len = 4);
+}
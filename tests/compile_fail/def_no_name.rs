@@ -0,0 +1,5 @@
+use envtime::*;
+
+fn main() {
+    let _port = envtime_def!(8080u16, 8081u16);
+}
@@ -0,0 +1,8 @@
+use envtime::*;
+
+fn main() {
+    let _mode = envtime_def!("TEST_ONE_OF_INVALID_COMP_ENV", "prod", one_of = ["dev", "staging", "prod"]);
+    let _mode = envtime_def!("TEST_ONE_OF_RUN_ENV", "prod", one_of = []);
+    let _port = envtime_def!("TEST_ONE_OF_RUN_ENV", 8080u16, one_of = ["80", "8080"]);
+    let _mode = envtime_def!("TEST_ONE_OF_RUN_ENV", String::new(), one_of = ["dev"]);
+}
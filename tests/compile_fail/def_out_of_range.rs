@@ -0,0 +1,10 @@
+use envtime::*;
+
+fn main() {
+    // TEST_RANGE_LOW_COMP_ENV is set to 80 and TEST_RANGE_HIGH_COMP_ENV to 50000 at compile time
+    let _port = envtime_def!("TEST_RANGE_LOW_COMP_ENV", 8080u16, min = 1024, max = 49151);
+    let _port = envtime_def!("TEST_RANGE_HIGH_COMP_ENV", 8080u16, min = 1024, max = 49151);
+    let _port = envtime_def!("TEST_RANGE_RUN_ENV", 80u16, min = 1024);
+    let _port = envtime_def!("TEST_RANGE_RUN_ENV", 8080u16, min = -1);
+    let _name = envtime_def!("TEST_RANGE_RUN_ENV", "localhost", max = 10);
+}
@@ -0,0 +1,8 @@
+use envtime::*;
+
+fn main() {
+    // TEST_OVERFLOW_U8_COMP_ENV is set to "300" and TEST_OVERFLOW_I8_COMP_ENV to "-129" at compile time
+    let _count = envtime_def!("TEST_OVERFLOW_U8_COMP_ENV", 8u8);
+    let _offset = envtime_def!("TEST_OVERFLOW_I8_COMP_ENV", 0i8);
+    let _octet = envtime_def!("TEST_OVERFLOW_I8_COMP_ENV", b'a', i8);
+}
@@ -0,0 +1,7 @@
+use envtime::*;
+
+fn main() {
+    let _region = envtime_def!("TEST_TRANSFORM_RUN_ENV", "us", transform = "titlecase");
+    let _port = envtime_def!("TEST_TRANSFORM_RUN_ENV", 8080u16, transform = "lowercase");
+    let _addr = envtime_def!("TEST_TRANSFORM_RUN_ENV", std::net::Ipv4Addr::LOCALHOST, std::net::Ipv4Addr, transform = "lowercase");
+}
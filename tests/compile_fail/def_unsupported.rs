@@ -0,0 +1,6 @@
+use envtime::*;
+
+fn main() {
+    let _count = envtime_def!("TEST_DEF_UNSUPPORTED_ENV", 5u9);
+    let _ratio = envtime_def!("TEST_DEF_UNSUPPORTED_ENV", 0.5f16);
+}
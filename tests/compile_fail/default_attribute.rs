@@ -0,0 +1,22 @@
+use envtime::*;
+
+#[envtime_default(prot = "PORT", 8080u16)]
+fn listen(host: &str, port: u16) -> String {
+    format!("{}:{}", host, port)
+}
+
+#[envtime_default(port = "PORT", 8080u16, port = "LEGACY_PORT", 8080u16)]
+fn serve(port: u16) -> u16 {
+    port
+}
+
+struct Server;
+
+impl Server {
+    #[envtime_default(port = "PORT", 8080u16)]
+    fn bind(&self, port: u16) -> u16 {
+        port
+    }
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use envtime::*;
+
+#[derive(Envtime)]
+struct MissingAttr {
+    port: u16,
+}
+
+#[derive(Envtime)]
+struct NoDefault {
+    #[envtime(name = "TEST_DERIVE_RUN_ENV")]
+    port: u16,
+}
+
+#[derive(Envtime)]
+struct Tuple(u16);
+
+fn main() {}
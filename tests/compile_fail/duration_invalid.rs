@@ -0,0 +1,7 @@
+use envtime::*;
+
+fn main() {
+    // TEST_DEF_INVALID_COMP_ENV is set to "abc" at compile time
+    let _timeout = envtime_duration!("TEST_DEF_INVALID_COMP_ENV", "30s");
+    let _timeout = envtime_duration!("TEST_DURATION_RUN_ENV", "soon");
+}
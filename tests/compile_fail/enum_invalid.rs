@@ -0,0 +1,13 @@
+use envtime::*;
+
+#[derive(EnvtimeEnum)]
+enum Mode {
+    Development,
+    Production,
+}
+
+fn main() {
+    // TEST_ENUM_INVALID_COMP_ENV is set to "prod" at compile time
+    let _mode = envtime_enum!("TEST_ENUM_INVALID_COMP_ENV", Mode, "development");
+    let _mode = envtime_enum!("TEST_ENUM_RUN_ENV", Mode, "dev");
+}
@@ -0,0 +1,22 @@
+use std::convert::TryFrom;
+use envtime::*;
+
+enum State {
+    Idle,
+}
+
+impl TryFrom<i64> for State {
+    type Error = ();
+    fn try_from(n: i64) -> Result<Self, ()> {
+        match n {
+            0 => Ok(State::Idle),
+            _ => Err(()),
+        }
+    }
+}
+
+fn main() {
+    // TEST_ENUM_INVALID_COMP_ENV is set to "prod" at compile time
+    let _state = envtime_enum_num!("TEST_ENUM_INVALID_COMP_ENV", State, 0);
+    let _state = envtime_enum_num!("TEST_ENUM_NUM_RUN_ENV", State, 9223372036854775808);
+}
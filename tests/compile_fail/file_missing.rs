@@ -0,0 +1,6 @@
+use envtime::*;
+
+fn main() {
+    // TEST_FILE_MISSING_COMP_ENV is set to "tests/fixtures/missing.txt" at compile time
+    let _password = envtime_file!("TEST_FILE_MISSING_COMP_ENV");
+}
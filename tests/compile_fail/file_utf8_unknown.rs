@@ -0,0 +1,5 @@
+use envtime::*;
+
+fn main() {
+    let _menu = envtime_file!("TEST_FILE_INVALID_UTF8_COMP_ENV", utf8 = "latin1");
+}
@@ -0,0 +1,8 @@
+use envtime::*;
+
+fn main() {
+    let _url = envtime!("TEST_INTERP_UNRESOLVED_COMP_ENV", interpolate = true);
+    let _url = envtime_def!("TEST_INTERP_UNRESOLVED_COMP_ENV", "", interpolate = true);
+    let _cycle = envtime_def!("TEST_INTERP_CYCLE_COMP_ENV", "", interpolate = true);
+    let _port = envtime_def!("TEST_INTERP_RUN_ENV", 8080u16, interpolate = true);
+}
@@ -0,0 +1,6 @@
+use envtime::*;
+
+fn main() {
+    // TEST_JSON_MALFORMED_COMP_ENV is set to an unterminated object at compile time
+    let _features = envtime_json!("TEST_JSON_MALFORMED_COMP_ENV", std::collections::HashMap<String, bool>);
+}
@@ -0,0 +1,7 @@
+use envtime::*;
+
+fn main() {
+    // TEST_LOG_LEVEL_INVALID_COMP_ENV is set to "verbose" at compile time
+    let _level = envtime_log_level!("TEST_LOG_LEVEL_INVALID_COMP_ENV", "info");
+    let _level = envtime_log_level!("TEST_LOG_LEVEL_RUN_ENV", "warning");
+}
@@ -0,0 +1,9 @@
+use envtime::*;
+
+fn main() {
+    // TEST_MAP_MALFORMED_COMP_ENV is set to "env=prod,team" at compile time
+    let _labels = envtime_map!("TEST_MAP_MALFORMED_COMP_ENV");
+    // TEST_MAP_COMP_ENV is set to "env=prod, team = core,,region=eu" at compile time
+    let _labels = envtime_map!("TEST_MAP_COMP_ENV", pair_delim = ':');
+    let _labels = envtime_map!("TEST_MAP_RUN_ENV", delim = '=');
+}
@@ -0,0 +1,8 @@
+use envtime::*;
+
+fn main() {
+    // TEST_NONZERO_ZERO_COMP_ENV is set to 0 at compile time
+    let _port = envtime_nonzero!("TEST_NONZERO_ZERO_COMP_ENV", 8080u16);
+    let _port = envtime_nonzero!("TEST_NONZERO_RUN_ENV", 0u16);
+    let _port = envtime_nonzero!("TEST_NONZERO_RUN_ENV", 8080i16);
+}
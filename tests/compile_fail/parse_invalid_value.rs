@@ -0,0 +1,6 @@
+use envtime::*;
+
+fn main() {
+    // TEST_DEF_INVALID_COMP_ENV is set to "abc" at compile time
+    let _ = envtime_parse!("TEST_DEF_INVALID_COMP_ENV", u16);
+}
@@ -0,0 +1,6 @@
+use envtime::*;
+
+fn main() {
+    // TEST_PATH_MISSING_COMP_ENV is set to "does/not/exist" at compile time
+    let _dir = envtime_path!("TEST_PATH_MISSING_COMP_ENV", "./assets", check_exists);
+}
@@ -0,0 +1,8 @@
+use envtime::*;
+
+fn main() {
+    // TEST_PATTERN_COMP_ENV is set to "my-app" at compile time
+    let _mismatch = envtime_def!("TEST_PATTERN_COMP_ENV", "default", pattern = "^[0-9]+$");
+    let _invalid = envtime_def!("TEST_PATTERN_RUN_ENV", "default", pattern = "^[a-z");
+    let _port = envtime_def!("TEST_PATTERN_RUN_ENV", 8080u16, pattern = "^[0-9]+$");
+}
@@ -0,0 +1,5 @@
+use envtime::*;
+
+fn main() {
+    let _url = envtime_req!("TEST_REQ_MISSING_ENV");
+}
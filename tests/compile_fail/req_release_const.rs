@@ -0,0 +1,7 @@
+use envtime::*;
+
+const DSN_VAR: &str = "TEST_REQ_RELEASE_RUN_ENV";
+
+fn main() {
+    let _dsn = envtime_req_release!(DSN_VAR, "");
+}
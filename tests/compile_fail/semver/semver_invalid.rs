@@ -0,0 +1,7 @@
+use envtime::*;
+
+fn main() {
+    // TEST_SEMVER_INVALID_COMP_ENV is set to "1.2" at compile time
+    let _min = envtime_semver!("TEST_SEMVER_INVALID_COMP_ENV", "1.0.0");
+    let _min = envtime_semver!("TEST_SEMVER_RUN_ENV", "latest");
+}
@@ -0,0 +1,13 @@
+use envtime::*;
+
+fn main() {
+    // TEST_STRICT_BOOL_TYPO_COMP_ENV is set to "TRUEE" at compile time
+    let _enabled = envtime_def!("TEST_STRICT_BOOL_TYPO_COMP_ENV", false);
+    let _enabled = envtime_bool!("TEST_STRICT_BOOL_TYPO_COMP_ENV");
+    const ENABLED: bool = envtime_const!("TEST_STRICT_BOOL_TYPO_COMP_ENV", true);
+    envtime_cfg!("TEST_STRICT_BOOL_TYPO_COMP_ENV", {
+        println!("enabled");
+    });
+    // TEST_BOOL_TWO_COMP_ENV is set to "2" at compile time, which is a number and not a bool
+    let _enabled = envtime_def!("TEST_BOOL_TWO_COMP_ENV", false);
+}
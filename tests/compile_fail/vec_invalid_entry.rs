@@ -0,0 +1,6 @@
+use envtime::*;
+
+fn main() {
+    // TEST_VEC_COMP_ENV is set to "b, a,,b" at compile time
+    let _ports = envtime_vec!("TEST_VEC_COMP_ENV", u16);
+}
@@ -0,0 +1,36 @@
+#![cfg(feature = "manifest")]
+
+use std::fs;
+use std::path::Path;
+use envtime::*;
+
+// Built with ENVTIME_MANIFEST_OUT set, each name known while the macros expand is appended to that file
+#[test]
+fn manifest_tests() {
+    let _ = envtime!("TEST_MANIFEST_RUN_ENV");
+    let _ = envtime!("TEST_MANIFEST_FIRST_RUN_ENV", "TEST_MANIFEST_SECOND_RUN_ENV");
+    let _ = envtime_def!("TEST_MANIFEST_DEF_RUN_ENV", 8080u16, env = "TEST_MANIFEST_FALLBACK_RUN_ENV");
+    let _ = envtime_def!("TEST_MANIFEST_DEF_RUN_ENV", "");
+    let _ = envtime_cached!("TEST_MANIFEST_CACHED_RUN_ENV", false);
+    const NAME: &str = "TEST_MANIFEST_CONST_RUN_ENV";
+    let _ = envtime!(NAME);
+
+    let out = match option_env!("ENVTIME_MANIFEST_OUT") {
+        Some(out) => out,
+        None => panic!("ENVTIME_MANIFEST_OUT has to be set during the build")
+    };
+    let manifest = fs::read_to_string(Path::new(env!("CARGO_MANIFEST_DIR")).join(out)).unwrap();
+    let names = manifest.lines().collect::<Vec<_>>();
+    for name in [
+        "TEST_MANIFEST_RUN_ENV",
+        "TEST_MANIFEST_FIRST_RUN_ENV",
+        "TEST_MANIFEST_SECOND_RUN_ENV",
+        "TEST_MANIFEST_DEF_RUN_ENV",
+        "TEST_MANIFEST_FALLBACK_RUN_ENV",
+        "TEST_MANIFEST_CACHED_RUN_ENV",
+    ] {
+        assert!(names.contains(&name), "{} is missing from the manifest", name);
+    }
+    // A const's value isn't known while the macro expands
+    assert!(!names.contains(&"TEST_MANIFEST_CONST_RUN_ENV"));
+}
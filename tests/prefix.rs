@@ -0,0 +1,52 @@
+use std::env;
+use envtime::*;
+
+const PREFIX_CONST_NAME: &str = "CONST_RUN_ENV";
+
+#[derive(Envtime)]
+struct PrefixConfig {
+    #[envtime(name = "U16_COMP_ENV", default = 80u16)]
+    port: u16,
+    #[envtime(name = "STR_COMP_ENV")]
+    name: Option<String>,
+}
+
+#[envtime_default(port = "U16_COMP_ENV", 80u16)]
+fn prefixed_port(port: u16) -> u16 {
+    port
+}
+
+#[test]
+fn prefix_tests() {
+    // Only meaningful when built with ENVTIME_PREFIX=TEST_PREFIX_, which `option_env!` also rebuilds this file on
+    if option_env!("ENVTIME_PREFIX") != Some("TEST_PREFIX_") {
+        return
+    }
+
+    // TEST_PREFIX_STR_COMP_ENV and TEST_PREFIX_U16_COMP_ENV are set at compile time
+    assert_eq!(envtime!("STR_COMP_ENV"), Some(String::from("prefixed")));
+    assert_eq!(envtime_def!("U16_COMP_ENV", 80u16), 9000u16);
+    assert_eq!(envtime_expect!("STR_COMP_ENV", "set TEST_PREFIX_STR_COMP_ENV"), "prefixed");
+    let config = PrefixConfig::from_env();
+    assert_eq!((config.port, config.name), (9000, Some(String::from("prefixed"))));
+    assert_eq!(envtime_cow!("STR_COMP_ENV").as_deref(), Some("prefixed"));
+    assert_eq!(envtime_cached!("U16_COMP_ENV", 80u16), 9000u16);
+    assert_eq!(envtime_req_release!("U16_COMP_ENV", 80u16), 9000u16);
+    assert_eq!(envtime_def!("U16_UNSET_ENV", 80u16, env = "U16_COMP_ENV"), 9000u16);
+    assert!(envtime_matches!("STR_COMP_ENV", "prefixed"));
+    assert_eq!(prefixed_port_with_env_defaults(), 9000);
+
+    env::set_var("STR_RUN_ENV", "unprefixed");
+    assert_eq!(envtime!("STR_RUN_ENV"), None);
+    assert_eq!(envtime_def!("STR_RUN_ENV", "default"), "default");
+    // Only the macros listed in the docs apply the prefix
+    assert_eq!(envtime_str!("STR_RUN_ENV").as_deref(), Some("unprefixed"));
+    env::set_var("TEST_PREFIX_STR_RUN_ENV", "prefixed");
+    assert_eq!(envtime!("STR_RUN_ENV"), Some(String::from("prefixed")));
+    assert_eq!(envtime_def!("STR_RUN_ENV", "default"), "prefixed");
+
+    assert_eq!(envtime_def!(PREFIX_CONST_NAME, 1u8), 1u8);
+    env::set_var("TEST_PREFIX_CONST_RUN_ENV", "2");
+    assert_eq!(envtime_def!(PREFIX_CONST_NAME, 1u8), 2u8);
+    assert_eq!(envtime!(PREFIX_CONST_NAME), Some(String::from("2")));
+}
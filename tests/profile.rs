@@ -0,0 +1,15 @@
+use envtime::*;
+
+#[test]
+fn profile_tests() {
+    // Only meaningful when built with ENVTIME_PROFILE=ci, which `option_env!` also rebuilds this file on
+    if option_env!("ENVTIME_PROFILE") != Some("ci") {
+        return
+    }
+
+    // .env.ci overrides .env and .env.local, and .env.ci.local overrides all of them
+    assert_eq!(envtime!("TEST_DOTENV_PROFILE_ENV"), Some(String::from("ci")));
+    assert_eq!(envtime!("TEST_DOTENV_PROFILE_LOCAL_ENV"), Some(String::from("ci-local")));
+    assert_eq!(envtime!("TEST_DOTENV_LAYER_ENV"), Some(String::from("local")));
+    assert_eq!(envtime!("TEST_DOTENV_STR_ENV"), Some(String::from("from-dotenv")));
+}
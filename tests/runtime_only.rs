@@ -0,0 +1,25 @@
+#![cfg(feature = "runtime-only")]
+
+use std::env;
+use envtime::*;
+
+#[test]
+fn runtime_only_tests() {
+    // TEST_COMP_ENV is set to 456 at compile time, but never baked in
+    env::set_var("TEST_COMP_ENV", "123");
+    assert_eq!(envtime!("TEST_COMP_ENV"), Some(String::from("123")));
+    assert_eq!(envtime_def!("TEST_COMP_ENV", 0u16), 123u16);
+    assert_eq!(envtime_def!("TEST_COMP_ENV", "default"), "123");
+
+    env::remove_var("TEST_COMP_ENV");
+    assert_eq!(envtime!("TEST_COMP_ENV"), None);
+    assert_eq!(envtime_def!("TEST_COMP_ENV", 7u16), 7u16);
+
+    // Values from the .env file aren't baked in either
+    assert_eq!(envtime!("TEST_DOTENV_STR_ENV"), None);
+
+    // Compile time only macros still see the build environment
+    assert_eq!(envtime_req!("TEST_COMP_ENV"), "456");
+    const BAKED: u16 = envtime_const!("TEST_COMP_ENV", 0u16);
+    assert_eq!(BAKED, 456);
+}
@@ -0,0 +1,44 @@
+#![cfg(feature = "strict")]
+#![allow(clippy::bool_assert_comparison)]
+
+use std::env;
+use envtime::*;
+
+#[test]
+fn strict_valid_tests() {
+    assert_eq!(envtime_def!("TEST_STRICT_UNSET_ENV", 8080u16), 8080u16);
+    assert_eq!(envtime_def!("TEST_STRICT_UNSET_ENV", true), true);
+
+    env::set_var("TEST_STRICT_VALID_ENV", "9090");
+    assert_eq!(envtime_def!("TEST_STRICT_VALID_ENV", 8080u16, max = 10000), 9090u16);
+    env::set_var("TEST_STRICT_VALID_ENV", "off");
+    assert_eq!(envtime_def!("TEST_STRICT_VALID_ENV", true), false);
+}
+
+#[test]
+#[should_panic(expected = "invalid value for TEST_STRICT_INT_ENV")]
+fn strict_invalid_int_tests() {
+    env::set_var("TEST_STRICT_INT_ENV", "abc");
+    let _ = envtime_def!("TEST_STRICT_INT_ENV", 8080u16);
+}
+
+#[test]
+#[should_panic(expected = "invalid value for TEST_STRICT_RANGE_ENV")]
+fn strict_out_of_range_tests() {
+    env::set_var("TEST_STRICT_RANGE_ENV", "80");
+    let _ = envtime_def!("TEST_STRICT_RANGE_ENV", 8080u16, min = 1024);
+}
+
+#[test]
+#[should_panic(expected = "invalid value for TEST_STRICT_BOOL_ENV")]
+fn strict_invalid_bool_tests() {
+    env::set_var("TEST_STRICT_BOOL_ENV", "disabled");
+    let _ = envtime_def!("TEST_STRICT_BOOL_ENV", false);
+}
+
+#[test]
+#[should_panic(expected = "invalid value for TEST_STRICT_HELPER_ENV")]
+fn strict_invalid_helper_tests() {
+    env::set_var("TEST_STRICT_HELPER_ENV", "abc");
+    let _ = envtime::get_parsed("TEST_STRICT_HELPER_ENV", 8080u16);
+}
@@ -0,0 +1,34 @@
+#![cfg(feature = "strict-bool")]
+#![allow(clippy::bool_assert_comparison)]
+
+use std::env;
+use envtime::*;
+
+#[test]
+fn strict_bool_baked_tests() {
+    // TEST_STRICT_BOOL_ARRAY_COMP_ENV is set to every recognized value at compile time
+    let values = envtime_array!("TEST_STRICT_BOOL_ARRAY_COMP_ENV", [false, false, false, false, false, true, true, true, true, true]);
+    assert_eq!(values, [true, true, true, true, true, false, false, false, false, false]);
+    assert_eq!(envtime_def!("TEST_BOOL_UPPER_COMP_ENV", false), true);
+    assert_eq!(envtime_def!("TEST_STRICT_BOOL_OFF_COMP_ENV", true), false);
+    assert_eq!(envtime_bool!("TEST_STRICT_BOOL_OFF_COMP_ENV"), Some(false));
+}
+
+#[test]
+fn strict_bool_runtime_tests() {
+    for &(value, expected) in &[
+        ("true", true), ("1", true), ("yes", true), ("on", true), ("Y", true),
+        ("false", false), ("0", false), ("no", false), ("OFF", false), (" n ", false),
+    ] {
+        env::set_var("TEST_STRICT_BOOL_RUN_ENV", value);
+        // The default is the opposite, so it can't be what comes out
+        let value = if expected { envtime_def!("TEST_STRICT_BOOL_RUN_ENV", false) } else { envtime_def!("TEST_STRICT_BOOL_RUN_ENV", true) };
+        assert_eq!(value, expected);
+        assert_eq!(envtime_bool!("TEST_STRICT_BOOL_RUN_ENV"), Some(expected));
+    }
+    // Unlike with `strict`, a typo at runtime falls back to the default
+    env::set_var("TEST_STRICT_BOOL_RUN_ENV", "TRUEE");
+    assert_eq!(envtime_def!("TEST_STRICT_BOOL_RUN_ENV", false), false);
+    assert_eq!(envtime_def!("TEST_STRICT_BOOL_RUN_ENV", true), true);
+    assert_eq!(envtime_bool!("TEST_STRICT_BOOL_RUN_ENV"), None);
+}
@@ -1,4 +1,10 @@
+// These rely on the default features, `strict`, `strict-bool` and `runtime-only` are covered by their own test files
+#![cfg(not(any(feature = "strict", feature = "strict-bool", feature = "runtime-only")))]
+#![allow(clippy::bool_assert_comparison)]
+
+use std::convert::TryFrom;
 use std::env;
+use std::time::Duration;
 use envtime::*;
 
 #[test]
@@ -15,6 +21,168 @@ fn main_tests() {
     assert_eq!(var, Some(String::from("456")));
 }
 
+#[test]
+fn fallback_chain_tests() {
+    assert_eq!(envtime!("TEST_NON_ENV", "TEST_COMP_ENV", "TEST_CHAIN_RUN_ENV"), Some(String::from("456")));
+    assert_eq!(envtime!("TEST_DOTENV_OVERRIDE_ENV", "TEST_COMP_ENV"), Some(String::from("process")));
+
+    assert_eq!(envtime!("TEST_CHAIN_NEW_RUN_ENV", "TEST_CHAIN_OLD_RUN_ENV"), None);
+    env::set_var("TEST_CHAIN_OLD_RUN_ENV", "old");
+    assert_eq!(envtime!("TEST_CHAIN_NEW_RUN_ENV", "TEST_CHAIN_OLD_RUN_ENV"), Some(String::from("old")));
+    env::set_var("TEST_CHAIN_NEW_RUN_ENV", "new");
+    assert_eq!(envtime!("TEST_CHAIN_NEW_RUN_ENV", "TEST_CHAIN_OLD_RUN_ENV"), Some(String::from("new")));
+    assert_eq!(envtime!("test_chain_none_run_env", "test_chain_old_run_env", any_case), Some(String::from("old")));
+}
+
+#[test]
+fn dotenv_tests() {
+    env::set_var("TEST_DOTENV_STR_ENV", "runtime");
+    assert_eq!(envtime!("TEST_DOTENV_STR_ENV"), Some(String::from("from-dotenv")));
+    assert_eq!(envtime_def!("TEST_DOTENV_STR_ENV", "default"), "from-dotenv");
+    assert_eq!(envtime_def!("TEST_DOTENV_U16_ENV", 80u16), 4242u16);
+    // The environment takes precedence over the .env file
+    assert_eq!(envtime!("TEST_DOTENV_OVERRIDE_ENV"), Some(String::from("process")));
+}
+
+#[test]
+fn dotenv_syntax_tests() {
+    assert_eq!(envtime!("TEST_DOTENV_EXPORT_ENV"), Some(String::from("exported")));
+    assert_eq!(envtime!("TEST_DOTENV_SPACED_ENV"), Some(String::from("spaced out")));
+    assert_eq!(envtime!("TEST_DOTENV_COMMENT_ENV"), Some(String::from("value")));
+    assert_eq!(envtime!("TEST_DOTENV_HASH_ENV"), Some(String::from("no#comment")));
+    assert_eq!(envtime!("TEST_DOTENV_DOUBLE_QUOTED_ENV"), Some(String::from(" quoted # not a comment ")));
+    assert_eq!(envtime!("TEST_DOTENV_SINGLE_QUOTED_ENV"), Some(String::from("single quoted")));
+    assert_eq!(envtime_def!("TEST_DOTENV_QUOTED_U16_ENV", 80u16), 8080u16);
+    assert_eq!(envtime!("exporter"), Some(String::from("not an export")));
+}
+
+#[test]
+fn dotenv_layer_tests() {
+    // .env.local overrides .env, keys only in either file are kept
+    assert_eq!(envtime!("TEST_DOTENV_LAYER_ENV"), Some(String::from("local")));
+    assert_eq!(envtime!("TEST_DOTENV_LOCAL_ONLY_ENV"), Some(String::from("local")));
+    assert_eq!(envtime!("TEST_DOTENV_STR_ENV"), Some(String::from("from-dotenv")));
+    if option_env!("ENVTIME_PROFILE").is_none() {
+        assert_eq!(envtime!("TEST_DOTENV_PROFILE_ENV"), Some(String::from("base")));
+    }
+}
+
+#[test]
+fn req_tests() {
+    env::set_var("TEST_COMP_ENV", "123");
+    let var: &'static str = envtime_req!("TEST_COMP_ENV");
+    assert_eq!(var, "456");
+}
+
+#[test]
+fn const_tests() {
+    // TEST_CONST_COMP_ENV is set to 16 at compile time, and the runtime value is never read
+    env::set_var("TEST_CONST_COMP_ENV", "32");
+    const BAKED: usize = envtime_const!("TEST_CONST_COMP_ENV", 1024usize);
+    let buffer = [0u8; BAKED];
+    assert_eq!(buffer.len(), 16);
+
+    env::set_var("TEST_CONST_RUN_ENV", "32");
+    const DEFAULT: usize = envtime_const!("TEST_CONST_RUN_ENV", 1024usize);
+    let buffer = [0u8; DEFAULT];
+    assert_eq!(buffer.len(), 1024);
+
+    const NAME: &str = envtime_const!("TEST_COMP_ENV", "unset");
+    const OFFSET: i32 = envtime_const!("TEST_CONST_RUN_ENV", -8i32);
+    const MASK: u32 = envtime_const!("TEST_HEX_U32_COMP_ENV", 0u32);
+    const PADDED: u16 = envtime_const!("TEST_TRIM_U16_COMP_ENV", 0u16);
+    const RATIO: f64 = envtime_const!("TEST_F64_COMP_ENV", 1.0);
+    const ENABLED: bool = envtime_const!("TEST_BOOL_COMP_ENV", false);
+    const DELIM: char = envtime_const!("TEST_CHAR_COMP_ENV", ',');
+    assert_eq!(NAME, "456");
+    assert_eq!(OFFSET, -8);
+    assert_eq!(MASK, 255);
+    assert_eq!(PADDED, 8080);
+    assert_eq!(RATIO, -2.5);
+    const _: () = assert!(ENABLED);
+    assert_eq!(DELIM, ';');
+}
+
+// A baked in value is a literal, usable in a `const`
+const MATCHES: [bool; 4] = [
+    envtime_matches!("TEST_MATCHES_COMP_ENV", "debug"),
+    envtime_matches!("TEST_MATCHES_COMP_ENV", "release"),
+    envtime_matches!("TEST_MATCHES_COMP_ENV", "DEBUG"),
+    envtime_matches!("TEST_MATCHES_COMP_ENV", "DEBUG", ignore_case = true),
+];
+
+#[test]
+fn matches_tests() {
+    // TEST_MATCHES_COMP_ENV is set to " debug " at compile time
+    env::set_var("TEST_MATCHES_COMP_ENV", "release");
+    assert_eq!(MATCHES, [true, false, false, true]);
+
+    assert!(!envtime_matches!("TEST_MATCHES_RUN_ENV", "debug"));
+    assert!(!envtime_matches!("TEST_MATCHES_RUN_ENV", "", ignore_case = true));
+    env::set_var("TEST_MATCHES_RUN_ENV", "Debug\n");
+    assert!(!envtime_matches!("TEST_MATCHES_RUN_ENV", "debug"));
+    assert!(envtime_matches!("TEST_MATCHES_RUN_ENV", "debug", ignore_case = true));
+    assert!(envtime_matches!("TEST_MATCHES_RUN_ENV", "Debug", ignore_case = false));
+    assert!(!envtime_matches!("TEST_MATCHES_RUN_ENV", "debugging", ignore_case = true));
+
+    // A const name is only read at runtime
+    const MATCHES_VAR: &str = "TEST_MATCHES_COMP_ENV";
+    assert!(envtime_matches!(MATCHES_VAR, "release"));
+}
+
+#[test]
+fn req_release_tests() {
+    // TEST_REQ_RELEASE_COMP_ENV is set to "https://key@sentry.example/1" at compile time
+    env::set_var("TEST_REQ_RELEASE_COMP_ENV", "ignored");
+    assert_eq!(envtime_req_release!("TEST_REQ_RELEASE_COMP_ENV", ""), "https://key@sentry.example/1");
+    assert_eq!(envtime_req_release!("TEST_REQ_RELEASE_RUN_ENV", "", env = "TEST_REQ_RELEASE_COMP_ENV"), "https://key@sentry.example/1");
+    assert_eq!(envtime_req_release!("TEST_COMP_ENV", 0u16), 456u16);
+
+    // An unset variable only builds in debug, CI also runs this test with `--release`
+    #[cfg(debug_assertions)]
+    {
+        assert_eq!(envtime_req_release!("TEST_REQ_RELEASE_RUN_ENV", "dev"), "dev");
+        assert_eq!(envtime_req_release!("TEST_REQ_RELEASE_RUN_ENV", 8080u16), 8080u16);
+        env::set_var("TEST_REQ_RELEASE_RUN_ENV", "9090");
+        assert_eq!(envtime_req_release!("TEST_REQ_RELEASE_RUN_ENV", "dev"), "9090");
+        assert_eq!(envtime_req_release!("TEST_REQ_RELEASE_RUN_ENV", 8080u16), 9090u16);
+    }
+}
+
+#[test]
+fn expect_tests() {
+    // TEST_COMP_ENV is set to 456 at compile time
+    env::set_var("TEST_COMP_ENV", "123");
+    assert_eq!(envtime_expect!("TEST_COMP_ENV", "set TEST_COMP_ENV"), "456");
+
+    env::set_var("TEST_EXPECT_RUN_ENV", "postgres://localhost/app");
+    assert_eq!(envtime_expect!("TEST_EXPECT_RUN_ENV", "set TEST_EXPECT_RUN_ENV"), "postgres://localhost/app");
+    const EXPECT_VAR: &str = "TEST_EXPECT_RUN_ENV";
+    assert_eq!(envtime_expect!(EXPECT_VAR, "set TEST_EXPECT_RUN_ENV"), "postgres://localhost/app");
+}
+
+#[test]
+#[should_panic(expected = "set TEST_EXPECT_MISSING_RUN_ENV to your DB connection string")]
+fn expect_missing_tests() {
+    env::set_var("TEST_EXPECT_MISSING_RUN_ENV", " ");
+    let _ = envtime_expect!("TEST_EXPECT_MISSING_RUN_ENV", "set TEST_EXPECT_MISSING_RUN_ENV to your DB connection string");
+}
+
+#[test]
+fn any_case_tests() {
+    assert_eq!(envtime!("test_comp_env", any_case), Some(String::from("456")));
+    assert_eq!(envtime!("test_comp_env"), None);
+
+    assert_eq!(envtime!("Test_Case_Run_Env", any_case), None);
+    env::set_var("test_case_run_env", "lower");
+    assert_eq!(envtime!("Test_Case_Run_Env", any_case), Some(String::from("lower")));
+    env::set_var("TEST_CASE_RUN_ENV", "upper");
+    assert_eq!(envtime!("Test_Case_Run_Env", any_case), Some(String::from("upper")));
+    env::set_var("Test_Case_Run_Env", "exact");
+    assert_eq!(envtime!("Test_Case_Run_Env", any_case), Some(String::from("exact")));
+    assert_eq!(envtime!("TEST_case_RUN_env"), None);
+}
+
 #[test]
 fn compilation_def_tests() {
     env::set_var("TEST_BOOL_COMP_ENV", "false"); // Setting runtime-variable, ignored
@@ -31,6 +199,19 @@ fn compilation_def_tests() {
     env::set_var("TEST_I128_COMP_ENV", "10");
     let var = envtime_def!("TEST_I128_COMP_ENV", 50i128);
     assert_eq!(var, 25i128);
+
+    env::set_var("TEST_F32_COMP_ENV", "0");
+    let var = envtime_def!("TEST_F32_COMP_ENV", 1.0f32);
+    assert_eq!(var, 1.25f32);
+
+    env::set_var("TEST_F64_COMP_ENV", "0");
+    let var = envtime_def!("TEST_F64_COMP_ENV", 0.5f64);
+    assert_eq!(var, -2.5f64);
+
+    let var = envtime_def!("TEST_FLOAT_COMP_ENV", 0.5);
+    assert_eq!(var, 0.75f64);
+    let var: f32 = envtime_def!("TEST_FLOAT_COMP_ENV", 0.5);
+    assert_eq!(var, 0.75f32);
 }
 
 #[test]
@@ -47,6 +228,14 @@ fn runtime_def_tests() {
     env::set_var("TEST_BYTE_RUN_ENV", "53");
     assert_eq!(envtime_def!("TEST_BYTE_RUN_ENV", b'a'), 53u8);
 
+    assert_eq!(envtime_def!("TEST_CHAR_DEF_RUN_ENV", ','), ',');
+    env::set_var("TEST_CHAR_DEF_RUN_ENV", "|");
+    assert_eq!(envtime_def!("TEST_CHAR_DEF_RUN_ENV", ','), '|');
+    env::set_var("TEST_CHAR_DEF_RUN_ENV", "||");
+    assert_eq!(envtime_def!("TEST_CHAR_DEF_RUN_ENV", ','), ',');
+    env::remove_var("TEST_CHAR_DEF_RUN_ENV");
+    assert_eq!(envtime_def!("TEST_CHAR_DEF_RUN_ENV", ','), ',');
+
     assert_eq!(envtime_def!("TEST_U8_RUN_ENV", 77u8), 77u8);
     env::set_var("TEST_U8_RUN_ENV", "53");
     assert_eq!(envtime_def!("TEST_U8_RUN_ENV", 77u8), 53u8);
@@ -86,4 +275,1689 @@ fn runtime_def_tests() {
     assert_eq!(envtime_def!("TEST_I128_RUN_ENV", -90234513046340598234675i128), -90234513046340598234675i128);
     env::set_var("TEST_I128_RUN_ENV", "-12345983458945603456064");
     assert_eq!(envtime_def!("TEST_I128_RUN_ENV", -90234513046340598234675i128), -12345983458945603456064);
-}
\ No newline at end of file
+
+    assert_eq!(envtime_def!("TEST_F32_RUN_ENV", 1.0f32), 1.0f32);
+    env::set_var("TEST_F32_RUN_ENV", "3.5");
+    assert_eq!(envtime_def!("TEST_F32_RUN_ENV", 1.0f32), 3.5f32);
+
+    assert_eq!(envtime_def!("TEST_F64_RUN_ENV", 0.5f64), 0.5f64);
+    env::set_var("TEST_F64_RUN_ENV", "-0.125");
+    assert_eq!(envtime_def!("TEST_F64_RUN_ENV", 0.5f64), -0.125f64);
+
+    assert_eq!(envtime_def!("TEST_FLOAT_RUN_ENV", 0.5), 0.5f64);
+    env::set_var("TEST_FLOAT_RUN_ENV", "abc");
+    assert_eq!(envtime_def!("TEST_FLOAT_RUN_ENV", 0.5), 0.5f64);
+    env::set_var("TEST_FLOAT_RUN_ENV", "12");
+    assert_eq!(envtime_def!("TEST_FLOAT_RUN_ENV", 0.5), 12.0f64);
+}
+
+#[test]
+fn trim_tests() {
+    assert_eq!(envtime_def!("TEST_TRIM_U16_COMP_ENV", 80u16), 8080u16);
+    assert_eq!(envtime_def!("TEST_TRIM_BOOL_COMP_ENV", false), true);
+    assert_eq!(envtime_def!("TEST_TRIM_STR_COMP_ENV", "default"), "  padded ");
+    assert_eq!(envtime_def!("TEST_TRIM_STR_COMP_ENV", "default", trim = true), "padded");
+
+    env::set_var("TEST_TRIM_U16_RUN_ENV", " 8080\n");
+    assert_eq!(envtime_def!("TEST_TRIM_U16_RUN_ENV", 80u16), 8080u16);
+    env::set_var("TEST_TRIM_I8_RUN_ENV", "\t-12 ");
+    assert_eq!(envtime_def!("TEST_TRIM_I8_RUN_ENV", 0i8), -12i8);
+    env::set_var("TEST_TRIM_BOOL_RUN_ENV", "true\n");
+    assert_eq!(envtime_def!("TEST_TRIM_BOOL_RUN_ENV", false), true);
+    env::set_var("TEST_TRIM_F64_RUN_ENV", " 1.5 ");
+    assert_eq!(envtime_def!("TEST_TRIM_F64_RUN_ENV", 0.0f64), 1.5f64);
+    env::set_var("TEST_TRIM_STR_RUN_ENV", " padded\n");
+    assert_eq!(envtime_def!("TEST_TRIM_STR_RUN_ENV", "default"), " padded\n");
+    assert_eq!(envtime_def!("TEST_TRIM_STR_RUN_ENV", "default", trim = true), "padded");
+}
+
+#[test]
+fn transform_tests() {
+    // TEST_TRANSFORM_COMP_ENV is set to "Eu-West-1" at compile time
+    env::set_var("TEST_TRANSFORM_COMP_ENV", "ignored");
+    assert_eq!(envtime_def!("TEST_TRANSFORM_COMP_ENV", "us", transform = "lowercase"), "eu-west-1");
+    assert_eq!(envtime_def!("TEST_TRANSFORM_COMP_ENV", "us", transform = "uppercase"), "EU-WEST-1");
+    assert_eq!(envtime_def!("TEST_TRANSFORM_COMP_ENV", "us"), "Eu-West-1");
+    assert_eq!(envtime_cached!("TEST_TRANSFORM_COMP_ENV", "us", transform = "lowercase"), "eu-west-1");
+
+    assert_eq!(envtime_def!("TEST_TRANSFORM_RUN_ENV", "Us", transform = "lowercase"), "Us");
+    env::set_var("TEST_TRANSFORM_RUN_ENV", " \"Eu-West-1\" ");
+    assert_eq!(envtime_def!("TEST_TRANSFORM_RUN_ENV", "us", transform = "lowercase"), " \"eu-west-1\" ");
+    assert_eq!(envtime_def!("TEST_TRANSFORM_RUN_ENV", "us", transform = "uppercase", trim = true, unquote = true), "EU-WEST-1");
+    assert_eq!(envtime_def!("TEST_TRANSFORM_RUN_ENV", env = "TEST_TRANSFORM_COMP_ENV", transform = "lowercase"), Some(String::from("eu-west-1")));
+    assert_eq!(envtime_def!("TEST_TRANSFORM_UNSET_RUN_ENV", env = "TEST_TRANSFORM_RUN_ENV", transform = "uppercase"), Some(String::from(" \"EU-WEST-1\" ")));
+}
+
+const BORROWED_STR: std::borrow::Cow<'static, str> = envtime_def!("TEST_BORROWED_COMP_ENV", "text", borrowed = true);
+
+#[test]
+fn borrowed_tests() {
+    use std::borrow::Cow;
+
+    // TEST_BORROWED_COMP_ENV is set to " JSON " at compile time, a baked value is borrowed from a literal
+    assert!(matches!(BORROWED_STR, Cow::Borrowed(" JSON ")));
+    assert!(matches!(envtime_def!("TEST_BORROWED_COMP_ENV", "text", borrowed = true, trim = true), Cow::Borrowed("JSON")));
+    assert!(matches!(
+        envtime_def!("TEST_BORROWED_COMP_ENV", "text", borrowed = true, trim = true, transform = "lowercase"),
+        Cow::Borrowed("json")
+    ));
+    assert_eq!(envtime_def!("TEST_BORROWED_COMP_ENV", "text", borrowed = false), " JSON ");
+
+    assert!(matches!(envtime_def!("TEST_BORROWED_RUN_ENV", "text", borrowed = true), Cow::Borrowed("text")));
+    assert!(matches!(envtime_def!("TEST_BORROWED_RUN_ENV", "text", borrowed = true, max_len = 8), Cow::Borrowed("text")));
+    env::set_var("TEST_BORROWED_RUN_ENV", "logfmt");
+    assert!(matches!(envtime_def!("TEST_BORROWED_RUN_ENV", "text", borrowed = true), Cow::Owned(s) if s == "logfmt"));
+    assert!(matches!(envtime_def!("TEST_BORROWED_RUN_ENV", "text", borrowed = true, max_len = 4), Cow::Borrowed("text")));
+    assert_eq!(envtime_def!("TEST_BORROWED_RUN_ENV", "text", borrowed = true, transform = "uppercase"), "LOGFMT");
+}
+
+const MAGIC: std::borrow::Cow<'static, [u8]> = envtime_def!("TEST_BYTE_STR_COMP_ENV", b"MZ");
+
+#[test]
+fn byte_str_tests() {
+    use std::borrow::Cow;
+
+    // TEST_BYTE_STR_COMP_ENV is set to " PK " at compile time, its bytes are baked in as they are
+    assert!(matches!(MAGIC, Cow::Borrowed(b" PK ")));
+    assert_eq!(envtime_cached!("TEST_BYTE_STR_COMP_ENV", b"MZ"), b" PK ");
+
+    assert!(matches!(envtime_def!("TEST_BYTE_STR_RUN_ENV", b"MZ"), Cow::Borrowed(b"MZ")));
+    assert_eq!(envtime_cached!("TEST_BYTE_STR_RUN_ENV", b"MZ"), b"MZ");
+    env::set_var("TEST_BYTE_STR_RUN_ENV", "\u{7f}ELF");
+    assert!(matches!(envtime_def!("TEST_BYTE_STR_RUN_ENV", b"MZ"), Cow::Owned(bytes) if bytes == b"\x7fELF"));
+    assert_eq!(&*envtime_def!("TEST_BYTE_STR_RUN_ENV", b""), b"\x7fELF");
+}
+
+#[test]
+fn unquote_tests() {
+    assert_eq!(envtime!("TEST_QUOTED_COMP_ENV", unquote = true), Some(String::from("alice")));
+    assert_eq!(envtime!("TEST_QUOTED_COMP_ENV"), Some(String::from("\"alice\"")));
+    assert_eq!(envtime_def!("TEST_SINGLE_QUOTED_COMP_ENV", "bob", unquote = true), "o'brien");
+    assert_eq!(envtime_def!("TEST_QUOTED_COMP_ENV", "bob", trim = true, unquote = true), "alice");
+
+    assert_eq!(envtime!("TEST_UNQUOTE_RUN_ENV", unquote = true), None);
+    assert_eq!(envtime_def!("TEST_UNQUOTE_RUN_ENV", "'default'", unquote = true), "'default'");
+    for &(value, expected) in &[
+        ("\"alice\"", "alice"),
+        ("'alice'", "alice"),
+        ("alice", "alice"),
+        ("say \"hi\"", "say \"hi\""),
+        ("\"it's\"", "it's"),
+        ("\"mixed'", "\"mixed'"),
+        ("\"", "\""),
+    ] {
+        env::set_var("TEST_UNQUOTE_RUN_ENV", value);
+        assert_eq!(envtime!("TEST_UNQUOTE_RUN_ENV", unquote = true), Some(String::from(expected)));
+        assert_eq!(envtime_def!("TEST_UNQUOTE_RUN_ENV", "bob", unquote = true), expected);
+    }
+    env::set_var("TEST_UNQUOTE_RUN_ENV", " 'padded' ");
+    assert_eq!(envtime_def!("TEST_UNQUOTE_RUN_ENV", "bob", unquote = true), " 'padded' ");
+    assert_eq!(envtime_def!("TEST_UNQUOTE_RUN_ENV", "bob", trim = true, unquote = true), "padded");
+}
+
+#[test]
+fn empty_tests() {
+    // TEST_EMPTY_COMP_ENV is set to an empty value at compile time, and isn't baked in
+    env::set_var("TEST_EMPTY_COMP_ENV", "runtime");
+    assert_eq!(envtime!("TEST_EMPTY_COMP_ENV"), Some(String::from("runtime")));
+    env::set_var("TEST_EMPTY_COMP_ENV", "");
+    assert_eq!(envtime!("TEST_EMPTY_COMP_ENV"), None);
+    assert_eq!(envtime_def!("TEST_EMPTY_COMP_ENV", "default"), "default");
+    assert_eq!(envtime_def!("TEST_EMPTY_COMP_ENV", 8080u16), 8080u16);
+    assert_eq!(envtime_def!("TEST_EMPTY_COMP_ENV", true), true);
+
+    env::set_var("TEST_EMPTY_RUN_ENV", "");
+    assert_eq!(envtime!("TEST_EMPTY_RUN_ENV"), None);
+    assert_eq!(envtime_def!("TEST_EMPTY_RUN_ENV", "default"), "default");
+    assert_eq!(envtime_def!("TEST_EMPTY_RUN_ENV", 8080u16), 8080u16);
+    assert_eq!(envtime_def!("TEST_EMPTY_RUN_ENV", true), true);
+    env::set_var("TEST_EMPTY_RUN_ENV", "  ");
+    assert_eq!(envtime!("TEST_EMPTY_RUN_ENV"), None);
+    assert_eq!(envtime_def!("TEST_EMPTY_RUN_ENV", 8080u16), 8080u16);
+    assert_eq!(envtime_def!("TEST_EMPTY_RUN_ENV", true), true);
+}
+
+#[test]
+fn cached_tests() {
+    env::set_var("TEST_COMP_ENV", "123");
+    assert_eq!(envtime_cached!("TEST_COMP_ENV", 0u16), 456u16);
+    assert_eq!(envtime_cached!("TEST_TRIM_STR_COMP_ENV", "default", trim = true), "padded");
+
+    let port = || envtime_cached!("TEST_CACHED_RUN_ENV", 8080u16);
+    let name = || envtime_cached!("TEST_CACHED_STR_RUN_ENV", "default");
+    env::set_var("TEST_CACHED_RUN_ENV", "1234");
+    env::set_var("TEST_CACHED_STR_RUN_ENV", "first");
+    assert_eq!(port(), 1234u16);
+    assert_eq!(name(), "first");
+    env::set_var("TEST_CACHED_RUN_ENV", "4321");
+    env::set_var("TEST_CACHED_STR_RUN_ENV", "second");
+    assert_eq!(port(), 1234u16);
+    assert_eq!(name(), "first");
+    // A different invocation has its own cache
+    assert_eq!(envtime_cached!("TEST_CACHED_RUN_ENV", 8080u16), 4321u16);
+}
+
+const TEST_COMP_ENV_NAME: &str = "TEST_COMP_ENV";
+const TEST_CONST_RUN_ENV_NAME: &str = "TEST_CONST_RUN_ENV";
+
+mod names {
+    pub const TEST_CONST_FALLBACK_ENV: &str = "TEST_CONST_FALLBACK_ENV";
+}
+
+#[test]
+fn const_name_tests() {
+    // The literal name is baked in, the const name is always read at runtime
+    env::set_var("TEST_COMP_ENV", "123");
+    assert_eq!(envtime!("TEST_COMP_ENV"), Some(String::from("456")));
+    assert_eq!(envtime!(TEST_COMP_ENV_NAME), Some(String::from("123")));
+    assert_eq!(envtime_def!("TEST_COMP_ENV", 0u16), 456u16);
+    assert_eq!(envtime_def!(TEST_COMP_ENV_NAME, 0u16), 123u16);
+
+    assert_eq!(envtime!(TEST_CONST_RUN_ENV_NAME), None);
+    assert_eq!(envtime_def!(TEST_CONST_RUN_ENV_NAME, 7u8), 7u8);
+    assert_eq!(envtime_def!(true, TEST_CONST_RUN_ENV_NAME), true);
+    env::set_var("TEST_CONST_RUN_ENV", "9");
+    assert_eq!(envtime!(TEST_CONST_RUN_ENV_NAME), Some(String::from("9")));
+    assert_eq!(envtime_def!(TEST_CONST_RUN_ENV_NAME, 7u8), 9u8);
+    assert_eq!(envtime_def!(TEST_CONST_RUN_ENV_NAME, "default"), "9");
+
+    // A const ahead of a literal keeps the literal from being baked in
+    assert_eq!(envtime!(names::TEST_CONST_FALLBACK_ENV, "TEST_COMP_ENV"), Some(String::from("123")));
+    env::set_var("TEST_CONST_FALLBACK_ENV", "first");
+    assert_eq!(envtime!(names::TEST_CONST_FALLBACK_ENV, "TEST_COMP_ENV"), Some(String::from("first")));
+}
+
+#[test]
+fn package_default_tests() {
+    assert_eq!(envtime_def!("TEST_PKG_RUN_ENV", pkg_version), env!("CARGO_PKG_VERSION"));
+    assert_eq!(envtime_def!("TEST_PKG_RUN_ENV", pkg_name), "envtime");
+    assert_eq!(envtime_def!("TEST_PKG_RUN_ENV", pkg_authors), env!("CARGO_PKG_AUTHORS"));
+    assert_eq!(envtime_def!("TEST_PKG_RUN_ENV", pkg_repository), "https://github.com/AsyncVoid/envtime");
+    assert_eq!(envtime_def!("TEST_PKG_RUN_ENV", pkg_homepage), "");
+    const PKG_VAR: &str = "TEST_PKG_CONST_RUN_ENV";
+    assert_eq!(envtime_def!(PKG_VAR, pkg_description), env!("CARGO_PKG_DESCRIPTION"));
+    assert_eq!(envtime_cached!("TEST_PKG_RUN_ENV", pkg_version), env!("CARGO_PKG_VERSION"));
+
+    // TEST_COMP_ENV is set to "456" at compile time
+    assert_eq!(envtime_def!("TEST_COMP_ENV", pkg_version), "456");
+    env::set_var("TEST_PKG_RUN_ENV", "0.1.0-dev");
+    assert_eq!(envtime_def!("TEST_PKG_RUN_ENV", pkg_version), "0.1.0-dev");
+    assert_eq!(envtime_def!("TEST_PKG_RUN_ENV", pkg_version, max_len = 5), env!("CARGO_PKG_VERSION"));
+}
+
+// TEST_CFG_OFF_COMP_ENV is set to "off" at compile time, so this never gets compiled
+envtime_cfg!("TEST_CFG_OFF_COMP_ENV", {
+    compile_error!("envtime_cfg! included a block for a falsy variable");
+});
+
+// TEST_CFG_ON_COMP_ENV is set to " Yes " at compile time
+envtime_cfg!("TEST_CFG_ON_COMP_ENV", {
+    fn cfg_included() -> &'static str {
+        "included"
+    }
+});
+
+#[test]
+fn cfg_tests() {
+    assert_eq!(cfg_included(), "included");
+
+    env::set_var("TEST_CFG_RUN_ENV", "true");
+    let mut included = String::new();
+    envtime_cfg!("TEST_CFG_ON_COMP_ENV", {
+        included.push_str("on");
+    });
+    envtime_cfg!("TEST_CFG_OFF_COMP_ENV", {
+        included.push_str("off");
+    });
+    envtime_cfg!("TEST_CFG_RUN_ENV", {
+        included.push_str("unset");
+    });
+    assert_eq!(included, "on");
+}
+
+#[test]
+fn hash_tests() {
+    // TEST_HASH_COMP_ENV is set to "manifest-v2" at compile time
+    const MANIFEST_HASH: &str = envtime_hash!("TEST_HASH_COMP_ENV");
+    assert_eq!(MANIFEST_HASH, "e6218962567036ef");
+    assert_eq!(envtime_hash!("TEST_HASH_COMP_ENV", "assets-v1.json"), "e6218962567036ef");
+    // The hash from the build is the same as the runtime one
+    assert_eq!(MANIFEST_HASH, envtime::runtime::fnv_hash("manifest-v2"));
+
+    assert_eq!(envtime_hash!("TEST_HASH_RUN_ENV"), "cbf29ce484222325");
+    assert_eq!(envtime_hash!("TEST_HASH_RUN_ENV", "assets-v1.json"), "1b7bbd47a1d17822");
+    env::set_var("TEST_HASH_RUN_ENV", "foobar");
+    assert_eq!(envtime_hash!("TEST_HASH_RUN_ENV", "assets-v1.json"), "85944171f73967e8");
+    assert_eq!(envtime_hash!("TEST_HASH_RUN_ENV"), envtime::runtime::fnv_hash("foobar"));
+}
+
+#[test]
+fn one_of_tests() {
+    // TEST_ONE_OF_COMP_ENV is set to "staging" at compile time
+    assert_eq!(envtime_def!("TEST_ONE_OF_COMP_ENV", "prod", one_of = ["dev", "staging", "prod"]), "staging");
+    assert_eq!(envtime_def!("TEST_ONE_OF_COMP_ENV", "", one_of = ["staging"], borrowed = true), "staging");
+
+    assert_eq!(envtime_def!("TEST_ONE_OF_RUN_ENV", "prod", one_of = ["dev", "staging", "prod"]), "prod");
+    env::set_var("TEST_ONE_OF_RUN_ENV", "dev");
+    assert_eq!(envtime_def!("TEST_ONE_OF_RUN_ENV", "prod", one_of = ["dev", "staging", "prod"]), "dev");
+    env::set_var("TEST_ONE_OF_RUN_ENV", "qa");
+    assert_eq!(envtime_def!("TEST_ONE_OF_RUN_ENV", "prod", one_of = ["dev", "staging", "prod"]), "prod");
+    env::set_var("TEST_ONE_OF_RUN_ENV", " Dev ");
+    assert_eq!(envtime_def!("TEST_ONE_OF_RUN_ENV", "prod", one_of = ["dev", "staging", "prod",]), "prod");
+    assert_eq!(envtime_def!("TEST_ONE_OF_RUN_ENV", "prod", one_of = ["dev", "staging", "prod"], trim = true, transform = "lowercase"), "dev");
+}
+
+#[test]
+#[should_panic(expected = "env var TEST_ONE_OF_REQUIRED_RUN_ENV has to be set to one of [\"dev\", \"prod\"]")]
+fn one_of_required_tests() {
+    envtime_def!("TEST_ONE_OF_REQUIRED_RUN_ENV", "", one_of = ["dev", "prod"]);
+}
+
+#[test]
+fn interpolate_tests() {
+    // TEST_INTERP_URL_COMP_ENV is set to " https://${TEST_INTERP_ADDR_COMP_ENV}/${ " at compile time,
+    // which references "${TEST_INTERP_HOST_COMP_ENV}:8080" and then "example.com"
+    assert_eq!(envtime!("TEST_INTERP_URL_COMP_ENV", interpolate = true), Some(String::from(" https://example.com:8080/${ ")));
+    assert_eq!(envtime!("TEST_INTERP_URL_COMP_ENV"), Some(String::from(" https://${TEST_INTERP_ADDR_COMP_ENV}/${ ")));
+    assert_eq!(envtime_def!("TEST_INTERP_URL_COMP_ENV", "", interpolate = true, trim = true), "https://example.com:8080/${");
+    assert_eq!(envtime_def!("TEST_INTERP_URL_COMP_ENV", String::new(), interpolate = true, trim = true), "https://example.com:8080/${");
+    assert_eq!(envtime_cached!("TEST_INTERP_ADDR_COMP_ENV", "", interpolate = true), "example.com:8080");
+
+    assert_eq!(envtime!("TEST_INTERP_RUN_ENV", interpolate = true), None);
+    env::set_var("TEST_INTERP_RUN_HOST_ENV", "db.local");
+    env::set_var("TEST_INTERP_RUN_ADDR_ENV", "${TEST_INTERP_RUN_HOST_ENV}:5432");
+    env::set_var("TEST_INTERP_RUN_ENV", "postgres://${TEST_INTERP_RUN_ADDR_ENV}/${TEST_INTERP_RUN_DB_ENV}");
+    assert_eq!(envtime!("TEST_INTERP_RUN_ENV", interpolate = true), Some(String::from("postgres://db.local:5432/${TEST_INTERP_RUN_DB_ENV}")));
+    assert_eq!(envtime_def!("TEST_INTERP_RUN_ENV", "", interpolate = true), "postgres://db.local:5432/${TEST_INTERP_RUN_DB_ENV}");
+    assert_eq!(envtime_def!("TEST_INTERP_RUN_ENV", ""), "postgres://${TEST_INTERP_RUN_ADDR_ENV}/${TEST_INTERP_RUN_DB_ENV}");
+    // Only the value is expanded, not the variable, so the reference back goes around once before it's left as written
+    env::set_var("TEST_INTERP_RUN_DB_ENV", "${TEST_INTERP_RUN_ENV}");
+    assert_eq!(
+        envtime_def!("TEST_INTERP_RUN_ENV", "", interpolate = true),
+        "postgres://db.local:5432/postgres://db.local:5432/${TEST_INTERP_RUN_DB_ENV}"
+    );
+}
+
+#[test]
+fn composed_default_tests() {
+    use std::borrow::Cow;
+
+    // TEST_INTERP_ADDR_COMP_ENV is set to "${TEST_INTERP_HOST_COMP_ENV}:8080" at compile time, the default isn't used
+    assert_eq!(envtime_def!("TEST_INTERP_ADDR_COMP_ENV", "${TEST_COMPOSED_HOST_RUN_ENV}", interpolate = true), "example.com:8080");
+
+    assert_eq!(envtime_def!("TEST_COMPOSED_RUN_ENV", "https://${TEST_COMPOSED_HOST_RUN_ENV}/v1", interpolate = true), "https://${TEST_COMPOSED_HOST_RUN_ENV}/v1");
+    assert_eq!(envtime_def!("TEST_COMPOSED_RUN_ENV", "https://${TEST_COMPOSED_HOST_RUN_ENV}/v1"), "https://${TEST_COMPOSED_HOST_RUN_ENV}/v1");
+    env::set_var("TEST_COMPOSED_HOST_RUN_ENV", "api.local");
+    assert_eq!(envtime_def!("TEST_COMPOSED_RUN_ENV", "https://${TEST_COMPOSED_HOST_RUN_ENV}/v1", interpolate = true), "https://api.local/v1");
+    assert_eq!(envtime_def!("TEST_COMPOSED_RUN_ENV", "https://${TEST_COMPOSED_HOST_RUN_ENV}/v1"), "https://${TEST_COMPOSED_HOST_RUN_ENV}/v1");
+    assert!(matches!(
+        envtime_def!("TEST_COMPOSED_RUN_ENV", "${TEST_COMPOSED_HOST_RUN_ENV}", interpolate = true, borrowed = true),
+        Cow::Owned(s) if s == "api.local"
+    ));
+    assert_eq!(envtime_def!("TEST_COMPOSED_RUN_ENV", "${TEST_COMPOSED_HOST_RUN_ENV}", interpolate = true, max_len = 32), "api.local");
+    assert_eq!(envtime_cached!("TEST_COMPOSED_RUN_ENV", "${TEST_COMPOSED_HOST_RUN_ENV}:443", interpolate = true), "api.local:443");
+
+    env::set_var("TEST_COMPOSED_RUN_ENV", "https://${TEST_COMPOSED_HOST_RUN_ENV}/v2");
+    assert_eq!(envtime_def!("TEST_COMPOSED_RUN_ENV", "https://${TEST_COMPOSED_HOST_RUN_ENV}/v1", interpolate = true), "https://api.local/v2");
+}
+
+#[test]
+fn none_default_tests() {
+    // TEST_NONE_COMP_ENV is set to " 8080 " at compile time
+    assert_eq!(envtime_def!("TEST_NONE_COMP_ENV", None::<u16>), Some(8080u16));
+    assert_eq!(envtime_def!("TEST_NONE_COMP_ENV", Option::<u64>::None), Some(8080u64));
+    assert_eq!(envtime_def!("TEST_NONE_COMP_ENV", None::<String>), Some(String::from("8080")));
+    let cached: Option<u16> = envtime_cached!("TEST_NONE_COMP_ENV", None::<u16>);
+    assert_eq!(cached, Some(8080));
+    assert_eq!(envtime_cached!("TEST_NONE_COMP_ENV", Option::<String>::None), Some(String::from("8080")));
+
+    assert_eq!(envtime_def!("TEST_NONE_RUN_ENV", None::<u16>), None);
+    assert_eq!(envtime_def!("TEST_NONE_RUN_ENV", Option::<u16>::None), None);
+    env::set_var("TEST_NONE_RUN_ENV", " 443 ");
+    assert_eq!(envtime_def!("TEST_NONE_RUN_ENV", None::<u16>), Some(443));
+    assert_eq!(envtime_def!("TEST_NONE_RUN_ENV", std::option::Option::<std::net::Ipv4Addr>::None), None);
+    const NONE_VAR: &str = "TEST_NONE_RUN_ENV";
+    assert_eq!(envtime_def!(NONE_VAR, None::<i32>), Some(443));
+
+    let cached = || envtime_cached!("TEST_NONE_CACHED_RUN_ENV", None::<u16>);
+    assert_eq!(cached(), None);
+    env::set_var("TEST_NONE_CACHED_RUN_ENV", "443");
+    assert_eq!(cached(), None);
+    assert_eq!(envtime_cached!("TEST_NONE_CACHED_RUN_ENV", None::<String>), Some(String::from("443")));
+}
+
+#[test]
+fn explicit_type_tests() {
+    assert_eq!(envtime_def!("TEST_COMP_ENV", 0, u32), 456u32);
+    assert_eq!(envtime_def!("TEST_I128_COMP_ENV", 0, i128), 25i128);
+    assert_eq!(envtime_def!("TEST_F32_COMP_ENV", 0, f32), 1.25f32);
+    assert_eq!(envtime_def!(0, "TEST_COMP_ENV", u64), 456u64);
+
+    assert_eq!(envtime_def!("TEST_EXPLICIT_RUN_ENV", 7, u8), 7u8);
+    assert_eq!(envtime_def!("TEST_EXPLICIT_RUN_ENV", -7, i16), -7i16);
+    assert_eq!(envtime_def!("TEST_EXPLICIT_RUN_ENV", 2f64, f64), 2.0);
+    env::set_var("TEST_EXPLICIT_RUN_ENV", "3000000000");
+    assert_eq!(envtime_def!("TEST_EXPLICIT_RUN_ENV", 0, u32), 3_000_000_000u32);
+    assert_eq!(envtime_def!("TEST_EXPLICIT_RUN_ENV", 0, i32), 0i32);
+    assert_eq!(envtime_def!("TEST_EXPLICIT_RUN_ENV", 0, f64), 3e9);
+    assert_eq!(envtime_def!("TEST_EXPLICIT_RUN_ENV", 0.5, f32), 3e9f32);
+    assert_eq!(envtime_def!("TEST_EXPLICIT_RUN_ENV", 2f32, f32), 3e9f32);
+    assert_eq!(envtime_def!("TEST_EXPLICIT_RUN_ENV", 0, u64, max = 100), 0u64);
+}
+
+#[test]
+fn byte_explicit_type_tests() {
+    assert_eq!(envtime_def!("TEST_SIGNED_BYTE_COMP_ENV", b'a', i8), -12i8);
+    assert_eq!(envtime_def!("TEST_U8_COMP_ENV", b'a', i8), 12i8);
+
+    assert_eq!(envtime_def!("TEST_SIGNED_BYTE_RUN_ENV", b'a', i8), 97i8);
+    assert_eq!(envtime_def!("TEST_SIGNED_BYTE_RUN_ENV", b'\x7F', i8), 127i8);
+    assert_eq!(envtime_def!("TEST_SIGNED_BYTE_RUN_ENV", b'\xFF', u16), 255u16);
+    for &(value, expected) in &[("-128", -128i8), ("127", 127), (" -5 ", -5), ("0x10", 16), ("128", 97), ("a", 97)] {
+        env::set_var("TEST_SIGNED_BYTE_RUN_ENV", value);
+        assert_eq!(envtime_def!("TEST_SIGNED_BYTE_RUN_ENV", b'a', i8), expected);
+    }
+}
+
+#[test]
+fn float_format_tests() {
+    // TEST_FLOAT_SCI_COMP_ENV, TEST_FLOAT_INF_COMP_ENV and TEST_FLOAT_NAN_COMP_ENV are set to "2.5E3", "-inf" and "NaN" at compile time
+    assert_eq!(envtime_def!("TEST_FLOAT_SCI_COMP_ENV", 0.0f32), 2500f32);
+    assert_eq!(envtime_def!("TEST_FLOAT_SCI_COMP_ENV", 0.0), 2500.0);
+    assert_eq!(envtime_def!("TEST_FLOAT_INF_COMP_ENV", 0.0f32), f32::NEG_INFINITY);
+    assert_eq!(envtime_def!("TEST_FLOAT_INF_COMP_ENV", 0.0f64), f64::NEG_INFINITY);
+    let ratio: f32 = envtime_def!("TEST_FLOAT_INF_COMP_ENV", 0.0);
+    assert_eq!(ratio, f32::NEG_INFINITY);
+    assert!(envtime_def!("TEST_FLOAT_NAN_COMP_ENV", 0.0f64).is_nan());
+    let ratio: f32 = envtime_def!("TEST_FLOAT_NAN_COMP_ENV", 0.0);
+    assert!(ratio.is_nan());
+    // Without an annotation a baked value falls back to f64, whether it's finite or not
+    let ratio = envtime_def!("TEST_FLOAT_NAN_COMP_ENV", 0.0);
+    assert_eq!(format!("{}", ratio), "NaN");
+    let ratio = envtime_def!("TEST_FLOAT_INF_COMP_ENV", 0.0);
+    assert_eq!(format!("{}", ratio), "-inf");
+    const LIMIT: f64 = envtime_const!("TEST_FLOAT_INF_COMP_ENV", 0.0f64);
+    assert_eq!(LIMIT, f64::NEG_INFINITY);
+    assert_eq!(envtime_def!("TEST_FLOAT_SCI_COMP_ENV", 0, f64), 2500.0);
+    assert!(envtime_def!("TEST_FLOAT_NAN_COMP_ENV", 0, f32).is_nan());
+
+    for &(value, expected) in &[("1e-9", 1e-9), ("2.5E3", 2500.0), ("inf", f64::INFINITY), ("-Infinity", f64::NEG_INFINITY)] {
+        env::set_var("TEST_FLOAT_FORMAT_RUN_ENV", value);
+        assert_eq!(envtime_def!("TEST_FLOAT_FORMAT_RUN_ENV", 0.5), expected);
+    }
+    env::set_var("TEST_FLOAT_FORMAT_RUN_ENV", "nan");
+    assert!(envtime_def!("TEST_FLOAT_FORMAT_RUN_ENV", 0.5f32).is_nan());
+    env::set_var("TEST_FLOAT_FORMAT_RUN_ENV", "1e");
+    assert_eq!(envtime_def!("TEST_FLOAT_FORMAT_RUN_ENV", 0.5), 0.5);
+}
+
+#[test]
+fn byte_format_tests() {
+    // TEST_BYTE_HEX_COMP_ENV, TEST_BYTE_OCTAL_COMP_ENV and TEST_BYTE_CHAR_COMP_ENV are set to "0x41", "0o101" and "A" at compile time
+    assert_eq!(envtime_def!("TEST_BYTE_HEX_COMP_ENV", b'a'), b'A');
+    assert_eq!(envtime_def!("TEST_BYTE_OCTAL_COMP_ENV", b'a'), b'A');
+    assert_eq!(envtime_def!("TEST_BYTE_CHAR_COMP_ENV", b'a'), b'A');
+    assert_eq!(envtime_const!("TEST_BYTE_CHAR_COMP_ENV", b'a'), b'A');
+    assert_eq!(envtime_def!("TEST_BYTE_COMP_ENV", b'a'), 10u8);
+
+    for &(value, expected) in &[("65", b'A'), ("0x41", b'A'), ("0X7f", 0x7F), ("0o101", b'A'), ("0b1000001", b'A'), (" A ", b'A'), ("7", 7), ("~", b'~')] {
+        env::set_var("TEST_BYTE_FORMAT_RUN_ENV", value);
+        assert_eq!(envtime_def!("TEST_BYTE_FORMAT_RUN_ENV", b'a'), expected);
+    }
+    for &value in &["é", "AB", "256", "0x100", "-1"] {
+        env::set_var("TEST_BYTE_FORMAT_RUN_ENV", value);
+        assert_eq!(envtime_def!("TEST_BYTE_FORMAT_RUN_ENV", b'a'), b'a');
+    }
+}
+
+const DEFAULT_HOST: &str = "localhost";
+
+fn default_level() -> Level {
+    Level::Info
+}
+
+#[test]
+fn expr_default_tests() {
+    assert_eq!(envtime_def!("TEST_COMP_ENV", DEFAULT_HOST), "456");
+    assert_eq!(envtime_def!("TEST_OPT_LEVEL_COMP_ENV", default_level(), Level), Level::Warn);
+    assert_eq!(envtime_def!("TEST_COMP_ENV", u16::MAX, u16), 456u16);
+
+    assert_eq!(envtime_def!("TEST_EXPR_RUN_ENV", DEFAULT_HOST), "localhost");
+    assert_eq!(envtime_def!("TEST_EXPR_RUN_ENV", DEFAULT_HOST.to_uppercase()), "LOCALHOST");
+    assert_eq!(envtime_def!("TEST_EXPR_RUN_ENV", default_level(), Level), Level::Info);
+    assert_eq!(envtime_def!("TEST_EXPR_RUN_ENV", u16::MAX, u16), u16::MAX);
+    env::set_var("TEST_EXPR_RUN_ENV", "warn");
+    assert_eq!(envtime_def!("TEST_EXPR_RUN_ENV", DEFAULT_HOST), "warn");
+    assert_eq!(envtime_def!("TEST_EXPR_RUN_ENV", default_level(), Level), Level::Warn);
+    env::set_var("TEST_EXPR_RUN_ENV", "debug");
+    assert_eq!(envtime_def!("TEST_EXPR_RUN_ENV", default_level(), Level), Level::Info);
+}
+
+#[test]
+fn env_default_tests() {
+    // Only TEST_FALLBACK_GENERIC_COMP_ENV is set at compile time, to 7000
+    assert_eq!(envtime_def!("TEST_FALLBACK_SPECIFIC_COMP_ENV", 8080u16, env = "TEST_FALLBACK_GENERIC_COMP_ENV"), 7000u16);
+    assert_eq!(envtime_def!("TEST_FALLBACK_SPECIFIC_COMP_ENV", env = "TEST_FALLBACK_GENERIC_COMP_ENV"), Some(String::from("7000")));
+    assert_eq!(envtime_cached!("TEST_FALLBACK_SPECIFIC_COMP_ENV", env = "TEST_FALLBACK_GENERIC_COMP_ENV"), Some("7000"));
+    assert_eq!(envtime_def!("TEST_U8_COMP_ENV", 0u8, env = "TEST_FALLBACK_GENERIC_COMP_ENV"), 12u8);
+
+    assert_eq!(envtime_def!("TEST_FALLBACK_SPECIFIC_RUN_ENV", 8080u16, env = "TEST_FALLBACK_GENERIC_RUN_ENV"), 8080u16);
+    assert_eq!(envtime_def!("TEST_FALLBACK_SPECIFIC_RUN_ENV", env = "TEST_FALLBACK_GENERIC_RUN_ENV"), None);
+    env::set_var("TEST_FALLBACK_GENERIC_RUN_ENV", "8000");
+    assert_eq!(envtime_def!("TEST_FALLBACK_SPECIFIC_RUN_ENV", 8080u16, env = "TEST_FALLBACK_GENERIC_RUN_ENV"), 8000u16);
+    assert_eq!(envtime_def!("TEST_FALLBACK_SPECIFIC_RUN_ENV", "none", env = "TEST_FALLBACK_GENERIC_RUN_ENV"), "8000");
+    assert_eq!(envtime_def!("TEST_FALLBACK_SPECIFIC_RUN_ENV", env = "TEST_FALLBACK_GENERIC_RUN_ENV"), Some(String::from("8000")));
+    let cached = || envtime_cached!("TEST_FALLBACK_SPECIFIC_RUN_ENV", env = "TEST_FALLBACK_GENERIC_RUN_ENV");
+    assert_eq!(cached(), Some("8000"));
+    env::set_var("TEST_FALLBACK_SPECIFIC_RUN_ENV", "9000");
+    assert_eq!(envtime_def!("TEST_FALLBACK_SPECIFIC_RUN_ENV", 8080u16, env = "TEST_FALLBACK_GENERIC_RUN_ENV"), 9000u16);
+    assert_eq!(envtime_def!("TEST_FALLBACK_SPECIFIC_RUN_ENV", env = "TEST_FALLBACK_GENERIC_RUN_ENV"), Some(String::from("9000")));
+    assert_eq!(cached(), Some("8000"));
+    env::set_var("TEST_FALLBACK_SPECIFIC_RUN_ENV", "many");
+    assert_eq!(envtime_def!("TEST_FALLBACK_SPECIFIC_RUN_ENV", 8080u16, env = "TEST_FALLBACK_GENERIC_RUN_ENV"), 8080u16);
+}
+
+#[test]
+fn trailing_comma_tests() {
+    assert_eq!(envtime_def!("TEST_COMP_ENV", 0u16,), 456u16);
+    assert_eq!(envtime_def!("TEST_BOOL_COMP_ENV", false,), true);
+
+    assert_eq!(envtime_def!("TEST_TRAILING_RUN_ENV", 8080u16,), 8080u16);
+    assert_eq!(envtime_def!("TEST_TRAILING_RUN_ENV", "default",), "default");
+    assert_eq!(envtime_def!("TEST_TRAILING_RUN_ENV", 0, u32,), 0u32);
+    assert_eq!(envtime_def!("TEST_TRAILING_RUN_ENV", 5u8, max = 10,), 5u8);
+    env::set_var("TEST_TRAILING_RUN_ENV", "7");
+    assert_eq!(envtime_def!("TEST_TRAILING_RUN_ENV", 8080u16,), 7u16);
+    assert_eq!(envtime_def!("TEST_TRAILING_RUN_ENV", "default",), "7");
+    assert_eq!(envtime_cached!("TEST_TRAILING_RUN_ENV", 0i64,), 7i64);
+}
+
+#[test]
+fn int_default_tests() {
+    assert_eq!(envtime_def!("TEST_U8_COMP_ENV", 0x1Fu8), 12u8);
+    assert_eq!(envtime_def!("TEST_COMP_ENV", 1_000i32), 456i32);
+    assert_eq!(envtime_def!("TEST_I128_COMP_ENV", -5i128), 25i128);
+
+    assert_eq!(envtime_def!("TEST_INT_DEFAULT_RUN_ENV", 0x1Fu8), 31u8);
+    assert_eq!(envtime_def!("TEST_INT_DEFAULT_RUN_ENV", 0b1_0000u16), 16u16);
+    assert_eq!(envtime_def!("TEST_INT_DEFAULT_RUN_ENV", 1_000i32), 1000i32);
+    assert_eq!(envtime_def!("TEST_INT_DEFAULT_RUN_ENV", -5i8), -5i8);
+    env::set_var("TEST_INT_DEFAULT_RUN_ENV", "-100");
+    assert_eq!(envtime_def!("TEST_INT_DEFAULT_RUN_ENV", 1_000i32), -100i32);
+    assert_eq!(envtime_def!("TEST_INT_DEFAULT_RUN_ENV", 0x1Fu8), 31u8);
+}
+
+#[test]
+fn range_tests() {
+    assert_eq!(envtime_def!("TEST_RANGE_COMP_ENV", 8080u16, min = 1024, max = 49151), 9000u16);
+    assert_eq!(envtime_def!("TEST_RANGE_COMP_ENV", 0i32, max = 9000), 9000i32);
+
+    env::set_var("TEST_RANGE_RUN_ENV", "9090");
+    assert_eq!(envtime_def!("TEST_RANGE_RUN_ENV", 8080u16, min = 1024, max = 49151), 9090u16);
+    env::set_var("TEST_RANGE_RUN_ENV", "80");
+    assert_eq!(envtime_def!("TEST_RANGE_RUN_ENV", 8080u16, min = 1024, max = 49151), 8080u16);
+    assert_eq!(envtime_def!("TEST_RANGE_RUN_ENV", 8080u16, max = 49151), 80u16);
+    env::set_var("TEST_RANGE_RUN_ENV", "50000");
+    assert_eq!(envtime_def!("TEST_RANGE_RUN_ENV", 8080u16, min = 1024, max = 49151), 8080u16);
+    assert_eq!(envtime_def!("TEST_RANGE_RUN_ENV", 8080u16, min = 1024), 50000u16);
+    env::set_var("TEST_RANGE_RUN_ENV", "-5");
+    assert_eq!(envtime_def!("TEST_RANGE_RUN_ENV", 0i8, min = -3, max = 3), 0i8);
+    env::set_var("TEST_RANGE_RUN_ENV", "-3");
+    assert_eq!(envtime_def!("TEST_RANGE_RUN_ENV", 0i8, min = -3, max = 3), -3i8);
+}
+
+#[test]
+fn len_tests() {
+    // TEST_LEN_COMP_ENV is set to a 19 char token at compile time
+    assert_eq!(envtime_def!("TEST_LEN_COMP_ENV", "", min_len = 16, max_len = 64), "sk-0123456789abcdef");
+    assert_eq!(envtime_def!("TEST_LEN_COMP_ENV", "", min_len = 19, max_len = 19), "sk-0123456789abcdef");
+
+    let default = "0123456789abcdef";
+    assert_eq!(envtime_def!("TEST_LEN_RUN_ENV", "0123456789abcdef", min_len = 16, max_len = 64), default);
+    for &(value, expected) in &[
+        ("too-short", default),
+        ("fedcba9876543210", "fedcba9876543210"),
+        ("ééééééééééééééééé", "ééééééééééééééééé"),
+        ("0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef0", default),
+    ] {
+        env::set_var("TEST_LEN_RUN_ENV", value);
+        assert_eq!(envtime_def!("TEST_LEN_RUN_ENV", "0123456789abcdef", min_len = 16, max_len = 64), expected);
+    }
+    env::set_var("TEST_LEN_RUN_ENV", "  padded  ");
+    assert_eq!(envtime_def!("TEST_LEN_RUN_ENV", "none", max_len = 6), "none");
+    assert_eq!(envtime_def!("TEST_LEN_RUN_ENV", "none", max_len = 6, trim = true), "padded");
+    assert_eq!(envtime_def!("TEST_LEN_RUN_ENV", "", min_len = 1), "  padded  ");
+}
+
+#[test]
+#[should_panic(expected = "env var TEST_LEN_REQUIRED_RUN_ENV has to be set to a value with a length in 16..=64")]
+fn len_required_tests() {
+    env::set_var("TEST_LEN_REQUIRED_RUN_ENV", "too-short");
+    let _ = envtime_def!("TEST_LEN_REQUIRED_RUN_ENV", "", min_len = 16, max_len = 64);
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn pattern_tests() {
+    // TEST_PATTERN_COMP_ENV is set to "my-app" at compile time
+    env::set_var("TEST_PATTERN_COMP_ENV", "Not A Slug");
+    assert_eq!(envtime_def!("TEST_PATTERN_COMP_ENV", "default", pattern = "^[a-z0-9-]+$"), "my-app");
+
+    assert_eq!(envtime_def!("TEST_PATTERN_RUN_ENV", "default", pattern = "^[a-z0-9-]+$"), "default");
+    for &(value, expected) in &[
+        ("my-app-2", "my-app-2"),
+        ("Not A Slug", "default"),
+        ("my_app", "default"),
+        ("", "default"),
+    ] {
+        env::set_var("TEST_PATTERN_RUN_ENV", value);
+        assert_eq!(envtime_def!("TEST_PATTERN_RUN_ENV", "default", pattern = "^[a-z0-9-]+$"), expected);
+    }
+    env::set_var("TEST_PATTERN_RUN_ENV", "  v1.2.3 ");
+    assert_eq!(envtime_def!("TEST_PATTERN_RUN_ENV", "v0.0.0", pattern = r"^v\d+\.\d+\.\d+$"), "v0.0.0");
+    assert_eq!(envtime_def!("TEST_PATTERN_RUN_ENV", "v0.0.0", pattern = r"^v\d+\.\d+\.\d+$", trim = true), "v1.2.3");
+    assert_eq!(envtime_def!("TEST_PATTERN_RUN_ENV", "v0", pattern = r"^v\d", max_len = 4, trim = true), "v0");
+}
+
+#[cfg(feature = "regex")]
+#[test]
+#[should_panic(expected = "env var TEST_PATTERN_REQUIRED_RUN_ENV has to be set to a value matching \"^[a-z0-9-]+$\"")]
+fn pattern_required_tests() {
+    env::set_var("TEST_PATTERN_REQUIRED_RUN_ENV", "Not A Slug");
+    let _ = envtime_def!("TEST_PATTERN_REQUIRED_RUN_ENV", "", pattern = "^[a-z0-9-]+$");
+}
+
+#[test]
+fn nonzero_tests() {
+    use std::num::{NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8};
+
+    assert_eq!(envtime_nonzero!("TEST_U8_COMP_ENV", 1u8), NonZeroU8::new(12).unwrap());
+    assert_eq!(envtime_nonzero!("PORT", 8080u16), NonZeroU16::new(5678).unwrap());
+
+    assert_eq!(envtime_nonzero!("TEST_NONZERO_RUN_ENV", 8080u16), NonZeroU16::new(8080).unwrap());
+    env::set_var("TEST_NONZERO_RUN_ENV", "0");
+    assert_eq!(envtime_nonzero!("TEST_NONZERO_RUN_ENV", 7u8), NonZeroU8::new(7).unwrap());
+    assert_eq!(envtime_nonzero!("TEST_NONZERO_RUN_ENV", 8080u16), NonZeroU16::new(8080).unwrap());
+    assert_eq!(envtime_nonzero!("TEST_NONZERO_RUN_ENV", 4u32), NonZeroU32::new(4).unwrap());
+    assert_eq!(envtime_nonzero!("TEST_NONZERO_RUN_ENV", 9u64), NonZeroU64::new(9).unwrap());
+    env::set_var("TEST_NONZERO_RUN_ENV", "0x10");
+    assert_eq!(envtime_nonzero!("TEST_NONZERO_RUN_ENV", 7u8), NonZeroU8::new(16).unwrap());
+    assert_eq!(envtime_nonzero!("TEST_NONZERO_RUN_ENV", 9u64), NonZeroU64::new(16).unwrap());
+    env::set_var("TEST_NONZERO_RUN_ENV", "-1");
+    assert_eq!(envtime_nonzero!("TEST_NONZERO_RUN_ENV", 4u32), NonZeroU32::new(4).unwrap());
+}
+
+#[derive(Envtime)]
+struct DeriveConfig {
+    #[envtime(name = "TEST_COMP_ENV", default = 0u16)]
+    baked_port: u16,
+    #[envtime(name = "TEST_BOOL_COMP_ENV", default = false)]
+    baked_flag: bool,
+    #[envtime(name = "TEST_DERIVE_HOST_RUN_ENV", default = "localhost")]
+    host: String,
+    #[envtime(name = "TEST_DERIVE_PORT_RUN_ENV", default = 8080u16)]
+    port: u16,
+    #[envtime(name = "TEST_DERIVE_VERBOSE_RUN_ENV", default = false)]
+    verbose: bool,
+    #[envtime(name = "TEST_DERIVE_WORKERS_RUN_ENV")]
+    workers: Option<u8>,
+}
+
+#[test]
+fn derive_tests() {
+    env::set_var("TEST_COMP_ENV", "123");
+    let config = DeriveConfig::from_env();
+    assert_eq!(config.baked_port, 456);
+    assert_eq!(config.baked_flag, true);
+    assert_eq!(config.host, "localhost");
+    assert_eq!(config.port, 8080);
+    assert_eq!(config.verbose, false);
+    assert_eq!(config.workers, None);
+
+    env::set_var("TEST_DERIVE_HOST_RUN_ENV", "example.com");
+    env::set_var("TEST_DERIVE_PORT_RUN_ENV", "9090");
+    env::set_var("TEST_DERIVE_VERBOSE_RUN_ENV", "yes");
+    env::set_var("TEST_DERIVE_WORKERS_RUN_ENV", "4");
+    let config = DeriveConfig::from_env();
+    assert_eq!(config.host, "example.com");
+    assert_eq!(config.port, 9090);
+    assert_eq!(config.verbose, true);
+    assert_eq!(config.workers, Some(4));
+}
+
+#[envtime_default(port = "TEST_COMP_ENV", 0u16, retries = "TEST_DEFAULT_RETRIES_RUN_ENV", 3u8)]
+fn connect(host: &str, port: u16, retries: u8) -> String {
+    format!("{}:{} ({} retries)", host, port, retries)
+}
+
+#[envtime_default(name = "TEST_DEFAULT_NAME_RUN_ENV", "world")]
+fn greet(name: String) -> String {
+    format!("hello {}", name)
+}
+
+#[test]
+fn default_attribute_tests() {
+    // TEST_COMP_ENV is set to 456 at compile time
+    env::set_var("TEST_COMP_ENV", "123");
+    assert_eq!(connect_with_env_defaults("localhost"), "localhost:456 (3 retries)");
+    env::set_var("TEST_DEFAULT_RETRIES_RUN_ENV", "5");
+    assert_eq!(connect_with_env_defaults("localhost"), "localhost:456 (5 retries)");
+    assert_eq!(connect("example.com", 443, 1), "example.com:443 (1 retries)");
+
+    assert_eq!(greet_with_env_defaults(), "hello world");
+    env::set_var("TEST_DEFAULT_NAME_RUN_ENV", "envtime");
+    assert_eq!(greet_with_env_defaults(), "hello envtime");
+}
+
+#[test]
+fn radix_tests() {
+    assert_eq!(envtime_def!("TEST_HEX_U32_COMP_ENV", 0u32), 255u32);
+    assert_eq!(envtime_def!("TEST_OCT_U16_COMP_ENV", 0u16), 493u16);
+    assert_eq!(envtime_def!("TEST_BIN_I8_COMP_ENV", 0i8), -5i8);
+    let flags: u64 = envtime_def!("TEST_HEX_COMP_ENV", 0);
+    assert_eq!(flags, 31);
+
+    env::set_var("TEST_RADIX_RUN_ENV", "0xff");
+    assert_eq!(envtime_def!("TEST_RADIX_RUN_ENV", 0u32), 255u32);
+    assert_eq!(envtime_def!("TEST_RADIX_RUN_ENV", 0u8), 255u8);
+    assert_eq!(envtime_def!("TEST_RADIX_RUN_ENV", 0i8), 0i8);
+    env::set_var("TEST_RADIX_RUN_ENV", "0o17");
+    assert_eq!(envtime_def!("TEST_RADIX_RUN_ENV", 0u32), 15u32);
+    assert_eq!(envtime_def!("TEST_RADIX_RUN_ENV", 0i64), 15i64);
+    env::set_var("TEST_RADIX_RUN_ENV", "-0b1010");
+    assert_eq!(envtime_def!("TEST_RADIX_RUN_ENV", 0i16), -10i16);
+    assert_eq!(envtime_def!("TEST_RADIX_RUN_ENV", 1u16), 1u16);
+    env::set_var("TEST_RADIX_RUN_ENV", "0X1F");
+    let flags: u64 = envtime_def!("TEST_RADIX_RUN_ENV", 0);
+    assert_eq!(flags, 31);
+    env::set_var("TEST_RADIX_RUN_ENV", "0x-1");
+    assert_eq!(envtime_def!("TEST_RADIX_RUN_ENV", 7i32), 7i32);
+    env::set_var("TEST_RADIX_RUN_ENV", "+42");
+    assert_eq!(envtime_def!("TEST_RADIX_RUN_ENV", 0u32), 42u32);
+}
+
+#[test]
+fn separator_tests() {
+    // TEST_SEPARATOR_U64_COMP_ENV is set to "1_000_000" and TEST_SEPARATOR_F64_COMP_ENV to "1_000.5" at compile time
+    assert_eq!(envtime_def!("TEST_SEPARATOR_U64_COMP_ENV", 0u64), 1_000_000u64);
+    assert_eq!(envtime_def!("TEST_SEPARATOR_U64_COMP_ENV", 5_000u32, min = 1_000), 1_000_000u32);
+    assert_eq!(envtime_def!("TEST_SEPARATOR_F64_COMP_ENV", 0.0f64), 1_000.5f64);
+    const BAKED: usize = envtime_const!("TEST_SEPARATOR_U64_COMP_ENV", 0usize);
+    assert_eq!(BAKED, 1_000_000);
+
+    env::set_var("TEST_SEPARATOR_RUN_ENV", "1_000_000");
+    assert_eq!(envtime_def!("TEST_SEPARATOR_RUN_ENV", 0u64), 1_000_000u64);
+    assert_eq!(envtime_def!("TEST_SEPARATOR_RUN_ENV", 0u16), 0u16);
+    env::set_var("TEST_SEPARATOR_RUN_ENV", "-12_345");
+    assert_eq!(envtime_def!("TEST_SEPARATOR_RUN_ENV", 0i16), -12_345i16);
+    assert_eq!(envtime_def!("TEST_SEPARATOR_RUN_ENV", 0.0), -12_345.0);
+    env::set_var("TEST_SEPARATOR_RUN_ENV", "0xFF_FF");
+    assert_eq!(envtime_def!("TEST_SEPARATOR_RUN_ENV", 0u32), 65_535u32);
+    env::set_var("TEST_SEPARATOR_RUN_ENV", "2_5_5");
+    assert_eq!(envtime_def!("TEST_SEPARATOR_RUN_ENV", b'a'), 255u8);
+
+    // Leading and trailing separators are malformed
+    for value in ["_100", "100_", "_"] {
+        env::set_var("TEST_SEPARATOR_RUN_ENV", value);
+        assert_eq!(envtime_def!("TEST_SEPARATOR_RUN_ENV", 7u32), 7u32);
+        assert_eq!(envtime_def!("TEST_SEPARATOR_RUN_ENV", 7i64), 7i64);
+        assert_eq!(envtime_def!("TEST_SEPARATOR_RUN_ENV", 0.5), 0.5);
+    }
+}
+
+#[test]
+fn array_tests() {
+    // TEST_ARRAY_COMP_ENV is set to "255, 128, 0" at compile time
+    env::set_var("TEST_ARRAY_COMP_ENV", "1, 2, 3");
+    assert_eq!(envtime_array!("TEST_ARRAY_COMP_ENV", [0u8, 0u8, 0u8]), [255u8, 128, 0]);
+    assert_eq!(envtime_array!("TEST_ARRAY_COMP_ENV", [0i32, 0, 0]), [255i32, 128, 0]);
+
+    assert_eq!(envtime_array!("TEST_ARRAY_RUN_ENV", [0u8, 0u8, 0u8]), [0u8, 0, 0]);
+    env::set_var("TEST_ARRAY_RUN_ENV", "12, 0x20,1_0");
+    assert_eq!(envtime_array!("TEST_ARRAY_RUN_ENV", [0u8, 0u8, 0u8]), [12u8, 32, 10]);
+    assert_eq!(envtime_array!("TEST_ARRAY_RUN_ENV", [1u16, 2u16]), [1u16, 2]);
+    assert_eq!(envtime_array!("TEST_ARRAY_RUN_ENV", [1u8, 2u8, 3u8, 4u8]), [1u8, 2, 3, 4]);
+    env::set_var("TEST_ARRAY_RUN_ENV", "12, 300, 10");
+    assert_eq!(envtime_array!("TEST_ARRAY_RUN_ENV", [0u8, 0u8, 0u8]), [0u8, 0, 0]);
+    assert_eq!(envtime_array!("TEST_ARRAY_RUN_ENV", [0u16, 0u16, 0u16]), [12u16, 300, 10]);
+    env::set_var("TEST_ARRAY_RUN_ENV", "1,,2");
+    assert_eq!(envtime_array!("TEST_ARRAY_RUN_ENV", [0i8, 0, -1]), [0i8, 0, -1]);
+
+    env::set_var("TEST_ARRAY_RUN_ENV", "0.5, 1.5");
+    assert_eq!(envtime_array!("TEST_ARRAY_RUN_ENV", [0.0, 1.0]), [0.5, 1.5]);
+    env::set_var("TEST_ARRAY_RUN_ENV", "yes, off");
+    assert_eq!(envtime_array!("TEST_ARRAY_RUN_ENV", [false, true]), [true, false]);
+    env::set_var("TEST_ARRAY_RUN_ENV", "a, ;");
+    assert_eq!(envtime_array!("TEST_ARRAY_RUN_ENV", [',', ',']), ['a', ';']);
+}
+
+#[test]
+fn swapped_def_tests() {
+    assert_eq!(envtime_def!(50u8, "TEST_U8_COMP_ENV"), 12u8);
+    assert_eq!(envtime_def!(false, "TEST_BOOL_COMP_ENV"), true);
+
+    assert_eq!(envtime_def!(7u16, "TEST_SWAPPED_RUN_ENV"), 7u16);
+    env::set_var("TEST_SWAPPED_RUN_ENV", "9");
+    assert_eq!(envtime_def!(7u16, "TEST_SWAPPED_RUN_ENV"), 9u16);
+    // With two strings the first one is the name
+    assert_eq!(envtime_def!("TEST_SWAPPED_RUN_ENV", "TEST_NON_ENV"), "9");
+}
+
+// Compile time values are borrowed literals, usable in a const without any allocation
+const STATIC_STR: std::borrow::Cow<'static, str> = envtime_str!("TEST_COMP_ENV", "default");
+const STATIC_OPT_STR: Option<std::borrow::Cow<'static, str>> = envtime_str!("TEST_COMP_ENV");
+
+#[test]
+fn str_tests() {
+    use std::borrow::Cow;
+
+    assert!(matches!(STATIC_STR, Cow::Borrowed("456")));
+    assert!(matches!(STATIC_OPT_STR, Some(Cow::Borrowed("456"))));
+    assert!(matches!(envtime_str!("TEST_COMP_ENV"), Some(Cow::Borrowed("456"))));
+
+    assert!(matches!(envtime_str!("TEST_STR_OPT_RUN_ENV", "default"), Cow::Borrowed("default")));
+    assert_eq!(envtime_str!("TEST_STR_OPT_RUN_ENV"), None);
+    env::set_var("TEST_STR_OPT_RUN_ENV", "runtime");
+    assert!(matches!(envtime_str!("TEST_STR_OPT_RUN_ENV", "default"), Cow::Owned(s) if s == "runtime"));
+    assert!(matches!(envtime_str!("TEST_STR_OPT_RUN_ENV"), Some(Cow::Owned(s)) if s == "runtime"));
+    assert_eq!(envtime_str!("TEST_STR_OPT_RUN_ENV", require = upper), None);
+}
+
+#[test]
+fn cow_tests() {
+    use std::borrow::Cow;
+
+    assert!(matches!(envtime_cow!("TEST_COMP_ENV"), Some(Cow::Borrowed("456"))));
+    assert!(matches!(envtime_cow!("TEST_COW_RUN_ENV", "TEST_COMP_ENV"), Some(Cow::Borrowed("456"))));
+    assert!(matches!(envtime_cow!("TEST_FILE_SECRET_COMP_ENV"), Some(Cow::Borrowed("hunter2"))));
+
+    assert_eq!(envtime_cow!("TEST_COW_RUN_ENV"), None);
+    env::set_var("TEST_COW_RUN_ENV", "'runtime'");
+    assert!(matches!(envtime_cow!("TEST_COW_RUN_ENV"), Some(Cow::Owned(s)) if s == "'runtime'"));
+    assert!(matches!(envtime_cow!("TEST_COW_RUN_ENV", unquote = true), Some(Cow::Owned(s)) if s == "runtime"));
+}
+
+#[test]
+fn str_require_tests() {
+    assert_eq!(envtime_str!("TEST_STR_LOWER_COMP_ENV", "us-east-1", require = lower), "eu-west-1");
+    assert_eq!(envtime_str!("TEST_STR_UPPER_COMP_ENV", "US", require = upper), "EU-WEST-1");
+    assert_eq!(envtime_str!("TEST_STR_LOWER_COMP_ENV", "us-east-1"), "eu-west-1");
+
+    assert_eq!(envtime_str!("TEST_STR_REQUIRE_RUN_ENV", "us-east-1", require = lower), "us-east-1");
+    env::set_var("TEST_STR_REQUIRE_RUN_ENV", "US-EAST-1");
+    assert_eq!(envtime_str!("TEST_STR_REQUIRE_RUN_ENV", "us-east-1", require = lower), "us-east-1");
+    assert_eq!(envtime_str!("TEST_STR_REQUIRE_RUN_ENV", "US", require = upper), "US-EAST-1");
+    assert_eq!(envtime_str!("TEST_STR_REQUIRE_RUN_ENV", "us", require = ascii), "US-EAST-1");
+    env::set_var("TEST_STR_REQUIRE_RUN_ENV", "zürich");
+    assert_eq!(envtime_str!("TEST_STR_REQUIRE_RUN_ENV", "us", require = lower), "zürich");
+    assert_eq!(envtime_str!("TEST_STR_REQUIRE_RUN_ENV", "us", require = ascii), "us");
+}
+
+#[test]
+fn recognized_bool_tests() {
+    assert_eq!(envtime_bool!("TEST_BOOL_COMP_ENV"), Some(true));
+    assert_eq!(envtime_bool!("TEST_BOOL_TYPO_COMP_ENV"), None);
+
+    assert_eq!(envtime_bool!("TEST_RECOGNIZED_BOOL_RUN_ENV"), None);
+    env::set_var("TEST_RECOGNIZED_BOOL_RUN_ENV", "yes");
+    assert_eq!(envtime_bool!("TEST_RECOGNIZED_BOOL_RUN_ENV"), Some(true));
+    env::set_var("TEST_RECOGNIZED_BOOL_RUN_ENV", "false");
+    assert_eq!(envtime_bool!("TEST_RECOGNIZED_BOOL_RUN_ENV"), Some(false));
+    env::set_var("TEST_RECOGNIZED_BOOL_RUN_ENV", "N");
+    assert_eq!(envtime_bool!("TEST_RECOGNIZED_BOOL_RUN_ENV"), Some(false));
+    env::set_var("TEST_RECOGNIZED_BOOL_RUN_ENV", "ture");
+    assert_eq!(envtime_bool!("TEST_RECOGNIZED_BOOL_RUN_ENV"), None);
+    env::set_var("TEST_RECOGNIZED_BOOL_RUN_ENV", " On ");
+    assert_eq!(envtime_bool!("TEST_RECOGNIZED_BOOL_RUN_ENV"), Some(true));
+    assert_eq!(envtime_bool!("TEST_TRIM_BOOL_COMP_ENV"), Some(true));
+}
+
+#[test]
+fn bool_values_tests() {
+    assert_eq!(envtime_def!("TEST_BOOL_UPPER_COMP_ENV", false), true);
+    assert_eq!(envtime_bool!("TEST_BOOL_UPPER_COMP_ENV"), Some(true));
+
+    for val in ["TRUE", "On", "1"] {
+        env::set_var("TEST_BOOL_VALUES_RUN_ENV", val);
+        assert_eq!(envtime_def!("TEST_BOOL_VALUES_RUN_ENV", false), true, "{}", val);
+    }
+    for val in ["0", "OFF", "No"] {
+        env::set_var("TEST_BOOL_VALUES_RUN_ENV", val);
+        assert_eq!(envtime_def!("TEST_BOOL_VALUES_RUN_ENV", true), false, "{}", val);
+    }
+    // Unrecognized values fall back to the default
+    env::set_var("TEST_BOOL_VALUES_RUN_ENV", "disabled");
+    assert_eq!(envtime_def!("TEST_BOOL_VALUES_RUN_ENV", true), true);
+    assert_eq!(envtime_def!("TEST_BOOL_VALUES_RUN_ENV", false), false);
+    env::set_var("TEST_BOOL_VALUES_RUN_ENV", "OFF");
+    assert_eq!(envtime_bool!("TEST_BOOL_VALUES_RUN_ENV"), Some(false));
+    env::set_var("TEST_BOOL_VALUES_RUN_ENV", "disabled");
+    assert_eq!(envtime_bool!("TEST_BOOL_VALUES_RUN_ENV"), None);
+}
+
+#[test]
+fn invalid_value_tests() {
+    env::set_var("TEST_INVALID_RUN_ENV", "abc");
+    assert_eq!(envtime_def!("TEST_INVALID_RUN_ENV", 8080u16), 8080u16);
+    assert_eq!(envtime_def!("TEST_INVALID_RUN_ENV", 8080u16, max = 9000), 8080u16);
+    assert_eq!(envtime_def!("TEST_INVALID_RUN_ENV", true), true);
+    assert_eq!(envtime_def!("TEST_INVALID_RUN_ENV", false), false);
+}
+
+#[test]
+fn try_tests() {
+    assert_eq!(envtime_try!("TEST_U8_COMP_ENV", u8), Ok(12));
+    assert_eq!(envtime_try!("TEST_DEF_INVALID_COMP_ENV", u16), Err(ParseError::Invalid(String::from("abc"))));
+    assert_eq!(envtime_try!("TEST_PARSE_ADDR_COMP_ENV", std::net::SocketAddr), Ok(std::net::SocketAddr::from(([10, 0, 0, 1], 443))));
+
+    assert_eq!(envtime_try!("TEST_TRY_RUN_ENV", u16), Err(ParseError::NotSet));
+    env::set_var("TEST_TRY_RUN_ENV", " ");
+    assert_eq!(envtime_try!("TEST_TRY_RUN_ENV", u16), Err(ParseError::NotSet));
+    env::set_var("TEST_TRY_RUN_ENV", "8080");
+    assert_eq!(envtime_try!("TEST_TRY_RUN_ENV", u16), Ok(8080));
+    env::set_var("TEST_TRY_RUN_ENV", "70000");
+    let err = envtime_try!("TEST_TRY_RUN_ENV", u16).unwrap_err();
+    assert_eq!(err, ParseError::Invalid(String::from("70000")));
+    assert_eq!(format!("TEST_TRY_RUN_ENV is {}", err), "TEST_TRY_RUN_ENV is invalid value \"70000\"");
+    assert_eq!(format!("{}", ParseError::NotSet), "not set");
+}
+
+#[test]
+fn overflow_tests() {
+    for &value in &["300", "256", "0x100", "-1", "99999999999999999999999999999999999999999"] {
+        env::set_var("TEST_OVERFLOW_RUN_ENV", value);
+        assert_eq!(envtime_def!("TEST_OVERFLOW_RUN_ENV", 8u8), 8u8);
+    }
+    for &value in &["300", "128", "0x80", "-129", "-99999999999999999999999999999999999999999"] {
+        env::set_var("TEST_OVERFLOW_RUN_ENV", value);
+        assert_eq!(envtime_def!("TEST_OVERFLOW_RUN_ENV", -8i8), -8i8);
+    }
+    env::set_var("TEST_OVERFLOW_RUN_ENV", "127");
+    assert_eq!(envtime_def!("TEST_OVERFLOW_RUN_ENV", 8u8), 127u8);
+    assert_eq!(envtime_def!("TEST_OVERFLOW_RUN_ENV", -8i8), 127i8);
+    env::set_var("TEST_OVERFLOW_RUN_ENV", "-128");
+    assert_eq!(envtime_def!("TEST_OVERFLOW_RUN_ENV", 8u8), 8u8);
+    assert_eq!(envtime_def!("TEST_OVERFLOW_RUN_ENV", -8i8), -128i8);
+}
+
+#[derive(Debug, PartialEq)]
+enum State {
+    Idle = 0,
+    Running = 1,
+    Stopped = 2,
+}
+
+impl TryFrom<i64> for State {
+    type Error = ();
+
+    fn try_from(n: i64) -> Result<Self, ()> {
+        match n {
+            0 => Ok(State::Idle),
+            1 => Ok(State::Running),
+            2 => Ok(State::Stopped),
+            _ => Err(()),
+        }
+    }
+}
+
+#[test]
+fn enum_num_tests() {
+    env::set_var("TEST_ENUM_NUM_COMP_ENV", "0");
+    assert_eq!(envtime_enum_num!("TEST_ENUM_NUM_COMP_ENV", State, 0), State::Stopped);
+
+    assert_eq!(envtime_enum_num!("TEST_ENUM_NUM_RUN_ENV", State, 1), State::Running);
+    env::set_var("TEST_ENUM_NUM_RUN_ENV", "2");
+    assert_eq!(envtime_enum_num!("TEST_ENUM_NUM_RUN_ENV", State, 1), State::Stopped);
+    env::set_var("TEST_ENUM_NUM_RUN_ENV", "7");
+    assert_eq!(envtime_enum_num!("TEST_ENUM_NUM_RUN_ENV", State, 1), State::Running);
+    env::set_var("TEST_ENUM_NUM_RUN_ENV", "idle");
+    assert_eq!(envtime_enum_num!("TEST_ENUM_NUM_RUN_ENV", State, 1), State::Running);
+    env::set_var("TEST_ENUM_NUM_RUN_ENV", " 0 ");
+    assert_eq!(envtime_enum_num!("TEST_ENUM_NUM_RUN_ENV", State, 1), State::Idle);
+    // TEST_ENUM_NUM_TRIM_COMP_ENV is set to " 1 " at compile time
+    assert_eq!(envtime_enum_num!("TEST_ENUM_NUM_TRIM_COMP_ENV", State, 0), State::Running);
+}
+
+#[test]
+#[should_panic(expected = "Default discriminant of TEST_ENUM_NUM_UNMAPPED_RUN_ENV is not a valid variant")]
+fn enum_num_unmapped_default_tests() {
+    let _ = envtime_enum_num!("TEST_ENUM_NUM_UNMAPPED_RUN_ENV", State, 9);
+}
+
+#[test]
+fn file_exists_tests() {
+    assert_eq!(envtime_file_exists!("Cargo.toml"), true);
+    assert_eq!(envtime_file_exists!("does/not/exist"), false);
+
+    assert_eq!(envtime_file_exists_env!("TEST_FILE_EXISTS_RUN_ENV"), false);
+    env::set_var("TEST_FILE_EXISTS_RUN_ENV", "Cargo.toml");
+    assert_eq!(envtime_file_exists_env!("TEST_FILE_EXISTS_RUN_ENV"), true);
+    env::set_var("TEST_FILE_EXISTS_RUN_ENV", " Cargo.toml\n");
+    assert_eq!(envtime_file_exists_env!("TEST_FILE_EXISTS_RUN_ENV"), true);
+    env::set_var("TEST_FILE_EXISTS_RUN_ENV", "does/not/exist");
+    assert_eq!(envtime_file_exists_env!("TEST_FILE_EXISTS_RUN_ENV"), false);
+}
+
+#[test]
+fn build_time_tests() {
+    // SOURCE_DATE_EPOCH is set to 1700000000 at compile time
+    assert_eq!(envtime_build_time!(), "2023-11-14T22:13:20Z");
+}
+
+#[test]
+fn clamped_tests() {
+    assert_eq!(envtime_clamped!("TEST_CLAMPED_LOW_COMP_ENV", 8usize, 1..=64), 1usize);
+    assert_eq!(envtime_clamped!("TEST_CLAMPED_MID_COMP_ENV", 8usize, 1..=64), 16usize);
+    assert_eq!(envtime_clamped!("TEST_CLAMPED_HIGH_COMP_ENV", 8usize, 1..=64), 64usize);
+
+    assert_eq!(envtime_clamped!("TEST_CLAMPED_RUN_ENV", 8usize, 1..=64), 8usize);
+    env::set_var("TEST_CLAMPED_RUN_ENV", "0");
+    assert_eq!(envtime_clamped!("TEST_CLAMPED_RUN_ENV", 8usize, 1..=64), 1usize);
+    env::set_var("TEST_CLAMPED_RUN_ENV", "32");
+    assert_eq!(envtime_clamped!("TEST_CLAMPED_RUN_ENV", 8usize, 1..=64), 32usize);
+    env::set_var("TEST_CLAMPED_RUN_ENV", "65");
+    assert_eq!(envtime_clamped!("TEST_CLAMPED_RUN_ENV", 8usize, 1..=64), 64usize);
+    env::set_var("TEST_CLAMPED_RUN_ENV", "-20");
+    assert_eq!(envtime_clamped!("TEST_CLAMPED_RUN_ENV", 0i8, -10..10), -10i8);
+    // Below the range and outside of the type altogether is still clamped, not the default
+    env::set_var("TEST_CLAMPED_RUN_ENV", "-5");
+    assert_eq!(envtime_clamped!("TEST_CLAMPED_RUN_ENV", 8usize, 1..=64), 1usize);
+    env::set_var("TEST_CLAMPED_RUN_ENV", "1000000000000000000000000000000");
+    assert_eq!(envtime_clamped!("TEST_CLAMPED_RUN_ENV", 8usize, 1..=64), 64usize);
+    env::set_var("TEST_CLAMPED_RUN_ENV", " 0x20 ");
+    assert_eq!(envtime_clamped!("TEST_CLAMPED_RUN_ENV", 8usize, 1..=64), 32usize);
+    env::set_var("TEST_CLAMPED_RUN_ENV", "1_000");
+    assert_eq!(envtime_clamped!("TEST_CLAMPED_RUN_ENV", 0i8, -10..10), 9i8);
+    // TEST_CLAMPED_NEGATIVE_COMP_ENV, TEST_CLAMPED_OVERFLOW_COMP_ENV and TEST_CLAMPED_HEX_COMP_ENV
+    // are "-5", "1000000000000000000000000000000" and " 0x20 " at compile time
+    assert_eq!(envtime_clamped!("TEST_CLAMPED_NEGATIVE_COMP_ENV", 8usize, 1..=64), 1usize);
+    assert_eq!(envtime_clamped!("TEST_CLAMPED_OVERFLOW_COMP_ENV", 8usize, 1..=64), 64usize);
+    assert_eq!(envtime_clamped!("TEST_CLAMPED_HEX_COMP_ENV", 8usize, 1..=64), 32usize);
+
+    assert_eq!(envtime_clamped!("TEST_CLAMPED_DEF_ENV", 0usize, 1..=64), 1usize);
+    assert_eq!(envtime_clamped!("TEST_CLAMPED_DEF_ENV", 8usize, 1..=64), 8usize);
+    assert_eq!(envtime_clamped!("TEST_CLAMPED_DEF_ENV", 100usize, 1..=64), 64usize);
+    assert_eq!(envtime_clamped!("TEST_CLAMPED_DEF_ENV", 100u8, 1..), 100u8);
+}
+
+#[test]
+fn secret_tests() {
+    assert_eq!(envtime_secret!("TEST_SECRET_RUN_ENV"), None);
+    env::set_var("TEST_SECRET_RUN_ENV", "hunter2");
+    assert_eq!(envtime_secret!("TEST_SECRET_RUN_ENV"), Some(String::from("hunter2")));
+
+    // Set at compile time as well, but never baked in
+    env::set_var("TEST_SECRET_COMP_ENV", "sk-runtime");
+    assert_eq!(envtime_secret!("TEST_SECRET_COMP_ENV"), Some(String::from("sk-runtime")));
+}
+
+#[cfg(feature = "secrets")]
+#[test]
+fn secret_wrapper_tests() {
+    // TEST_SECRET_COMP_ENV is set at compile time, but secrets are only read at runtime
+    env::set_var("TEST_SECRET_COMP_ENV", "sk-runtime");
+    let secret = envtime_secret!("TEST_SECRET_COMP_ENV", "");
+    assert_eq!(format!("{:?}", secret), "[REDACTED]");
+    assert_eq!(secret.to_string(), "[REDACTED]");
+    assert_eq!(secret.expose(), "sk-runtime");
+
+    let secret = envtime_secret!("TEST_SECRET_WRAPPER_RUN_ENV", "fallback");
+    assert_eq!(format!("{:?}", Some(&secret)), "Some([REDACTED])");
+    assert_eq!(secret.into_inner(), "fallback");
+    env::set_var("TEST_SECRET_WRAPPER_RUN_ENV", "hunter2");
+    let secret: envtime::Secret<String> = envtime_secret!("TEST_SECRET_WRAPPER_RUN_ENV", "fallback");
+    assert_eq!(secret.expose(), "hunter2");
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn secret_format_tests() {
+    env::set_var("TEST_SECRET_FORMAT_COMP_ENV", "not validated at runtime");
+    let key = envtime_secret!("TEST_SECRET_FORMAT_COMP_ENV", format = "^sk-[a-z0-9]{32}$");
+    assert_eq!(key, Some(String::from("not validated at runtime")));
+
+    assert_eq!(envtime_secret!("TEST_SECRET_VALIDATE_RUN_ENV", format = "^sk-[a-z0-9]{4}$", validate = true), None);
+    env::set_var("TEST_SECRET_VALIDATE_RUN_ENV", "sk-1a2b");
+    let key = envtime_secret!("TEST_SECRET_VALIDATE_RUN_ENV", format = "^sk-[a-z0-9]{4}$", validate = true);
+    assert_eq!(key, Some(String::from("sk-1a2b")));
+}
+
+#[cfg(feature = "regex")]
+#[test]
+#[should_panic(expected = "env var TEST_SECRET_INVALID_RUN_ENV does not match the format \"^sk-[a-z0-9]{4}$\"")]
+fn secret_validate_panic_tests() {
+    env::set_var("TEST_SECRET_INVALID_RUN_ENV", "sk-TOO-LONG");
+    envtime_secret!("TEST_SECRET_INVALID_RUN_ENV", format = "^sk-[a-z0-9]{4}$", validate = true);
+}
+
+#[test]
+fn pairs_tests() {
+    let pairs = envtime_pairs!("TEST_PAIRS_COMP_ENV");
+    assert_eq!(pairs, vec![
+        (String::from("b"), String::from("2")),
+        (String::from("a"), String::from("1")),
+    ]);
+
+    assert_eq!(envtime_pairs!("TEST_PAIRS_RUN_ENV"), Vec::new());
+    env::set_var("TEST_PAIRS_RUN_ENV", "z = 26; y=25;;x=a=b; broken");
+    assert_eq!(envtime_pairs!("TEST_PAIRS_RUN_ENV"), vec![
+        (String::from("z"), String::from("26")),
+        (String::from("y"), String::from("25")),
+        (String::from("x"), String::from("a=b")),
+    ]);
+
+    env::set_var("TEST_PAIRS_STRICT_RUN_ENV", "a=1;b=2");
+    assert_eq!(envtime_pairs!("TEST_PAIRS_STRICT_RUN_ENV", malformed = error).len(), 2);
+    env::set_var("TEST_PAIRS_STRICT_RUN_ENV", "a=1;broken");
+    let result = std::panic::catch_unwind(|| envtime_pairs!("TEST_PAIRS_STRICT_RUN_ENV", malformed = error));
+    assert!(result.is_err());
+}
+
+#[test]
+fn map_tests() {
+    use std::collections::HashMap;
+
+    let map = |pairs: &[(&str, &str)]| pairs.iter()
+        .map(|&(key, val)| (String::from(key), String::from(val)))
+        .collect::<HashMap<_, _>>();
+    // TEST_MAP_COMP_ENV is set to "env=prod, team = core,,region=eu" at compile time
+    env::set_var("TEST_MAP_COMP_ENV", "env=dev");
+    assert_eq!(envtime_map!("TEST_MAP_COMP_ENV"), map(&[("env", "prod"), ("team", "core"), ("region", "eu")]));
+
+    assert_eq!(envtime_map!("TEST_MAP_RUN_ENV"), HashMap::new());
+    env::set_var("TEST_MAP_RUN_ENV", "a=1, b = 2, broken, c=x=y, =3, a=4, d=");
+    assert_eq!(envtime_map!("TEST_MAP_RUN_ENV"), map(&[("a", "4"), ("b", "2"), ("d", "")]));
+    env::set_var("TEST_MAP_RUN_ENV", "host: db; port: 5432; user");
+    assert_eq!(envtime_map!("TEST_MAP_RUN_ENV", delim = ';', pair_delim = ':'), map(&[("host", "db"), ("port", "5432")]));
+}
+
+#[test]
+fn group_tests() {
+    use std::collections::HashMap;
+
+    // TEST_GROUP_COMP_ENV_ALPHA and TEST_GROUP_COMP_ENV_BETA are set at compile time
+    env::set_var("TEST_GROUP_COMP_ENV_GAMMA", "ignored");
+    let group = envtime_group!("TEST_GROUP_COMP_ENV_");
+    let expected = [("ALPHA", "1"), ("BETA", "two")].iter()
+        .map(|&(key, val)| (String::from(key), String::from(val)))
+        .collect::<HashMap<_, _>>();
+    assert_eq!(group, expected);
+    let dotenv = envtime_group!("TEST_DOTENV_");
+    assert_eq!(dotenv.get("STR_ENV").map(String::as_str), Some("from-dotenv"));
+    assert_eq!(dotenv.get("OVERRIDE_ENV").map(String::as_str), Some("process"));
+
+    assert!(envtime_group!("TEST_GROUP_RUN_ENV_").is_empty());
+    env::set_var("TEST_GROUP_RUN_ENV_SEARCH", "on");
+    env::set_var("TEST_GROUP_RUN_ENV_EXPORT", "off");
+    env::set_var("TEST_GROUP_RUN_ENV_BLANK", " ");
+    env::set_var("TEST_GROUP_RUN_ENV_", "no key");
+    let group = envtime_group!("TEST_GROUP_RUN_ENV_");
+    let mut keys = group.keys().map(String::as_str).collect::<Vec<_>>();
+    keys.sort_unstable();
+    assert_eq!(keys, ["EXPORT", "SEARCH"]);
+    assert_eq!(group["SEARCH"], "on");
+    assert_eq!(group["EXPORT"], "off");
+}
+
+#[test]
+fn prefix_value_tests() {
+    let id: Option<&'static str> = envtime_with_prefix_value!("service-", "TEST_COMP_ENV");
+    assert_eq!(id, Some("service-456"));
+
+    assert_eq!(envtime_with_prefix_value!("service-", "TEST_PREFIX_VALUE_RUN_ENV"), None);
+    env::set_var("TEST_PREFIX_VALUE_RUN_ENV", "abc");
+    let id: Option<String> = envtime_with_prefix_value!("service-", "TEST_PREFIX_VALUE_RUN_ENV");
+    assert_eq!(id.as_deref(), Some("service-abc"));
+}
+
+#[test]
+fn char_tests() {
+    assert_eq!(envtime_char!("TEST_CHAR_COMP_ENV", ','), ';');
+    assert_eq!(envtime_char!("TEST_CHAR_HEX_COMP_ENV", ','), ';');
+    assert_eq!(envtime_char!("TEST_CHAR_NUM_COMP_ENV", ','), ';');
+    assert_eq!(envtime_char!("TEST_CHAR_ESCAPE_COMP_ENV", ','), '\u{2603}');
+    assert_eq!(envtime_def!("TEST_CHAR_COMP_ENV", ','), ';');
+    assert_eq!(envtime_def!("TEST_CHAR_NUM_COMP_ENV", ','), ';');
+    assert_eq!(envtime_def!("TEST_CHAR_ESCAPE_COMP_ENV", ','), '\u{2603}');
+
+    assert_eq!(envtime_char!("TEST_CHAR_RUN_ENV", ','), ',');
+    env::set_var("TEST_CHAR_RUN_ENV", "|");
+    assert_eq!(envtime_char!("TEST_CHAR_RUN_ENV", ','), '|');
+    env::set_var("TEST_CHAR_RUN_ENV", "5");
+    assert_eq!(envtime_char!("TEST_CHAR_RUN_ENV", ','), '5');
+    env::set_var("TEST_CHAR_RUN_ENV", "59");
+    assert_eq!(envtime_char!("TEST_CHAR_RUN_ENV", ','), ';');
+    env::set_var("TEST_CHAR_RUN_ENV", "0x2603");
+    assert_eq!(envtime_char!("TEST_CHAR_RUN_ENV", ','), '\u{2603}');
+    env::set_var("TEST_CHAR_RUN_ENV", "0xD800");
+    assert_eq!(envtime_char!("TEST_CHAR_RUN_ENV", ','), ',');
+    env::set_var("TEST_CHAR_RUN_ENV", "\\u{2603}");
+    assert_eq!(envtime_char!("TEST_CHAR_RUN_ENV", ','), '\u{2603}');
+    env::set_var("TEST_CHAR_RUN_ENV", "\\u{110000}");
+    assert_eq!(envtime_char!("TEST_CHAR_RUN_ENV", ','), ',');
+    env::set_var("TEST_CHAR_RUN_ENV", "ab");
+    assert_eq!(envtime_char!("TEST_CHAR_RUN_ENV", ','), ',');
+
+    assert_eq!(envtime_def!("TEST_CHAR_DEF_RUN_ENV", ','), ',');
+    env::set_var("TEST_CHAR_DEF_RUN_ENV", "|");
+    assert_eq!(envtime_def!("TEST_CHAR_DEF_RUN_ENV", ','), '|');
+    env::set_var("TEST_CHAR_DEF_RUN_ENV", "\\u{2603}");
+    assert_eq!(envtime_def!("TEST_CHAR_DEF_RUN_ENV", ','), '\u{2603}');
+    env::set_var("TEST_CHAR_DEF_RUN_ENV", "9731");
+    assert_eq!(envtime_def!("TEST_CHAR_DEF_RUN_ENV", ','), '\u{2603}');
+    env::set_var("TEST_CHAR_DEF_RUN_ENV", "0xD800");
+    assert_eq!(envtime_def!("TEST_CHAR_DEF_RUN_ENV", ','), ',');
+}
+
+#[test]
+fn bytes_b64_tests() {
+    assert_eq!(envtime_bytes_b64!("TEST_B64_COMP_ENV").as_deref(), Some(&b"hello world"[..]));
+    const KEY: Option<std::borrow::Cow<'static, [u8]>> = envtime_bytes_b64!("TEST_B64_COMP_ENV");
+    assert_eq!(KEY.as_deref(), Some(&b"hello world"[..]));
+
+    assert_eq!(envtime_bytes_b64!("TEST_B64_RUN_ENV"), None);
+    env::set_var("TEST_B64_RUN_ENV", "aGVsbG8=");
+    assert_eq!(envtime_bytes_b64!("TEST_B64_RUN_ENV").as_deref(), Some(&b"hello"[..]));
+    env::set_var("TEST_B64_RUN_ENV", " aGVsbG8 ");
+    assert_eq!(envtime_bytes_b64!("TEST_B64_RUN_ENV").as_deref(), Some(&b"hello"[..]));
+    env::set_var("TEST_B64_RUN_ENV", "+/8=");
+    assert_eq!(envtime_bytes_b64!("TEST_B64_RUN_ENV").as_deref(), Some(&[0xfb, 0xff][..]));
+    env::set_var("TEST_B64_RUN_ENV", "aGVsbG8");
+    assert_eq!(envtime_bytes_b64!("TEST_B64_RUN_ENV").as_deref(), Some(&b"hello"[..]));
+    env::set_var("TEST_B64_RUN_ENV", "aGVsbG8h=");
+    assert_eq!(envtime_bytes_b64!("TEST_B64_RUN_ENV"), None);
+    env::set_var("TEST_B64_RUN_ENV", "aGVsb");
+    assert_eq!(envtime_bytes_b64!("TEST_B64_RUN_ENV"), None);
+    env::set_var("TEST_B64_RUN_ENV", "not base64!");
+    assert_eq!(envtime_bytes_b64!("TEST_B64_RUN_ENV"), None);
+}
+
+#[test]
+fn bytes_hex_tests() {
+    assert_eq!(envtime_bytes_hex!("TEST_HEX_BYTES_COMP_ENV").as_deref(), Some(&[0x00, 0x11, 0x22, 0xaa, 0xbb, 0xcc][..]));
+    assert_eq!(envtime_bytes_hex!("TEST_HEX_BYTES_PREFIX_COMP_ENV").as_deref(), Some(&[0xde, 0xad, 0xbe, 0xef][..]));
+
+    assert_eq!(envtime_bytes_hex!("TEST_HEX_BYTES_RUN_ENV"), None);
+    env::set_var("TEST_HEX_BYTES_RUN_ENV", "001122aabbcc");
+    assert_eq!(envtime_bytes_hex!("TEST_HEX_BYTES_RUN_ENV").as_deref(), Some(&[0x00, 0x11, 0x22, 0xaa, 0xbb, 0xcc][..]));
+    env::set_var("TEST_HEX_BYTES_RUN_ENV", " 0xCAFE ");
+    assert_eq!(envtime_bytes_hex!("TEST_HEX_BYTES_RUN_ENV").as_deref(), Some(&[0xca, 0xfe][..]));
+    env::set_var("TEST_HEX_BYTES_RUN_ENV", "abc");
+    assert_eq!(envtime_bytes_hex!("TEST_HEX_BYTES_RUN_ENV"), None);
+    env::set_var("TEST_HEX_BYTES_RUN_ENV", "zz");
+    assert_eq!(envtime_bytes_hex!("TEST_HEX_BYTES_RUN_ENV"), None);
+}
+
+#[test]
+fn addr_tests() {
+    use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+    assert_eq!(envtime_ip!("TEST_IP_V4_COMP_ENV", "127.0.0.1"), IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)));
+    assert_eq!(envtime_ip!("TEST_IP_V6_COMP_ENV", "127.0.0.1"), "fe80::1".parse::<IpAddr>().unwrap());
+    assert_eq!(envtime_socket!("TEST_PARSE_ADDR_COMP_ENV", "0.0.0.0:8080"), SocketAddr::from(([10, 0, 0, 1], 443)));
+
+    assert_eq!(envtime_ip!("TEST_IP_RUN_ENV", "127.0.0.1"), IpAddr::V4(Ipv4Addr::LOCALHOST));
+    assert_eq!(envtime_ip!("TEST_IP_RUN_ENV", "::1"), IpAddr::V6(Ipv6Addr::LOCALHOST));
+    env::set_var("TEST_IP_RUN_ENV", "192.168.1.20");
+    assert_eq!(envtime_ip!("TEST_IP_RUN_ENV", "::1"), IpAddr::V4(Ipv4Addr::new(192, 168, 1, 20)));
+    env::set_var("TEST_IP_RUN_ENV", " ::ffff ");
+    assert_eq!(envtime_ip!("TEST_IP_RUN_ENV", "127.0.0.1"), IpAddr::V6(Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 0xffff)));
+    env::set_var("TEST_IP_RUN_ENV", "localhost");
+    assert_eq!(envtime_ip!("TEST_IP_RUN_ENV", "127.0.0.1"), IpAddr::V4(Ipv4Addr::LOCALHOST));
+
+    assert_eq!(envtime_socket!("TEST_SOCKET_RUN_ENV", "0.0.0.0:8080"), SocketAddr::from(([0, 0, 0, 0], 8080)));
+    env::set_var("TEST_SOCKET_RUN_ENV", "[::1]:443");
+    assert_eq!(envtime_socket!("TEST_SOCKET_RUN_ENV", "0.0.0.0:8080"), SocketAddr::from((Ipv6Addr::LOCALHOST, 443)));
+    env::set_var("TEST_SOCKET_RUN_ENV", "::1:443");
+    assert_eq!(envtime_socket!("TEST_SOCKET_RUN_ENV", "0.0.0.0:8080"), SocketAddr::from(([0, 0, 0, 0], 8080)));
+}
+
+#[test]
+fn file_tests() {
+    assert_eq!(envtime_file!("TEST_FILE_COMP_ENV"), Some(String::from("hunter2")));
+    assert_eq!(envtime_file!("TEST_FILE_BLANK_COMP_ENV"), None);
+
+    assert_eq!(envtime_file!("TEST_FILE_RUN_ENV"), None);
+    env::set_var("TEST_FILE_RUN_ENV", concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/db_password.txt"));
+    assert_eq!(envtime_file!("TEST_FILE_RUN_ENV"), Some(String::from("hunter2")));
+    env::set_var("TEST_FILE_RUN_ENV", concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/blank.txt"));
+    assert_eq!(envtime_file!("TEST_FILE_RUN_ENV"), None);
+    env::set_var("TEST_FILE_RUN_ENV", concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/missing.txt"));
+    assert_eq!(envtime_file!("TEST_FILE_RUN_ENV"), None);
+}
+
+#[test]
+fn file_utf8_tests() {
+    // TEST_FILE_INVALID_UTF8_COMP_ENV points at a Latin-1 encoded file at compile time
+    assert_eq!(envtime_file!("TEST_FILE_INVALID_UTF8_COMP_ENV", utf8 = "lossy"), Some(String::from("caf\u{FFFD} latte")));
+
+    let fixture = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/invalid_utf8.txt");
+    env::set_var("TEST_FILE_UTF8_RUN_ENV", fixture);
+    assert_eq!(envtime_file!("TEST_FILE_UTF8_RUN_ENV"), None);
+    assert_eq!(envtime_file!("TEST_FILE_UTF8_RUN_ENV", utf8 = "lossy"), Some(String::from("caf\u{FFFD} latte")));
+    env::set_var("TEST_FILE_UTF8_RUN_ENV", concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/db_password.txt"));
+    assert_eq!(envtime_file!("TEST_FILE_UTF8_RUN_ENV", utf8 = "strict"), Some(String::from("hunter2")));
+}
+
+#[test]
+#[should_panic(expected = "file from env var TEST_FILE_UTF8_STRICT_RUN_ENV isn't valid UTF-8")]
+fn file_utf8_strict_tests() {
+    env::set_var("TEST_FILE_UTF8_STRICT_RUN_ENV", concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/invalid_utf8.txt"));
+    let _ = envtime_file!("TEST_FILE_UTF8_STRICT_RUN_ENV", utf8 = "strict");
+}
+
+#[test]
+fn file_fallback_tests() {
+    // Only TEST_FILE_SECRET_COMP_ENV_FILE is set at compile time
+    assert_eq!(envtime!("TEST_FILE_SECRET_COMP_ENV"), Some(String::from("hunter2")));
+    assert_eq!(envtime!("TEST_FILE_SECRET_COMP_ENV", file_suffix = ""), None);
+
+    let fixture = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/db_password.txt");
+    assert_eq!(envtime!("TEST_FILE_SECRET_RUN_ENV"), None);
+    env::set_var("TEST_FILE_SECRET_RUN_ENV_FILE", fixture);
+    assert_eq!(envtime!("TEST_FILE_SECRET_RUN_ENV"), Some(String::from("hunter2")));
+    assert_eq!(envtime!("TEST_FILE_SECRET_RUN_ENV", file_suffix = "_PATH"), None);
+    assert_eq!(envtime!("TEST_FILE_SECRET_LEGACY_RUN_ENV", "TEST_FILE_SECRET_RUN_ENV"), Some(String::from("hunter2")));
+    env::set_var("TEST_FILE_SECRET_RUN_ENV", "from-env");
+    assert_eq!(envtime!("TEST_FILE_SECRET_RUN_ENV"), Some(String::from("from-env")));
+
+    assert_eq!(envtime!("TEST_FILE_SECRET_PATH_RUN_ENV", file_suffix = "_PATH"), None);
+    env::set_var("TEST_FILE_SECRET_PATH_RUN_ENV_PATH", fixture);
+    assert_eq!(envtime!("TEST_FILE_SECRET_PATH_RUN_ENV", file_suffix = "_PATH"), Some(String::from("hunter2")));
+    assert_eq!(envtime!("TEST_FILE_SECRET_PATH_RUN_ENV"), None);
+
+    // TEST_FILE_UNREADABLE_COMP_ENV_FILE points at a file that doesn't exist, which warns and is left for runtime
+    assert_eq!(envtime!("TEST_FILE_UNREADABLE_COMP_ENV"), None);
+    env::set_var("TEST_FILE_UNREADABLE_COMP_ENV_FILE", fixture);
+    assert_eq!(envtime!("TEST_FILE_UNREADABLE_COMP_ENV"), Some(String::from("hunter2")));
+}
+
+#[derive(EnvtimeEnum, Debug, PartialEq)]
+enum Mode {
+    Development,
+    Staging,
+    Production,
+}
+
+#[test]
+fn enum_tests() {
+    assert_eq!(envtime_enum!("TEST_ENUM_COMP_ENV", Mode, "development"), Mode::Production);
+
+    assert_eq!(envtime_enum!("TEST_ENUM_RUN_ENV", Mode, "development"), Mode::Development);
+    env::set_var("TEST_ENUM_RUN_ENV", "staging");
+    assert_eq!(envtime_enum!("TEST_ENUM_RUN_ENV", Mode, "development"), Mode::Staging);
+    env::set_var("TEST_ENUM_RUN_ENV", " Production ");
+    assert_eq!(envtime_enum!("TEST_ENUM_RUN_ENV", Mode, "Development"), Mode::Production);
+    env::set_var("TEST_ENUM_RUN_ENV", "prod");
+    assert_eq!(envtime_enum!("TEST_ENUM_RUN_ENV", Mode, "STAGING"), Mode::Staging);
+    assert_eq!(Mode::envtime_variant("productio"), None);
+}
+
+#[test]
+fn vec_tests() {
+    assert_eq!(envtime_vec!("TEST_VEC_COMP_ENV", String), vec!["b", "a", "b"]);
+    assert_eq!(envtime_vec!("TEST_VEC_INT_COMP_ENV", u16, delim = ';'), vec![8080, 443]);
+    assert_eq!(envtime_vec!("TEST_OPT_LEVEL_COMP_ENV", Level), vec![Level::Warn]);
+
+    assert!(envtime_vec!("TEST_VEC_RUN_ENV", String).is_empty());
+    env::set_var("TEST_VEC_RUN_ENV", "");
+    assert!(envtime_vec!("TEST_VEC_RUN_ENV", String).is_empty());
+    env::set_var("TEST_VEC_RUN_ENV", " x, y,,x ");
+    assert_eq!(envtime_vec!("TEST_VEC_RUN_ENV", String), vec!["x", "y", "x"]);
+
+    env::set_var("TEST_VEC_INT_RUN_ENV", "1;2; abc;70000;2");
+    assert_eq!(envtime_vec!("TEST_VEC_INT_RUN_ENV", u16, delim = ';'), vec![1, 2, 2]);
+    assert_eq!(envtime_vec!("TEST_VEC_INT_RUN_ENV", u16), Vec::<u16>::new());
+}
+
+#[test]
+fn path_tests() {
+    use std::path::PathBuf;
+
+    assert_eq!(envtime_path!("TEST_PATH_COMP_ENV", "./assets", check_exists), PathBuf::from("src/lib.rs"));
+    assert_eq!(envtime_path!("TEST_PATH_MISSING_COMP_ENV", "./assets"), PathBuf::from("does/not/exist"));
+
+    assert_eq!(envtime_path!("TEST_PATH_RUN_ENV", "./assets"), PathBuf::from("./assets"));
+    env::set_var("TEST_PATH_RUN_ENV", "/srv/assets");
+    assert_eq!(envtime_path!("TEST_PATH_RUN_ENV", "./assets", check_exists), PathBuf::from("/srv/assets"));
+}
+
+#[test]
+fn set_tests() {
+    let features = envtime_set!("TEST_SET_COMP_ENV", String);
+    assert_eq!(features.len(), 2);
+    assert!(features.contains("b") && features.contains("a"));
+    let ports = envtime_set!("TEST_SET_INT_COMP_ENV", u16);
+    assert_eq!(ports, [80u16, 443u16].iter().copied().collect());
+
+    assert!(envtime_set!("TEST_SET_RUN_ENV", String).is_empty());
+    env::set_var("TEST_SET_RUN_ENV", "x, y,,x ");
+    let features = envtime_set!("TEST_SET_RUN_ENV", String);
+    assert_eq!(features.len(), 2);
+    assert!(features.contains("x") && features.contains("y"));
+
+    env::set_var("TEST_SET_INT_RUN_ENV", "1,2,abc,70000,2");
+    assert_eq!(envtime_set!("TEST_SET_INT_RUN_ENV", u16), [1u16, 2u16].iter().copied().collect());
+}
+
+#[derive(Debug, PartialEq)]
+enum Level {
+    Info,
+    Warn,
+}
+
+impl std::str::FromStr for Level {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, ()> {
+        match s {
+            "info" => Ok(Level::Info),
+            "warn" => Ok(Level::Warn),
+            _ => Err(()),
+        }
+    }
+}
+
+#[test]
+fn opt_tests() {
+    env::set_var("TEST_OPT_COMP_ENV", "1");
+    assert_eq!(envtime_opt!("TEST_OPT_COMP_ENV", u16), Some(4242));
+    assert_eq!(envtime_opt!("TEST_OPT_LEVEL_COMP_ENV", Level), Some(Level::Warn));
+
+    assert_eq!(envtime_opt!("TEST_OPT_RUN_ENV", u16), None);
+    env::set_var("TEST_OPT_RUN_ENV", "8080");
+    assert_eq!(envtime_opt!("TEST_OPT_RUN_ENV", u16), Some(8080));
+    env::set_var("TEST_OPT_RUN_ENV", "70000");
+    assert_eq!(envtime_opt!("TEST_OPT_RUN_ENV", u16), None);
+
+    assert_eq!(envtime_opt!("TEST_OPT_LEVEL_RUN_ENV", Level), None);
+    env::set_var("TEST_OPT_LEVEL_RUN_ENV", "info");
+    assert_eq!(envtime_opt!("TEST_OPT_LEVEL_RUN_ENV", Level), Some(Level::Info));
+    env::set_var("TEST_OPT_LEVEL_RUN_ENV", "debug");
+    assert_eq!(envtime_opt!("TEST_OPT_LEVEL_RUN_ENV", Level), None);
+}
+
+#[test]
+fn opt_try_primitive_tests() {
+    // TEST_OPT_HEX_COMP_ENV is "0x10" and TEST_OPT_BOOL_COMP_ENV is "yes" at compile time,
+    // parsed like `envtime_def!` literals
+    assert_eq!(envtime_opt!("TEST_OPT_HEX_COMP_ENV", u16), Some(16));
+    assert_eq!(envtime_try!("TEST_OPT_HEX_COMP_ENV", u16), Ok(16));
+    assert_eq!(envtime_opt!("TEST_OPT_BOOL_COMP_ENV", bool), Some(true));
+    assert_eq!(envtime_try!("TEST_OPT_BOOL_COMP_ENV", bool), Ok(true));
+    assert_eq!(envtime_parse!("TEST_OPT_HEX_COMP_ENV", u16), Some(16));
+    assert_eq!(envtime_vec!("TEST_OPT_HEX_COMP_ENV", u16), vec![16]);
+
+    // A baked value that doesn't parse stays invalid, the runtime value is never looked at
+    env::set_var("TEST_DEF_INVALID_COMP_ENV", "8080");
+    assert_eq!(envtime_opt!("TEST_DEF_INVALID_COMP_ENV", u16), None);
+    assert_eq!(envtime_try!("TEST_DEF_INVALID_COMP_ENV", u16), Err(ParseError::Invalid(String::from("abc"))));
+
+    env::set_var("TEST_OPT_PRIMITIVE_RUN_ENV", " 0x10 ");
+    assert_eq!(envtime_opt!("TEST_OPT_PRIMITIVE_RUN_ENV", u16), Some(16));
+    assert_eq!(envtime_try!("TEST_OPT_PRIMITIVE_RUN_ENV", u16), Ok(16));
+    env::set_var("TEST_OPT_PRIMITIVE_RUN_ENV", "Off");
+    assert_eq!(envtime_opt!("TEST_OPT_PRIMITIVE_RUN_ENV", bool), Some(false));
+    assert_eq!(envtime_try!("TEST_OPT_PRIMITIVE_RUN_ENV", bool), Ok(false));
+    assert_eq!(envtime_try!("TEST_OPT_PRIMITIVE_RUN_ENV", u16), Err(ParseError::Invalid(String::from("Off"))));
+}
+
+#[test]
+fn parse_tests() {
+    use std::net::SocketAddr;
+    use std::path::PathBuf;
+
+    env::set_var("TEST_PARSE_ADDR_COMP_ENV", "127.0.0.1:1");
+    let addr = envtime_parse!("TEST_PARSE_ADDR_COMP_ENV", SocketAddr);
+    assert_eq!(addr, Some(SocketAddr::from(([10, 0, 0, 1], 443))));
+    let addr = envtime_parse!("TEST_PARSE_ADDR_COMP_ENV", SocketAddr, SocketAddr::from(([127, 0, 0, 1], 80)));
+    assert_eq!(addr, SocketAddr::from(([10, 0, 0, 1], 443)));
+    assert_eq!(envtime_parse!("TEST_PARSE_PATH_COMP_ENV", PathBuf), Some(PathBuf::from("/etc/app.toml")));
+    assert_eq!(envtime_parse!("TEST_U8_COMP_ENV", u8, 0), 12u8);
+
+    assert_eq!(envtime_parse!("TEST_PARSE_ADDR_RUN_ENV", SocketAddr), None);
+    let default = SocketAddr::from(([127, 0, 0, 1], 80));
+    assert_eq!(envtime_parse!("TEST_PARSE_ADDR_RUN_ENV", SocketAddr, default), default);
+    env::set_var("TEST_PARSE_ADDR_RUN_ENV", "[::1]:8080");
+    assert_eq!(envtime_parse!("TEST_PARSE_ADDR_RUN_ENV", SocketAddr, default).port(), 8080);
+    env::set_var("TEST_PARSE_ADDR_RUN_ENV", "localhost");
+    assert_eq!(envtime_parse!("TEST_PARSE_ADDR_RUN_ENV", SocketAddr, default), default);
+
+    assert_eq!(envtime_parse!("TEST_PARSE_PATH_RUN_ENV", PathBuf, PathBuf::from("app.toml")), PathBuf::from("app.toml"));
+    env::set_var("TEST_PARSE_PATH_RUN_ENV", "/tmp/app.toml");
+    assert_eq!(envtime_parse!("TEST_PARSE_PATH_RUN_ENV", PathBuf), Some(PathBuf::from("/tmp/app.toml")));
+}
+
+#[test]
+fn duration_tests() {
+    assert_eq!(envtime_duration!("TEST_OPT_DURATION_COMP_ENV", "5s"), Duration::from_secs(90));
+
+    assert_eq!(envtime_duration!("TEST_DURATION_RUN_ENV", "5m"), Duration::from_secs(300));
+    env::set_var("TEST_DURATION_RUN_ENV", "750ms");
+    assert_eq!(envtime_duration!("TEST_DURATION_RUN_ENV", "5m"), Duration::from_millis(750));
+    env::set_var("TEST_DURATION_RUN_ENV", "3m");
+    assert_eq!(envtime_duration!("TEST_DURATION_RUN_ENV", "5m"), Duration::from_secs(180));
+    env::set_var("TEST_DURATION_RUN_ENV", "1h");
+    assert_eq!(envtime_duration!("TEST_DURATION_RUN_ENV", "5m"), Duration::from_secs(3600));
+    env::set_var("TEST_DURATION_RUN_ENV", "12");
+    assert_eq!(envtime_duration!("TEST_DURATION_RUN_ENV", "5m"), Duration::from_secs(12));
+    env::set_var("TEST_DURATION_RUN_ENV", "12 parsecs");
+    assert_eq!(envtime_duration!("TEST_DURATION_RUN_ENV", "5m"), Duration::from_secs(300));
+}
+
+#[test]
+fn opt_duration_tests() {
+    assert_eq!(envtime_opt_duration!("TEST_OPT_DURATION_COMP_ENV"), Some(Duration::from_secs(90)));
+
+    assert_eq!(envtime_opt_duration!("TEST_OPT_DURATION_RUN_ENV"), None);
+    env::set_var("TEST_OPT_DURATION_RUN_ENV", "250ms");
+    assert_eq!(envtime_opt_duration!("TEST_OPT_DURATION_RUN_ENV"), Some(Duration::from_millis(250)));
+    env::set_var("TEST_OPT_DURATION_RUN_ENV", "2h");
+    assert_eq!(envtime_opt_duration!("TEST_OPT_DURATION_RUN_ENV"), Some(Duration::from_secs(7200)));
+    env::set_var("TEST_OPT_DURATION_RUN_ENV", "45");
+    assert_eq!(envtime_opt_duration!("TEST_OPT_DURATION_RUN_ENV"), Some(Duration::from_secs(45)));
+    env::set_var("TEST_OPT_DURATION_RUN_ENV", "soon");
+    assert_eq!(envtime_opt_duration!("TEST_OPT_DURATION_RUN_ENV"), None);
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn json_path_tests() {
+    assert_eq!(envtime_json_path!("TEST_JSON_PATH_COMP_ENV", "/server/port", 8080u16), 9000u16);
+    assert_eq!(envtime_json_path!("TEST_JSON_PATH_COMP_ENV", "/server/host", "localhost"), "example.com");
+    assert_eq!(envtime_json_path!("TEST_JSON_PATH_COMP_ENV", "/server/tls", false), true);
+    assert_eq!(envtime_json_path!("TEST_JSON_PATH_COMP_ENV", "/server/missing", 1u8), 1u8);
+    assert_eq!(envtime_json_path!("TEST_JSON_PATH_COMP_ENV", "/server/host", 1u8), 1u8);
+
+    assert_eq!(envtime_json_path!("TEST_JSON_PATH_RUN_ENV", "/server/port", 8080u16), 8080u16);
+    env::set_var("TEST_JSON_PATH_RUN_ENV", r#"{"server": {"port": 9001, "ratio": 0.5, "name": "api"}}"#);
+    assert_eq!(envtime_json_path!("TEST_JSON_PATH_RUN_ENV", "/server/port", 8080u16), 9001u16);
+    assert_eq!(envtime_json_path!("TEST_JSON_PATH_RUN_ENV", "/server/ratio", 1.0f64), 0.5f64);
+    assert_eq!(envtime_json_path!("TEST_JSON_PATH_RUN_ENV", "/server/name", "none"), "api");
+    assert_eq!(envtime_json_path!("TEST_JSON_PATH_RUN_ENV", "/server/name", 3u8), 3u8);
+    assert_eq!(envtime_json_path!("TEST_JSON_PATH_RUN_ENV", "/nope", 3u8), 3u8);
+    env::set_var("TEST_JSON_PATH_RUN_ENV", "{not json");
+    assert_eq!(envtime_json_path!("TEST_JSON_PATH_RUN_ENV", "/server/port", 8080u16), 8080u16);
+}
+
+#[test]
+fn bool_numeric_tests() {
+    // TEST_BOOL_ONE_COMP_ENV, TEST_BOOL_ZERO_COMP_ENV and TEST_BOOL_TWO_COMP_ENV are "1", "0" and "2" at compile time
+    assert_eq!(envtime_def!("TEST_BOOL_ONE_COMP_ENV", false), true);
+    assert_eq!(envtime_def!("TEST_BOOL_ZERO_COMP_ENV", true), false);
+    assert_eq!(envtime_bool!("TEST_BOOL_ONE_COMP_ENV"), Some(true));
+    assert_eq!(envtime_bool!("TEST_BOOL_ZERO_COMP_ENV"), Some(false));
+    // Any other number isn't a bool and falls back to the default
+    assert_eq!(envtime_def!("TEST_BOOL_TWO_COMP_ENV", false), false);
+    assert_eq!(envtime_def!("TEST_BOOL_TWO_COMP_ENV", true), true);
+    assert_eq!(envtime_bool!("TEST_BOOL_TWO_COMP_ENV"), None);
+    // The same values are still numbers for an integer default
+    assert_eq!(envtime_def!("TEST_BOOL_ZERO_COMP_ENV", 5u32), 0u32);
+    assert_eq!(envtime_def!("TEST_BOOL_TWO_COMP_ENV", 5u32), 2u32);
+
+    env::set_var("TEST_BOOL_NUMERIC_RUN_ENV", "1");
+    assert_eq!(envtime_def!("TEST_BOOL_NUMERIC_RUN_ENV", false), true);
+    assert_eq!(envtime_bool!("TEST_BOOL_NUMERIC_RUN_ENV"), Some(true));
+    env::set_var("TEST_BOOL_NUMERIC_RUN_ENV", " 0 ");
+    assert_eq!(envtime_def!("TEST_BOOL_NUMERIC_RUN_ENV", true), false);
+    assert_eq!(envtime_bool!("TEST_BOOL_NUMERIC_RUN_ENV"), Some(false));
+    assert_eq!(envtime_def!("TEST_BOOL_NUMERIC_RUN_ENV", 5u32), 0u32);
+    for val in ["2", "-1", "01", "1.0"] {
+        env::set_var("TEST_BOOL_NUMERIC_RUN_ENV", val);
+        assert_eq!(envtime_def!("TEST_BOOL_NUMERIC_RUN_ENV", true), true, "{}", val);
+        assert_eq!(envtime_def!("TEST_BOOL_NUMERIC_RUN_ENV", false), false, "{}", val);
+        assert_eq!(envtime_bool!("TEST_BOOL_NUMERIC_RUN_ENV"), None, "{}", val);
+    }
+}
+
+#[test]
+fn truthy_tests() {
+    use envtime::runtime::{is_falsy, is_truthy};
+
+    for value in ["1", "true", "yes", "y", "on", "TRUE", "Yes", "Y", "On", " on\n"] {
+        assert!(is_truthy(value), "{:?}", value);
+        assert!(!is_falsy(value), "{:?}", value);
+    }
+    for value in ["0", "false", "no", "n", "off", "FALSE", "No", " OFF "] {
+        assert!(is_falsy(value), "{:?}", value);
+        assert!(!is_truthy(value), "{:?}", value);
+    }
+    for value in ["", " ", "2", "ture", "enabled", "yes please", "t", "f"] {
+        assert!(!is_truthy(value) && !is_falsy(value), "{:?}", value);
+    }
+}
+
+#[test]
+fn runtime_helper_tests() {
+    assert_eq!(envtime::get_string("TEST_HELPER_RUN_ENV"), envtime!("TEST_HELPER_RUN_ENV"));
+    assert_eq!(envtime::get_parsed("TEST_HELPER_RUN_ENV", 7u16), envtime_def!("TEST_HELPER_RUN_ENV", 7u16));
+    for value in ["", "  ", " 42 ", "0x2A", "-0b101", "+7", "70000", "abc", "1_000", "_1", "1_"] {
+        env::set_var("TEST_HELPER_RUN_ENV", value);
+        assert_eq!(envtime::get_string("TEST_HELPER_RUN_ENV"), envtime!("TEST_HELPER_RUN_ENV"));
+        assert_eq!(envtime::get_parsed("TEST_HELPER_RUN_ENV", 7u16), envtime_def!("TEST_HELPER_RUN_ENV", 7u16));
+        assert_eq!(envtime::get_parsed("TEST_HELPER_RUN_ENV", 7i64), envtime_def!("TEST_HELPER_RUN_ENV", 7i64));
+        assert_eq!(envtime::get_parsed("TEST_HELPER_RUN_ENV", 0.5f64), envtime_def!("TEST_HELPER_RUN_ENV", 0.5f64));
+    }
+    for value in ["1", "true", " Yes ", "y", "ON", "0", "False", "no", "n", "off", "maybe"] {
+        env::set_var("TEST_HELPER_RUN_ENV", value);
+        assert_eq!(envtime::get_parsed("TEST_HELPER_RUN_ENV", false), envtime_def!("TEST_HELPER_RUN_ENV", false));
+        assert_eq!(envtime::get_parsed("TEST_HELPER_RUN_ENV", true), envtime_def!("TEST_HELPER_RUN_ENV", true));
+    }
+    for value in ["x", "\\u{2603}", "65", "0x41", "ab"] {
+        env::set_var("TEST_HELPER_RUN_ENV", value);
+        assert_eq!(envtime::get_parsed("TEST_HELPER_RUN_ENV", '-'), envtime_def!("TEST_HELPER_RUN_ENV", '-'));
+    }
+
+    // Names built at runtime, which the macros can't take
+    let service = "HELPER";
+    env::set_var("TEST_HELPER_PORT_RUN_ENV", "8080");
+    assert_eq!(envtime::get_parsed(&format!("TEST_{}_PORT_RUN_ENV", service), 80u16), 8080);
+    assert_eq!(envtime::get_parsed(&format!("TEST_{}_PORT_RUN_ENV", service), String::new()), "8080");
+
+    let fixture = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/db_password.txt");
+    env::set_var("TEST_HELPER_SECRET_RUN_ENV_FILE", fixture);
+    assert_eq!(envtime::get_string("TEST_HELPER_SECRET_RUN_ENV"), envtime!("TEST_HELPER_SECRET_RUN_ENV"));
+    assert_eq!(envtime::get_string("TEST_HELPER_SECRET_RUN_ENV"), Some(String::from("hunter2")));
+}
+
+#[cfg(feature = "json")]
+#[derive(Debug, PartialEq, serde::Deserialize)]
+struct Features {
+    search: bool,
+    export: bool,
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn json_tests() {
+    assert_eq!(envtime_json!("TEST_JSON_COMP_ENV", Features), Some(Features { search: true, export: false }));
+
+    assert_eq!(envtime_json!("TEST_JSON_RUN_ENV", Features), None);
+    env::set_var("TEST_JSON_RUN_ENV", r#" {"search": false, "export": true} "#);
+    assert_eq!(envtime_json!("TEST_JSON_RUN_ENV", Features), Some(Features { search: false, export: true }));
+    assert_eq!(envtime_json!("TEST_JSON_RUN_ENV", std::collections::BTreeMap<String, bool>).map(|map| map.len()), Some(2));
+    env::set_var("TEST_JSON_RUN_ENV", r#"{"search": true}"#);
+    assert_eq!(envtime_json!("TEST_JSON_RUN_ENV", Features), None);
+    env::set_var("TEST_JSON_RUN_ENV", r#"{"search": tru"#);
+    assert_eq!(envtime_json!("TEST_JSON_RUN_ENV", Features), None);
+}
+
+#[cfg(feature = "json")]
+#[test]
+#[should_panic(expected = "Cannot parse compilation env var TEST_JSON_MISMATCH_COMP_ENV as Features")]
+fn json_mismatch_tests() {
+    let _ = envtime_json!("TEST_JSON_MISMATCH_COMP_ENV", Features);
+}
+
+#[cfg(feature = "semver")]
+#[test]
+fn semver_tests() {
+    use semver::Version;
+
+    assert_eq!(envtime_semver!("TEST_SEMVER_COMP_ENV", "1.0.0"), Version::new(1, 4, 0));
+    assert_eq!(envtime_semver!("TEST_SEMVER_PRE_COMP_ENV", "1.0.0"), Version::parse("2.0.0-beta.2+build.5").unwrap());
+
+    assert_eq!(envtime_semver!("TEST_SEMVER_RUN_ENV", "1.2.3"), Version::new(1, 2, 3));
+    assert_eq!(envtime_semver!("TEST_SEMVER_RUN_ENV", "1.2.3-alpha"), Version::parse("1.2.3-alpha").unwrap());
+    env::set_var("TEST_SEMVER_RUN_ENV", " 3.1.4 ");
+    assert_eq!(envtime_semver!("TEST_SEMVER_RUN_ENV", "1.2.3"), Version::new(3, 1, 4));
+    for &value in &["3.1", "v3.1.4", "latest"] {
+        env::set_var("TEST_SEMVER_RUN_ENV", value);
+        assert_eq!(envtime_semver!("TEST_SEMVER_RUN_ENV", "1.2.3"), Version::new(1, 2, 3));
+    }
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn datetime_tests() {
+    use chrono::{DateTime, Utc};
+
+    let at = |secs| DateTime::<Utc>::from_timestamp(secs, 0).unwrap();
+    // TEST_DATETIME_COMP_ENV is set to "2024-03-01T14:30:00+02:00" at compile time
+    assert_eq!(envtime_datetime!("TEST_DATETIME_COMP_ENV", "2024-01-01T00:00:00Z"), at(1709296200));
+    const CUTOFF: DateTime<Utc> = envtime_datetime!("TEST_DATETIME_COMP_ENV", "2024-01-01T00:00:00Z");
+    assert_eq!(CUTOFF, at(1709296200));
+
+    assert_eq!(envtime_datetime!("TEST_DATETIME_RUN_ENV", "2024-01-01T00:00:00Z"), at(1704067200));
+    assert_eq!(
+        envtime_datetime!("TEST_DATETIME_RUN_ENV", "2024-01-01T00:00:00.25Z"),
+        DateTime::<Utc>::from_timestamp(1704067200, 250_000_000).unwrap()
+    );
+    env::set_var("TEST_DATETIME_RUN_ENV", " 2024-06-30T23:59:59-01:00 ");
+    assert_eq!(envtime_datetime!("TEST_DATETIME_RUN_ENV", "2024-01-01T00:00:00Z"), at(1719795599));
+    for &value in &["2024-06-30", "2024-06-30T23:59:59", "yesterday"] {
+        env::set_var("TEST_DATETIME_RUN_ENV", value);
+        assert_eq!(envtime_datetime!("TEST_DATETIME_RUN_ENV", "2024-01-01T00:00:00Z"), at(1704067200));
+    }
+}
+
+#[cfg(feature = "log")]
+#[test]
+fn log_level_tests() {
+    use log::LevelFilter;
+
+    // TEST_LOG_LEVEL_COMP_ENV is set to "Warn" at compile time
+    assert_eq!(envtime_log_level!("TEST_LOG_LEVEL_COMP_ENV", "info"), LevelFilter::Warn);
+    const LEVEL: LevelFilter = envtime_log_level!("TEST_LOG_LEVEL_COMP_ENV", "info");
+    assert_eq!(LEVEL, LevelFilter::Warn);
+
+    assert_eq!(envtime_log_level!("TEST_LOG_LEVEL_RUN_ENV", "info"), LevelFilter::Info);
+    assert_eq!(envtime_log_level!("TEST_LOG_LEVEL_RUN_ENV", "OFF"), LevelFilter::Off);
+    for &(value, expected) in &[
+        ("off", LevelFilter::Off),
+        ("error", LevelFilter::Error),
+        ("warn", LevelFilter::Warn),
+        ("info", LevelFilter::Info),
+        ("debug", LevelFilter::Debug),
+        ("trace", LevelFilter::Trace),
+        (" TRACE ", LevelFilter::Trace),
+        ("Debug", LevelFilter::Debug),
+    ] {
+        env::set_var("TEST_LOG_LEVEL_RUN_ENV", value);
+        assert_eq!(envtime_log_level!("TEST_LOG_LEVEL_RUN_ENV", "info"), expected);
+    }
+    for &value in &["warning", "verbose", "3", ""] {
+        env::set_var("TEST_LOG_LEVEL_RUN_ENV", value);
+        assert_eq!(envtime_log_level!("TEST_LOG_LEVEL_RUN_ENV", "info"), LevelFilter::Info);
+    }
+}
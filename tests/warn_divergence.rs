@@ -0,0 +1,55 @@
+#![cfg(feature = "warn-divergence")]
+
+use std::env;
+use std::process::Command;
+use envtime::*;
+
+// Only does something in the child process `divergence_tests` starts, to read the warnings from its stderr
+#[test]
+fn divergence_child() {
+    if env::var("TEST_DIVERGENCE_CHILD").is_err() {
+        return
+    }
+    for _ in 0..2 {
+        assert_eq!(envtime!("TEST_COMP_ENV"), Some(String::from("456")));
+    }
+    assert_eq!(envtime_def!("TEST_U8_COMP_ENV", 0u8), 12u8);
+    assert!(envtime_def!("TEST_BOOL_COMP_ENV", false));
+    assert_eq!(envtime_str!("TEST_STR_LOWER_COMP_ENV").as_deref(), Some("eu-west-1"));
+    assert_eq!(envtime_opt!("TEST_OPT_COMP_ENV", u16), Some(4242));
+    assert_eq!(envtime_vec!("TEST_VEC_INT_COMP_ENV", u16, delim = ';'), vec![8080, 443]);
+    assert_eq!(envtime_clamped!("TEST_CLAMPED_MID_COMP_ENV", 8usize, 1..=64), 16usize);
+    assert_eq!(envtime_cached!("TEST_TRIM_STR_COMP_ENV", "default", trim = true), "padded");
+    assert_eq!(envtime_cached!("TEST_BYTE_STR_COMP_ENV", b"MZ"), b" PK ");
+}
+
+#[test]
+fn divergence_tests() {
+    // TEST_COMP_ENV is set to 456 and TEST_U8_COMP_ENV to 12 at compile time
+    let output = Command::new(env::current_exe().unwrap())
+        .args(["divergence_child", "--exact", "--nocapture"])
+        .env("TEST_DIVERGENCE_CHILD", "1")
+        .env("TEST_COMP_ENV", "123")
+        .env("TEST_U8_COMP_ENV", " 12 ")
+        .env_remove("TEST_BOOL_COMP_ENV")
+        .env("TEST_STR_LOWER_COMP_ENV", "us-east-1")
+        .env("TEST_OPT_COMP_ENV", "4242")
+        .env("TEST_VEC_INT_COMP_ENV", "80")
+        .env("TEST_CLAMPED_MID_COMP_ENV", "32")
+        .env("TEST_TRIM_STR_COMP_ENV", "unpadded")
+        .env("TEST_BYTE_STR_COMP_ENV", "ZIP")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    // Warned about once per use, without the values, only when the runtime value differs
+    assert_eq!(stderr.matches("warning: env var TEST_COMP_ENV differs").count(), 1);
+    assert!(!stderr.contains("123") && !stderr.contains("456"));
+    assert!(!stderr.contains("TEST_U8_COMP_ENV"));
+    assert!(!stderr.contains("TEST_BOOL_COMP_ENV"));
+    // The typed macros are checked the same way
+    for name in ["TEST_STR_LOWER_COMP_ENV", "TEST_VEC_INT_COMP_ENV", "TEST_CLAMPED_MID_COMP_ENV", "TEST_TRIM_STR_COMP_ENV", "TEST_BYTE_STR_COMP_ENV"] {
+        assert_eq!(stderr.matches(&format!("warning: env var {} differs", name)).count(), 1, "{}", name);
+    }
+    assert!(!stderr.contains("TEST_OPT_COMP_ENV"));
+}